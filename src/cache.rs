@@ -4,50 +4,104 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Nombre d'entrées conservées par défaut avant d'évincer les moins récemment utilisées
+const DEFAULT_MAX_ENTRIES: usize = 200;
 
 #[derive(Clone)]
 pub struct Cache<T: Clone + Serialize + for<'de> Deserialize<'de>> {
     data: Arc<RwLock<HashMap<String, CacheEntry<T>>>>,
     cache_file: PathBuf,
     default_ttl: Duration,
+    max_entries: usize,
 }
 
 struct CacheEntry<T> {
     value: T,
     timestamp: Instant,
+    last_access: Instant,
+    /// Durée de vie propre à l'entrée, sinon celle du cache
+    ttl: Option<Duration>,
+}
+
+impl<T> CacheEntry<T> {
+    fn is_expired(&self, default_ttl: Duration) -> bool {
+        self.timestamp.elapsed() >= self.ttl.unwrap_or(default_ttl)
+    }
+}
+
+/// Format d'une entrée sur disque : la valeur et sa date d'écriture (secondes Unix)
+#[derive(Serialize, Deserialize)]
+struct StoredEntry<T> {
+    value: T,
+    saved_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ttl_secs: Option<u64>,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 impl<T: Clone + Serialize + for<'de> Deserialize<'de>> Cache<T> {
     pub fn new(cache_file: PathBuf, ttl: Duration) -> Self {
-        let data = Self::load_from_disk(&cache_file).unwrap_or_default();
-        Self {
+        let data = Self::load_from_disk(&cache_file, ttl).unwrap_or_default();
+        let cache = Self {
             data: Arc::new(RwLock::new(data)),
             cache_file,
             default_ttl: ttl,
-        }
+            max_entries: DEFAULT_MAX_ENTRIES,
+        };
+        // Réécrire le fichier sans les entrées périmées depuis la dernière session
+        cache.prune_expired();
+        cache
+    }
+
+    /// Définit le nombre maximum d'entrées conservées (au moins une)
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
     }
 
     pub fn get(&self, key: &str) -> Option<T> {
-        let cache = self.data.read();
-        cache.get(key).and_then(|entry| {
-            if entry.timestamp.elapsed() < self.default_ttl {
-                Some(entry.value.clone())
-            } else {
+        let mut cache = self.data.write();
+        cache.get_mut(key).and_then(|entry| {
+            if entry.is_expired(self.default_ttl) {
                 None
+            } else {
+                entry.last_access = Instant::now();
+                Some(entry.value.clone())
             }
         })
     }
 
     pub fn set(&self, key: String, value: T) {
+        self.insert(key, value, None);
+    }
+
+    /// Comme `set`, avec une durée de vie propre à cette entrée
+    pub fn set_with_ttl(&self, key: String, value: T, ttl: Duration) {
+        self.insert(key, value, Some(ttl));
+    }
+
+    fn insert(&self, key: String, value: T, ttl: Option<Duration>) {
         let mut cache = self.data.write();
+        Self::remove_expired(&mut cache, self.default_ttl);
+        let now = Instant::now();
         cache.insert(
             key,
             CacheEntry {
                 value,
-                timestamp: Instant::now(),
+                timestamp: now,
+                last_access: now,
+                ttl,
             },
         );
+        Self::evict_least_recently_used(&mut cache, self.max_entries);
         drop(cache);
 
         if let Err(error) = self.save_to_disk() {
@@ -55,17 +109,84 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de>> Cache<T> {
         }
     }
 
-    fn load_from_disk(path: &PathBuf) -> Result<HashMap<String, CacheEntry<T>>> {
+    /// Supprime une seule entrée, en mémoire et sur disque
+    pub fn invalidate(&self, key: &str) {
+        if self.data.write().remove(key).is_none() {
+            return;
+        }
+
+        if let Err(error) = self.save_to_disk() {
+            tracing::warn!("Failed to save cache to disk: {}", error);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.data.write().clear();
+
+        if let Err(error) = self.save_to_disk() {
+            tracing::warn!("Failed to save cache to disk: {}", error);
+        }
+    }
+
+    /// Supprime les entrées expirées, en mémoire et sur disque
+    pub fn prune_expired(&self) {
+        let removed = Self::remove_expired(&mut self.data.write(), self.default_ttl);
+        if removed == 0 {
+            return;
+        }
+
+        tracing::debug!("Pruned {} expired cache entries", removed);
+        if let Err(error) = self.save_to_disk() {
+            tracing::warn!("Failed to save cache to disk: {}", error);
+        }
+    }
+
+    /// Retire les entrées expirées et retourne leur nombre
+    fn remove_expired(cache: &mut HashMap<String, CacheEntry<T>>, ttl: Duration) -> usize {
+        let before = cache.len();
+        cache.retain(|_, entry| !entry.is_expired(ttl));
+        before - cache.len()
+    }
+
+    /// Retire les entrées les moins récemment lues ou écrites au-delà de `max_entries`
+    fn evict_least_recently_used(cache: &mut HashMap<String, CacheEntry<T>>, max_entries: usize) {
+        while cache.len() > max_entries {
+            let Some(oldest) = cache
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            cache.remove(&oldest);
+        }
+    }
+
+    fn load_from_disk(path: &PathBuf, ttl: Duration) -> Result<HashMap<String, CacheEntry<T>>> {
         let content = std::fs::read_to_string(path)?;
-        let data: HashMap<String, T> = serde_json::from_str(&content)?;
+        let data: HashMap<String, StoredEntry<T>> = serde_json::from_str(&content)?;
+        let now = unix_now();
         Ok(data
             .into_iter()
-            .map(|(k, v)| {
+            .map(|(k, entry)| {
+                // Reconstruire l'âge réel de l'entrée; les entrées expirées sont gardées
+                // à l'âge limite pour que `prune_expired` les retire aussi du fichier
+                let entry_ttl = entry.ttl_secs.map(Duration::from_secs);
+                let lifetime = entry_ttl.unwrap_or(ttl);
+                let age = Duration::from_secs(now.saturating_sub(entry.saved_at)).min(lifetime);
+                // Entrée plus ancienne que le démarrage de la machine (`Instant` ne remonte
+                // pas avant): la dater de maintenant avec sa seule durée de vie restante
+                let (timestamp, entry_ttl) = match Instant::now().checked_sub(age) {
+                    Some(timestamp) => (timestamp, entry_ttl),
+                    None => (Instant::now(), Some(lifetime - age)),
+                };
                 (
                     k,
                     CacheEntry {
-                        value: v,
-                        timestamp: Instant::now(),
+                        value: entry.value,
+                        timestamp,
+                        last_access: timestamp,
+                        ttl: entry_ttl,
                     },
                 )
             })
@@ -74,12 +195,184 @@ impl<T: Clone + Serialize + for<'de> Deserialize<'de>> Cache<T> {
 
     fn save_to_disk(&self) -> Result<()> {
         let cache = self.data.read();
-        let data: HashMap<String, T> = cache
+        let now = unix_now();
+        let data: HashMap<String, StoredEntry<T>> = cache
             .iter()
-            .map(|(k, v)| (k.clone(), v.value.clone()))
+            .map(|(k, v)| {
+                (
+                    k.clone(),
+                    StoredEntry {
+                        value: v.value.clone(),
+                        saved_at: now.saturating_sub(v.timestamp.elapsed().as_secs()),
+                        ttl_secs: v.ttl.map(|ttl| ttl.as_secs()),
+                    },
+                )
+            })
             .collect();
         let content = serde_json::to_string_pretty(&data)?;
         std::fs::write(&self.cache_file, content)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let cache: Cache<String> = Cache::new(path.clone(), Duration::from_secs(60));
+
+        cache.set("a".to_string(), "value".to_string());
+        assert_eq!(cache.get("a"), Some("value".to_string()));
+
+        cache.clear();
+        assert_eq!(cache.get("a"), None);
+
+        // Le fichier sur disque doit aussi être vidé
+        let reloaded: Cache<String> = Cache::new(path, Duration::from_secs(60));
+        assert_eq!(reloaded.get("a"), None);
+
+        // Vider un cache déjà vide ne doit pas paniquer
+        cache.clear();
+    }
+
+    #[test]
+    fn test_cache_invalidate() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let cache: Cache<String> = Cache::new(path.clone(), Duration::from_secs(60));
+
+        cache.set("chan-a#1-30".to_string(), "a".to_string());
+        cache.set("chan-b#1-30".to_string(), "b".to_string());
+        cache.invalidate("chan-a#1-30");
+
+        assert_eq!(cache.get("chan-a#1-30"), None);
+        assert_eq!(cache.get("chan-b#1-30"), Some("b".to_string()));
+
+        let reloaded: Cache<String> = Cache::new(path, Duration::from_secs(60));
+        assert_eq!(reloaded.get("chan-a#1-30"), None);
+        assert_eq!(reloaded.get("chan-b#1-30"), Some("b".to_string()));
+
+        // Une clé absente est ignorée
+        cache.invalidate("unknown");
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let cache: Cache<String> = Cache::new(path.clone(), Duration::from_secs(60)).max_entries(2);
+
+        cache.set("a".to_string(), "1".to_string());
+        std::thread::sleep(Duration::from_millis(2));
+        cache.set("b".to_string(), "2".to_string());
+        std::thread::sleep(Duration::from_millis(2));
+        // Lire "a" le rend plus récent que "b"
+        assert!(cache.get("a").is_some());
+        std::thread::sleep(Duration::from_millis(2));
+        cache.set("c".to_string(), "3".to_string());
+
+        assert_eq!(cache.get("b"), None);
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+
+        // L'entrée évincée a aussi disparu du fichier
+        let reloaded: Cache<String> = Cache::new(path, Duration::from_secs(60));
+        assert_eq!(reloaded.get("b"), None);
+        assert_eq!(reloaded.get("a"), Some("1".to_string()));
+    }
+
+    #[test]
+    fn test_cache_timestamps_survive_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+
+        let old = unix_now() - 120;
+        let content = format!(
+            r#"{{"fresh": {{"value": "a", "saved_at": {}}}, "stale": {{"value": "b", "saved_at": {}}}}}"#,
+            unix_now(),
+            old
+        );
+        std::fs::write(&path, content).unwrap();
+
+        let cache: Cache<String> = Cache::new(path.clone(), Duration::from_secs(60));
+        assert_eq!(cache.get("fresh"), Some("a".to_string()));
+        assert_eq!(cache.get("stale"), None);
+
+        // L'entrée périmée est retirée du fichier dès le chargement
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("fresh"));
+        assert!(!content.contains("stale"));
+    }
+
+    #[test]
+    fn test_cache_keeps_entries_older_than_uptime() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+
+        // Écrites il y a 40 ans, bien avant le démarrage de la machine
+        const YEAR: u64 = 365 * 24 * 3600;
+        let content = format!(
+            r#"{{"kept": {{"value": "a", "saved_at": {saved_at}, "ttl_secs": {}}},
+                "stale": {{"value": "b", "saved_at": {saved_at}}}}}"#,
+            50 * YEAR,
+            saved_at = unix_now() - 40 * YEAR,
+        );
+        std::fs::write(&path, content).unwrap();
+
+        let cache: Cache<String> = Cache::new(path, Duration::from_secs(60));
+        assert_eq!(cache.get("kept"), Some("a".to_string()));
+        assert_eq!(cache.get("stale"), None);
+
+        // Seule la durée de vie restante est accordée, que l'horloge `Instant` du
+        // système remonte ou non avant le démarrage
+        let data = cache.data.read();
+        let remaining = data["kept"].ttl.unwrap() - data["kept"].timestamp.elapsed();
+        assert!(remaining <= Duration::from_secs(10 * YEAR));
+        assert!(remaining > Duration::from_secs(9 * YEAR));
+    }
+
+    #[test]
+    fn test_cache_per_key_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let cache: Cache<String> = Cache::new(path.clone(), Duration::from_millis(20));
+
+        cache.set("short".to_string(), "a".to_string());
+        cache.set_with_ttl(
+            "long".to_string(),
+            "b".to_string(),
+            Duration::from_secs(3600),
+        );
+        std::thread::sleep(Duration::from_millis(30));
+
+        assert_eq!(cache.get("short"), None);
+        assert_eq!(cache.get("long"), Some("b".to_string()));
+
+        // La durée de vie propre est conservée sur disque
+        let reloaded: Cache<String> = Cache::new(path, Duration::from_millis(20));
+        assert_eq!(reloaded.get("long"), Some("b".to_string()));
+    }
+
+    #[test]
+    fn test_prune_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache: Cache<String> =
+            Cache::new(dir.path().join("cache.json"), Duration::from_millis(20));
+
+        cache.set("a".to_string(), "1".to_string());
+        std::thread::sleep(Duration::from_millis(30));
+        cache.set("b".to_string(), "2".to_string());
+
+        // "a" a expiré et a été retirée lors de l'écriture de "b"
+        assert_eq!(cache.data.read().len(), 1);
+        assert_eq!(cache.get("b"), Some("2".to_string()));
+
+        std::thread::sleep(Duration::from_millis(30));
+        cache.prune_expired();
+        assert!(cache.data.read().is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Téléchargeur automatique de vidéos YouTube et Twitch
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Cli {
+    /// Fichier de configuration à utiliser à la place de l'emplacement par défaut
+    #[arg(long, value_name = "FICHIER")]
+    pub config: Option<PathBuf>,
+
+    /// Ajoute une chaîne, une playlist ou la chaîne d'une vidéo sans ouvrir la fenêtre
+    /// (peut être répété)
+    #[arg(long, value_name = "URL")]
+    pub add: Vec<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Télécharge une vidéo sans ouvrir la fenêtre, en affichant la progression.
+    /// Le code de sortie indique le résultat (0 = téléchargée).
+    Download {
+        /// URL de la vidéo
+        url: String,
+        /// Chaîne de la vidéo, qui donne son dossier de destination
+        #[arg(long)]
+        channel: String,
+        /// Nom du fichier sans extension (titre de la vidéo par défaut)
+        #[arg(long, value_name = "FICHIER")]
+        name: Option<String>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let cli = Cli::try_parse_from([
+            "ndownloader",
+            "--config",
+            "/tmp/config.json",
+            "--add",
+            "https://www.twitch.tv/streamer",
+            "--add",
+            "https://www.youtube.com/@handle",
+        ])
+        .unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("/tmp/config.json")));
+        assert_eq!(cli.add.len(), 2);
+
+        let cli = Cli::try_parse_from([
+            "ndownloader",
+            "download",
+            "https://www.youtube.com/watch?v=abc",
+            "--channel",
+            "handle",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Download { channel, name: None, .. }) if channel == "handle"
+        ));
+        assert!(Cli::try_parse_from(["ndownloader", "download", "https://youtu.be/abc"]).is_err());
+
+        // Un argument inconnu est une erreur, pas ignoré
+        assert!(Cli::try_parse_from(["ndownloader", "--unknown"]).is_err());
+        assert!(Cli::try_parse_from(["ndownloader", "extra"]).is_err());
+    }
+}
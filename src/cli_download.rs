@@ -0,0 +1,154 @@
+use crate::config::Config;
+use crate::database::{self, Database};
+use crate::downloader_queue::{DownloadOptions, DownloadQueue, DownloadStatus};
+use crate::filename;
+use crate::paths;
+use crate::scanner::VideoScanner;
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Intervalle de lecture de la progression
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Code de sortie correspondant au statut final d'un téléchargement
+pub fn exit_code(status: &DownloadStatus) -> ExitCode {
+    if *status == DownloadStatus::Completed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Télécharge une vidéo sans interface, par la même file et les mêmes réglages que
+/// l'application, dans `{stockage}/{chaîne}/`. La progression est écrite sur la sortie standard.
+pub fn download(url: &str, channel: &str, name: Option<&str>) -> Result<DownloadStatus> {
+    let config = Config::load();
+    let database = match Database::open(&paths::database_file()) {
+        Ok(database) => Some(Arc::new(database)),
+        Err(error) => {
+            tracing::warn!("Failed to open download history: {}", error);
+            None
+        }
+    };
+    let scanner = VideoScanner::from_config(&config).database(database.clone());
+
+    let (queue, worker) = DownloadQueue::with_worker();
+    queue.configure(&config);
+    smol::spawn(worker).detach();
+
+    smol::block_on(async {
+        let video = scanner.video_metadata(url).await?;
+        if let Some(path) = scanner
+            .is_video_downloaded(channel, &video.id, video.duration)
+            .await?
+        {
+            println!("Déjà téléchargée: {path}");
+            return Ok(DownloadStatus::Completed);
+        }
+
+        let file_name = match name {
+            Some(name) => filename::sanitize(name),
+            None => filename::from_template(
+                &config.filename_template,
+                &filename::TemplateValues {
+                    title: &video.title,
+                    id: &video.id,
+                    date: video.upload_date.as_deref(),
+                    channel,
+                },
+            ),
+        };
+        let file_name = match (file_name.is_empty(), name) {
+            (false, _) => file_name,
+            (true, None) => video.id.clone(),
+            (true, Some(_)) => {
+                bail!("Nom de fichier invalide : utilisez au moins une lettre ou un chiffre")
+            }
+        };
+        let storage = scanner.storage_for_download()?;
+        let quality = config.default_quality;
+        let container = config.default_container;
+        let output_path = PathBuf::from(format!(
+            "{}/{}/{}.{}",
+            storage.path,
+            filename::directory_name(channel),
+            file_name,
+            quality.extension(container)
+        ));
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let output_path = filename::unique_path(&output_path, |path| path.exists());
+
+        println!(
+            "Téléchargement de {} vers {}",
+            video.title,
+            output_path.display()
+        );
+        let mut completion = queue
+            .add_download(
+                video.id.clone(),
+                url.to_string(),
+                file_name,
+                output_path.clone(),
+                DownloadOptions {
+                    quality,
+                    container,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        // Une ligne par pourcent gagné, lisible dans un journal comme dans un terminal
+        let mut last_percent = None;
+        let status = loop {
+            smol::Timer::after(PROGRESS_INTERVAL).await;
+            if let Some(task) = queue.get_tasks().into_iter().find(|t| t.video_url == url) {
+                let percent = (task.progress * 100.0) as u32;
+                if task.status == DownloadStatus::Downloading && last_percent != Some(percent) {
+                    last_percent = Some(percent);
+                    let details: Vec<String> =
+                        [task.speed, task.eta.map(|eta| format!("ETA {eta}"))]
+                            .into_iter()
+                            .flatten()
+                            .collect();
+                    println!("{percent:>3}% {}", details.join(" · "));
+                }
+            }
+            match completion.try_recv() {
+                Ok(Some(status)) => break status,
+                Ok(None) => {}
+                Err(_) => {
+                    break DownloadStatus::Failed(
+                        "La file de téléchargement s'est arrêtée".to_string(),
+                    )
+                }
+            }
+        };
+
+        match &status {
+            DownloadStatus::Completed => {
+                println!("Terminé: {}", output_path.display());
+                if let Some(database) = &database {
+                    let record = database::Video {
+                        id: video.id,
+                        channel: filename::directory_name(channel),
+                        title: video.title,
+                        path: output_path,
+                        duration: video.duration,
+                        downloaded_at: chrono::Utc::now().timestamp(),
+                    };
+                    if let Err(error) = database.record(&record) {
+                        tracing::warn!("Failed to record download: {}", error);
+                    }
+                }
+            }
+            DownloadStatus::Failed(error) => eprintln!("Échec du téléchargement: {error}"),
+            _ => eprintln!("Téléchargement annulé"),
+        }
+        Ok(status)
+    })
+}
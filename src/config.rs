@@ -0,0 +1,233 @@
+use crate::downloader_queue::{DownloadQuality, VideoContainer};
+use crate::filename;
+use crate::paths;
+use crate::scanner::DEFAULT_VIDEO_EXTENSIONS;
+use anyhow::Result;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Dossiers de stockage des vidéos (vide = chemins par défaut)
+    pub storage_paths: Vec<String>,
+    /// Nombre de vidéos récupérées par scan de chaîne
+    pub scan_limit: usize,
+    /// Profondeur de sous-dossiers parcourue pour retrouver les vidéos téléchargées
+    pub scan_depth: usize,
+    /// Extensions des fichiers comparés aux vidéos scannées
+    pub video_extensions: Vec<String>,
+    /// Écart de durée toléré (secondes) pour reconnaître une vidéo sans identifiant ni URL source
+    pub duration_tolerance_secs: usize,
+    /// Modèle des noms de fichier proposés, avec les jetons `{title}`, `{id}`, `{date}`
+    /// et `{channel}` (voir `filename::from_template`)
+    pub filename_template: String,
+    /// Qualité présélectionnée dans la fenêtre de téléchargement
+    pub default_quality: DownloadQuality,
+    /// Conteneur présélectionné pour les vidéos (mp4, mkv ou webm)
+    pub default_container: VideoContainer,
+    /// Langue des sous-titres téléchargés (code yt-dlp, ex: "en", "fr")
+    pub subtitle_language: String,
+    /// Nombre de téléchargements simultanés, les suivants sont mis en attente
+    pub max_concurrent_downloads: usize,
+    /// Pause minimale (secondes) entre deux appels à yt-dlp pour lister ou décrire des
+    /// vidéos, qui passent un par un pour éviter les refus pour trop de requêtes
+    pub min_scan_interval_secs: usize,
+    /// Nombre de nouveaux essais après une erreur réseau
+    pub max_download_retries: usize,
+    /// Débit maximal des téléchargements (ex: "2M", "500K"), illimité si absent
+    pub download_rate_limit: Option<String>,
+    /// Fichier cookies.txt (format Netscape) passé à yt-dlp avec `--cookies`, pour les
+    /// vidéos soumises à l'âge ou réservées aux abonnés.
+    /// `--cookies-from-browser` pourrait à terme éviter l'export manuel.
+    pub cookies_file: Option<String>,
+    /// Palette de couleurs de l'interface
+    pub theme: ThemeMode,
+    /// Intervalle de vérification des nouvelles vidéos, en minutes (0 = désactivé)
+    pub monitor_interval_minutes: usize,
+    /// Espace libre minimal sur le disque choisi avant un téléchargement, en Mo (0 = désactivé)
+    pub min_free_space_mb: usize,
+    /// Intégrer la miniature aux fichiers téléchargés (`--embed-thumbnail`)
+    pub embed_thumbnail: bool,
+}
+
+/// Thème de l'interface, modifiable depuis les paramètres
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 2] = [ThemeMode::Dark, ThemeMode::Light];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeMode::Dark => "Sombre",
+            ThemeMode::Light => "Clair",
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            storage_paths: Vec::new(),
+            scan_limit: 30,
+            scan_depth: 3,
+            video_extensions: DEFAULT_VIDEO_EXTENSIONS
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+            duration_tolerance_secs: 5,
+            filename_template: filename::DEFAULT_TEMPLATE.to_string(),
+            default_quality: DownloadQuality::default(),
+            default_container: VideoContainer::default(),
+            subtitle_language: "en".to_string(),
+            max_concurrent_downloads: 2,
+            min_scan_interval_secs: 2,
+            max_download_retries: 3,
+            download_rate_limit: None,
+            cookies_file: None,
+            theme: ThemeMode::default(),
+            monitor_interval_minutes: 30,
+            min_free_space_mb: 1024,
+            embed_thumbnail: false,
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        match std::fs::read_to_string(paths::config_file()) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(config) => config,
+                Err(error) => {
+                    tracing::warn!("Failed to parse config file: {}", error);
+                    Self::default()
+                }
+            },
+            Err(error) => {
+                tracing::debug!("No config file found: {}", error);
+                Self::default()
+            }
+        }
+    }
+
+    /// Relit le fichier de configuration. Contrairement à `load`, un fichier illisible
+    /// ou invalide est une erreur plutôt qu'un retour aux valeurs par défaut.
+    pub fn reload() -> Result<Self> {
+        let content = std::fs::read_to_string(paths::config_file())?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Sépare une configuration relue de ce qui ne peut pas changer tout de suite:
+    /// les dossiers de stockage restent les mêmes tant que des téléchargements sont en cours.
+    /// Retourne la configuration à appliquer et les dossiers mis de côté.
+    pub fn apply_reloaded(
+        &self,
+        mut reloaded: Config,
+        downloads_active: bool,
+    ) -> (Config, Option<Vec<String>>) {
+        if !downloads_active || reloaded.storage_paths == self.storage_paths {
+            return (reloaded, None);
+        }
+        let deferred = std::mem::replace(&mut reloaded.storage_paths, self.storage_paths.clone());
+        (reloaded, Some(deferred))
+    }
+
+    /// Extensions reconnues au scan: celles configurées, plus celle du conteneur choisi
+    /// pour que les fichiers téléchargés soient toujours retrouvés
+    pub fn scanned_extensions(&self) -> Vec<String> {
+        let mut extensions = self.video_extensions.clone();
+        let container = self.default_container.extension();
+        if !extensions
+            .iter()
+            .any(|ext| ext.trim_start_matches('.').eq_ignore_ascii_case(container))
+        {
+            extensions.push(container.to_string());
+        }
+        extensions
+    }
+
+    /// Surveille le fichier de configuration. `on_change` est appelé depuis le thread du
+    /// watcher à chaque écriture, y compris celles de l'application elle-même.
+    /// Le dossier est surveillé plutôt que le fichier, que les éditeurs remplacent souvent.
+    pub fn watch(on_change: impl Fn() + Send + 'static) -> notify::Result<RecommendedWatcher> {
+        let path = paths::config_file();
+        let file_name = path.file_name().map(|name| name.to_os_string());
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<_>| {
+            let event: notify::Event = match event {
+                Ok(event) => event,
+                Err(error) => {
+                    tracing::warn!("Config watcher error: {}", error);
+                    return;
+                }
+            };
+            let written = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_));
+            if written
+                && event
+                    .paths
+                    .iter()
+                    .any(|changed| changed.file_name() == file_name.as_deref())
+            {
+                on_change();
+            }
+        })?;
+        if let Some(directory) = path.parent() {
+            watcher.watch(directory, RecursiveMode::NonRecursive)?;
+        }
+        Ok(watcher)
+    }
+
+    pub fn save(&self) {
+        match serde_json::to_string_pretty(self) {
+            Ok(content) => {
+                if let Err(error) = std::fs::write(paths::config_file(), content) {
+                    tracing::error!("Failed to save config file: {}", error);
+                }
+            }
+            Err(error) => {
+                tracing::error!("Failed to serialize config: {}", error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_reloaded_defers_storage_paths_during_downloads() {
+        let current = Config::default();
+        let mut edited = current.clone();
+        edited.storage_paths = vec!["/mnt/new".to_string()];
+        edited.scan_limit = 50;
+
+        let (applied, deferred) = current.apply_reloaded(edited.clone(), true);
+        assert_eq!(applied.scan_limit, 50);
+        assert_eq!(applied.storage_paths, current.storage_paths);
+        assert_eq!(deferred, Some(vec!["/mnt/new".to_string()]));
+
+        // Sans téléchargement en cours, tout s'applique
+        assert_eq!(
+            current.apply_reloaded(edited.clone(), false),
+            (edited, None)
+        );
+    }
+
+    #[test]
+    fn test_scanned_extensions_include_container() {
+        let mut config = Config {
+            video_extensions: vec!["mp4".to_string(), ".MKV".to_string()],
+            ..Config::default()
+        };
+        config.default_container = VideoContainer::Mkv;
+        assert_eq!(config.scanned_extensions(), ["mp4", ".MKV"]);
+
+        config.default_container = VideoContainer::Webm;
+        assert_eq!(config.scanned_extensions(), ["mp4", ".MKV", "webm"]);
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::Result;
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::path::{Path, PathBuf};
+
+/// Vidéo téléchargée, enregistrée à la fin de son téléchargement
+#[derive(Debug, Clone, PartialEq)]
+pub struct Video {
+    pub id: String,
+    pub channel: String,
+    pub title: String,
+    pub path: PathBuf,
+    pub duration: Option<f64>,
+    /// Date de fin du téléchargement (secondes Unix)
+    pub downloaded_at: i64,
+}
+
+impl Video {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get("id")?,
+            channel: row.get("channel")?,
+            title: row.get("title")?,
+            path: PathBuf::from(row.get::<_, String>("path")?),
+            duration: row.get("duration")?,
+            downloaded_at: row.get("downloaded_at")?,
+        })
+    }
+}
+
+/// Échappe les jokers de LIKE (`%`, `_`) pour chercher le texte tel quel
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS videos (
+        id TEXT PRIMARY KEY NOT NULL,
+        channel TEXT NOT NULL,
+        title TEXT NOT NULL,
+        path TEXT NOT NULL,
+        duration REAL,
+        downloaded_at INTEGER NOT NULL
+    );
+";
+
+/// Historique des téléchargements, pour reconnaître une vidéo sans parcourir les disques
+pub struct Database {
+    connection: Mutex<Connection>,
+}
+
+impl Database {
+    /// Ouvre la base (créée si elle n'existe pas encore)
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Enregistre un téléchargement, en remplaçant une fiche plus ancienne de la même vidéo
+    pub fn record(&self, video: &Video) -> Result<()> {
+        self.connection.lock().execute(
+            "INSERT OR REPLACE INTO videos (id, channel, title, path, duration, downloaded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                video.id,
+                video.channel,
+                video.title,
+                video.path.to_string_lossy(),
+                video.duration,
+                video.downloaded_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Fiche d'une vidéo téléchargée
+    pub fn find(&self, video_id: &str) -> Result<Option<Video>> {
+        let video = self
+            .connection
+            .lock()
+            .query_row(
+                "SELECT * FROM videos WHERE id = ?1",
+                params![video_id],
+                Video::from_row,
+            )
+            .optional()?;
+        Ok(video)
+    }
+
+    /// Téléchargements dont le titre ou la chaîne contient le texte cherché (sans tenir
+    /// compte de la casse), du plus récent au plus ancien. Une recherche vide les liste tous.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<Video>> {
+        let pattern = format!("%{}%", escape_like(query.trim()));
+        let connection = self.connection.lock();
+        let mut statement = connection.prepare(
+            "SELECT * FROM videos
+             WHERE title LIKE ?1 ESCAPE '\\' OR channel LIKE ?1 ESCAPE '\\'
+             ORDER BY downloaded_at DESC
+             LIMIT ?2",
+        )?;
+        let videos = statement
+            .query_map(params![pattern, limit as i64], Video::from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(videos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(id: &str, downloaded_at: i64) -> Video {
+        Video {
+            id: id.to_string(),
+            channel: "chan".to_string(),
+            title: format!("Vidéo {id}"),
+            path: PathBuf::from(format!("/videos/chan/{id}.mp4")),
+            duration: Some(63.5),
+            downloaded_at,
+        }
+    }
+
+    #[test]
+    fn test_record_and_find() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = Database::open(&dir.path().join("downloads.db")).unwrap();
+
+        database.record(&video("abc", 10)).unwrap();
+        assert_eq!(database.find("abc").unwrap(), Some(video("abc", 10)));
+        assert_eq!(database.find("other").unwrap(), None);
+
+        // Un nouveau téléchargement remplace l'ancienne fiche
+        let mut again = video("abc", 20);
+        again.path = PathBuf::from("/videos/chan/abc (2).mp4");
+        database.record(&again).unwrap();
+        assert_eq!(database.find("abc").unwrap(), Some(again));
+    }
+
+    #[test]
+    fn test_search_title_or_channel() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = Database::open(&dir.path().join("downloads.db")).unwrap();
+        let mut recipe = video("a", 1);
+        recipe.title = "Recette du pain".to_string();
+        let mut other = video("b", 3);
+        other.channel = "Boulangerie".to_string();
+        let mut percent = video("c", 2);
+        percent.title = "100% réussite".to_string();
+        for video in [&recipe, &other, &percent] {
+            database.record(video).unwrap();
+        }
+
+        let ids = |query: &str| -> Vec<String> {
+            database
+                .search(query, 10)
+                .unwrap()
+                .into_iter()
+                .map(|video| video.id)
+                .collect()
+        };
+        assert_eq!(ids("PAIN"), ["a"]);
+        assert_eq!(ids("boulanger"), ["b"]);
+        // Les jokers de LIKE sont cherchés tels quels
+        assert_eq!(ids("%"), ["c"]);
+        assert_eq!(ids("_"), Vec::<String>::new());
+        // Recherche vide: tout, du plus récent au plus ancien
+        assert_eq!(ids(" "), ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_records_survive_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("downloads.db");
+        Database::open(&path)
+            .unwrap()
+            .record(&video("abc", 10))
+            .unwrap();
+
+        let database = Database::open(&path).unwrap();
+        assert_eq!(database.find("abc").unwrap(), Some(video("abc", 10)));
+    }
+}
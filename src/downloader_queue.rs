@@ -1,12 +1,21 @@
+use crate::config::Config;
+use crate::scanner;
 use anyhow::Result;
-use futures::channel::mpsc;
+use futures::channel::{mpsc, oneshot};
+use futures::future::{self, Either};
+use futures::stream::FuturesUnordered;
 use futures::{SinkExt, StreamExt};
 use gpui::AppContext;
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 use smol::io::{AsyncBufReadExt, BufReader};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct DownloadTask {
@@ -14,169 +23,850 @@ pub struct DownloadTask {
     pub video_url: String,
     pub title: String,
     pub output_path: PathBuf,
+    pub options: DownloadOptions,
     pub status: DownloadStatus,
     pub progress: f32,
     pub speed: Option<String>,
     pub eta: Option<String>,
 }
 
+#[cfg(test)]
+impl DownloadTask {
+    /// Tâche de test dont l'identifiant, l'URL et le titre valent `name`,
+    /// enregistrée dans `/videos/chan/<name>.mp4`
+    pub fn for_test(name: &str, status: DownloadStatus) -> Self {
+        Self {
+            video_id: name.to_string(),
+            video_url: name.to_string(),
+            title: name.to_string(),
+            output_path: PathBuf::from(format!("/videos/chan/{name}.mp4")),
+            options: DownloadOptions::default(),
+            status,
+            progress: 0.0,
+            speed: None,
+            eta: None,
+        }
+    }
+}
+
+/// Options passées à yt-dlp pour un téléchargement
+#[derive(Debug, Clone, Default)]
+pub struct DownloadOptions {
+    pub quality: DownloadQuality,
+    /// Conteneur du fichier vidéo (ignoré en audio seul)
+    pub container: VideoContainer,
+    /// Langue des sous-titres à télécharger et intégrer, si demandés
+    pub subtitles: Option<String>,
+    /// Débit maximal au format yt-dlp (ex: "2M", "500K")
+    pub rate_limit: Option<String>,
+    /// Fichier de cookies pour les contenus nécessitant une connexion
+    pub cookies_file: Option<PathBuf>,
+    /// Intégrer la miniature au fichier (ignoré si le conteneur ne le permet pas)
+    pub embed_thumbnail: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum DownloadQuality {
+    #[default]
+    Best,
+    P1080,
+    P720,
+    AudioOnly,
+}
+
+impl DownloadQuality {
+    pub const ALL: [DownloadQuality; 4] = [
+        DownloadQuality::Best,
+        DownloadQuality::P1080,
+        DownloadQuality::P720,
+        DownloadQuality::AudioOnly,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DownloadQuality::Best => "Meilleure",
+            DownloadQuality::P1080 => "1080p",
+            DownloadQuality::P720 => "720p",
+            DownloadQuality::AudioOnly => "Audio (mp3)",
+        }
+    }
+
+    /// Extension du fichier produit dans le conteneur choisi
+    pub fn extension(&self, container: VideoContainer) -> &'static str {
+        match self {
+            DownloadQuality::AudioOnly => "mp3",
+            _ => container.extension(),
+        }
+    }
+
+    /// Arguments de sélection de format pour yt-dlp
+    fn format_args(&self, container: VideoContainer) -> Vec<&'static str> {
+        // Hors mp4, les flux VP9/Opus sont gardés tels quels plutôt que ré-encodés
+        let any_codec = container != VideoContainer::Mp4;
+        let selector = match self {
+            DownloadQuality::AudioOnly => {
+                return vec![
+                    "-f",
+                    "bestaudio",
+                    "--extract-audio",
+                    "--audio-format",
+                    "mp3",
+                ];
+            }
+            DownloadQuality::Best if any_codec => "bestvideo+bestaudio/best",
+            DownloadQuality::Best => "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]",
+            DownloadQuality::P1080 if any_codec => {
+                "bestvideo[height<=1080]+bestaudio/best[height<=1080]"
+            }
+            DownloadQuality::P1080 => {
+                "bestvideo[height<=1080][ext=mp4]+bestaudio[ext=m4a]/best[height<=1080][ext=mp4]"
+            }
+            DownloadQuality::P720 if any_codec => {
+                "bestvideo[height<=720]+bestaudio/best[height<=720]"
+            }
+            DownloadQuality::P720 => {
+                "bestvideo[height<=720][ext=mp4]+bestaudio[ext=m4a]/best[height<=720][ext=mp4]"
+            }
+        };
+        let extension = container.extension();
+        vec![
+            "-f",
+            selector,
+            "--merge-output-format",
+            extension,
+            "--remux-video",
+            extension,
+        ]
+    }
+}
+
+/// Conteneur des fichiers vidéo téléchargés
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum VideoContainer {
+    #[default]
+    Mp4,
+    Mkv,
+    Webm,
+}
+
+impl VideoContainer {
+    pub const ALL: [VideoContainer; 3] = [
+        VideoContainer::Mp4,
+        VideoContainer::Mkv,
+        VideoContainer::Webm,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VideoContainer::Mp4 => "MP4",
+            VideoContainer::Mkv => "MKV",
+            VideoContainer::Webm => "WebM",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            VideoContainer::Mp4 => "mp4",
+            VideoContainer::Mkv => "mkv",
+            VideoContainer::Webm => "webm",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum DownloadStatus {
     Queued,
     Downloading,
     Completed,
     Failed(String),
+    Cancelled,
+}
+
+impl DownloadStatus {
+    /// La tâche est terminée (succès, échec ou annulation)
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, DownloadStatus::Queued | DownloadStatus::Downloading)
+    }
+}
+
+/// Tâche en attente, avec le canal de notification du statut final
+type QueuedDownload = (DownloadTask, oneshot::Sender<DownloadStatus>);
+
+/// Déplacement d'une tâche dans la file d'attente (la première démarre en premier)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueueMove {
+    Up,
+    Down,
+    /// Démarrer dès qu'une place se libère
+    First,
+}
+
+impl QueueMove {
+    /// Nouvelle position d'une tâche, `None` si elle ne peut pas bouger dans ce sens
+    fn target(self, index: usize, len: usize) -> Option<usize> {
+        let target = match self {
+            QueueMove::Up | QueueMove::First if index == 0 => return None,
+            QueueMove::Up => index - 1,
+            QueueMove::Down => index + 1,
+            QueueMove::First => 0,
+        };
+        (target < len).then_some(target)
+    }
+}
+
+/// Éléments facultatifs d'un téléchargement, abandonnés s'ils font échouer yt-dlp
+#[derive(Debug, Clone, Copy)]
+struct Extras {
+    subtitles: bool,
+    thumbnail: bool,
+}
+
+impl Extras {
+    /// Tous les éléments demandés par les options de la tâche
+    fn requested(options: &DownloadOptions) -> Self {
+        Self {
+            subtitles: options.subtitles.is_some(),
+            thumbnail: options.embed_thumbnail,
+        }
+    }
+}
+
+const DEFAULT_MAX_CONCURRENT: usize = 2;
+const DEFAULT_MAX_RETRIES: usize = 3;
+/// Intervalle de vérification de la reprise pour un nouvel essai mis en attente par la pause
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// État partagé entre la queue et son worker
+struct QueueState {
+    tasks: Mutex<Vec<DownloadTask>>,
+    pending: Mutex<VecDeque<QueuedDownload>>,
+    processes: Mutex<HashMap<String, u32>>, // URL vidéo -> PID yt-dlp
+    cancelled: Mutex<HashSet<String>>,
+    max_concurrent: AtomicUsize,
+    max_retries: AtomicUsize,
+    rate_limit: Mutex<Option<String>>,
+    cookies_file: Mutex<Option<PathBuf>>,
+    embed_thumbnail: AtomicBool,
+    /// File en pause: aucune tâche ne démarre et les yt-dlp en cours sont suspendus
+    paused: AtomicBool,
+}
+
+impl QueueState {
+    /// Met en pause ou reprend la file et suspend (SIGSTOP) ou relance (SIGCONT) les yt-dlp
+    /// en cours. Retourne `false` si la file était déjà dans cet état.
+    fn set_paused(&self, paused: bool) -> bool {
+        // Verrou des processus gardé pendant le changement: un yt-dlp enregistré en même
+        // temps voit le nouvel état ou reçoit le signal
+        let processes = self.processes.lock();
+        if self.paused.swap(paused, Ordering::SeqCst) == paused {
+            return false;
+        }
+        let signal = if paused { libc::SIGSTOP } else { libc::SIGCONT };
+        for pid in processes.values() {
+            unsafe {
+                libc::kill(*pid as libc::pid_t, signal);
+            }
+        }
+        true
+    }
+
+    /// Enregistre le yt-dlp d'une tâche. Sous le verrou des processus, comme `cancel` et
+    /// `set_paused`: une annulation arrivée avant l'enregistrement arrête aussitôt le
+    /// processus, une mise en pause le suspend
+    fn register_process(&self, video_url: &str, pid: u32) {
+        let mut processes = self.processes.lock();
+        processes.insert(video_url.to_string(), pid);
+        if self.cancelled.lock().contains(video_url) {
+            tracing::info!("Arrêt de yt-dlp (pid {}) pour {}", pid, video_url);
+            unsafe {
+                libc::kill(pid as libc::pid_t, libc::SIGTERM);
+            }
+        } else if self.paused.load(Ordering::SeqCst) {
+            unsafe {
+                libc::kill(pid as libc::pid_t, libc::SIGSTOP);
+            }
+        }
+    }
+
+    fn set_status(&self, video_url: &str, status: DownloadStatus) {
+        if let Some(t) = self
+            .tasks
+            .lock()
+            .iter_mut()
+            .find(|t| t.video_url == video_url)
+        {
+            t.status = status;
+        }
+    }
 }
 
 pub struct DownloadQueue {
-    tasks: Arc<Mutex<Vec<DownloadTask>>>,
-    tx: mpsc::UnboundedSender<DownloadTask>,
+    state: Arc<QueueState>,
+    wake_tx: mpsc::UnboundedSender<()>,
 }
 
 impl DownloadQueue {
     pub fn new(cx: &impl AppContext) -> Self {
-        let tasks: Arc<Mutex<Vec<DownloadTask>>> = Arc::new(Mutex::new(Vec::new()));
-        let (tx, mut rx) = mpsc::unbounded::<DownloadTask>();
+        let (queue, worker) = Self::with_worker();
+        cx.background_spawn(worker).detach();
+        queue
+    }
 
-        let tasks_clone = tasks.clone();
+    /// Crée la queue sans la lancer: le worker retourné doit être exécuté par l'appelant
+    /// (mode ligne de commande, sans exécuteur gpui)
+    pub fn with_worker() -> (Self, impl Future<Output = ()> + Send + 'static) {
+        let state = Arc::new(QueueState {
+            tasks: Mutex::new(Vec::new()),
+            pending: Mutex::new(VecDeque::new()),
+            processes: Mutex::new(HashMap::new()),
+            cancelled: Mutex::new(HashSet::new()),
+            max_concurrent: AtomicUsize::new(DEFAULT_MAX_CONCURRENT),
+            max_retries: AtomicUsize::new(DEFAULT_MAX_RETRIES),
+            rate_limit: Mutex::new(None),
+            cookies_file: Mutex::new(None),
+            embed_thumbnail: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+        });
+        let (wake_tx, mut wake_rx) = mpsc::unbounded::<()>();
 
-        // Worker qui traite les téléchargements
-        cx.background_spawn(async move {
-            while let Some(mut task) = rx.next().await {
-                tracing::info!("Début du téléchargement: {}", task.title);
+        let worker_state = state.clone();
 
-                // Mettre à jour le statut
-                task.status = DownloadStatus::Downloading;
+        // Worker qui démarre les téléchargements en respectant la limite de concurrence
+        let worker = async move {
+            let mut running = FuturesUnordered::new();
+            loop {
+                while !worker_state.paused.load(Ordering::SeqCst)
+                    && running.len() < worker_state.max_concurrent.load(Ordering::SeqCst)
                 {
-                    let mut tasks_lock = tasks_clone.lock();
-                    if let Some(t) = tasks_lock.iter_mut().find(|t| t.video_id == task.video_id) {
-                        t.status = DownloadStatus::Downloading;
+                    let Some((task, done)) = worker_state.pending.lock().pop_front() else {
+                        break;
+                    };
+                    running.push(Self::run_task(worker_state.clone(), task, done));
+                }
+
+                if running.is_empty() {
+                    // Rien en cours: attendre une nouvelle tâche
+                    if wake_rx.next().await.is_none() {
+                        break;
                     }
+                    continue;
                 }
 
-                // Télécharger la vidéo avec mise à jour de progression
-                let tasks_for_progress = tasks_clone.clone();
-                let video_id = task.video_id.clone();
+                // Attendre une nouvelle tâche ou la fin d'un téléchargement
+                let closed = matches!(
+                    future::select(wake_rx.next(), running.next()).await,
+                    Either::Left((None, _))
+                );
+                if closed {
+                    // La queue a été détruite: terminer les téléchargements en cours
+                    worker_state.set_paused(false);
+                    while running.next().await.is_some() {}
+                    break;
+                }
+            }
+        };
 
-                match Self::download_video(&task, move |progress, speed, eta| {
-                    let mut tasks_lock = tasks_for_progress.lock();
-                    if let Some(t) = tasks_lock.iter_mut().find(|t| t.video_id == video_id) {
-                        t.progress = progress;
-                        t.speed = speed;
-                        t.eta = eta;
-                    }
-                })
-                .await
+        (Self { state, wake_tx }, worker)
+    }
+
+    /// Télécharge une tâche sortie de la file d'attente et publie son statut final
+    async fn run_task(
+        state: Arc<QueueState>,
+        mut task: DownloadTask,
+        done: oneshot::Sender<DownloadStatus>,
+    ) {
+        tracing::info!("Début du téléchargement: {}", task.title);
+
+        // Appliquer la limite de débit courante de la queue
+        if let Some(limit) = state.rate_limit.lock().clone() {
+            task.options.rate_limit = Some(limit);
+        }
+        if let Some(cookies) = state.cookies_file.lock().clone() {
+            task.options.cookies_file = Some(cookies);
+        }
+        task.options.embed_thumbnail |= state.embed_thumbnail.load(Ordering::SeqCst);
+
+        // Mettre à jour le statut
+        task.status = DownloadStatus::Downloading;
+        state.set_status(&task.video_url, DownloadStatus::Downloading);
+
+        // Télécharger la vidéo avec mise à jour de progression
+        let progress_state = state.clone();
+        let video_url = task.video_url.clone();
+
+        let mut on_progress = move |progress, speed, eta| {
+            let mut tasks_lock = progress_state.tasks.lock();
+            if let Some(t) = tasks_lock.iter_mut().find(|t| t.video_url == video_url) {
+                t.progress = progress;
+                t.speed = speed;
+                t.eta = eta;
+            }
+        };
+
+        // Réessayer les erreurs passagères (réseau, timeout) avec un délai croissant
+        let max_retries = state.max_retries.load(Ordering::SeqCst);
+        let mut attempt = 0;
+        let result = loop {
+            let result = Self::download_video(&task, &state, &mut on_progress).await;
+            match result {
+                Err(e)
+                    if attempt < max_retries
+                        && is_retryable_error(&e.to_string())
+                        && !state.cancelled.lock().contains(&task.video_url) =>
                 {
-                    Ok(_) => {
-                        tracing::info!("Téléchargement terminé: {}", task.title);
-                        task.status = DownloadStatus::Completed;
-                        task.progress = 1.0;
+                    attempt += 1;
+                    let delay = retry_delay(attempt);
+                    tracing::warn!(
+                        "Échec du téléchargement {} ({}), nouvel essai {}/{} dans {}s",
+                        task.title,
+                        e,
+                        attempt,
+                        max_retries,
+                        delay.as_secs()
+                    );
+                    smol::Timer::after(delay).await;
+                    // Ne pas relancer yt-dlp tant que la file est en pause
+                    while state.paused.load(Ordering::SeqCst)
+                        && !state.cancelled.lock().contains(&task.video_url)
+                    {
+                        smol::Timer::after(PAUSE_POLL_INTERVAL).await;
                     }
-                    Err(e) => {
-                        tracing::error!("Erreur téléchargement {}: {}", task.title, e);
-                        task.status = DownloadStatus::Failed(e.to_string());
+                    if state.cancelled.lock().contains(&task.video_url) {
+                        break Err(e);
                     }
                 }
+                result => break result,
+            }
+        };
 
-                // Mettre à jour le statut final
-                {
-                    let mut tasks_lock = tasks_clone.lock();
-                    if let Some(t) = tasks_lock.iter_mut().find(|t| t.video_id == task.video_id) {
-                        t.status = task.status.clone();
-                        t.progress = task.progress;
-                    }
+        // yt-dlp arrêté renvoie une erreur: un succès signifie que l'annulation est arrivée
+        // trop tard, le fichier terminé est gardé
+        let cancelled = state.cancelled.lock().remove(&task.video_url);
+        match result {
+            Err(_) if cancelled => {
+                tracing::info!("Téléchargement annulé: {}", task.title);
+                Self::remove_partial_files(&task);
+                task.status = DownloadStatus::Cancelled;
+            }
+            Ok(_) => {
+                tracing::info!("Téléchargement terminé: {}", task.title);
+                Self::write_video_id(&task);
+                task.status = DownloadStatus::Completed;
+                task.progress = 1.0;
+            }
+            Err(e) => {
+                tracing::error!("Erreur téléchargement {}: {}", task.title, e);
+                task.status = DownloadStatus::Failed(e.to_string());
+            }
+        }
+
+        // Mettre à jour le statut final
+        {
+            let mut tasks_lock = state.tasks.lock();
+            if let Some(t) = tasks_lock
+                .iter_mut()
+                .find(|t| t.video_url == task.video_url)
+            {
+                t.status = task.status.clone();
+                t.progress = task.progress;
+            }
+        }
+
+        // Prévenir l'appelant (il a pu abandonner l'attente)
+        done.send(task.status).ok();
+    }
+
+    /// Applique les paramètres de téléchargement de la configuration
+    pub fn configure(&self, config: &Config) {
+        self.set_max_concurrent(config.max_concurrent_downloads);
+        self.set_max_retries(config.max_download_retries);
+        self.set_embed_thumbnail(config.embed_thumbnail);
+        if let Err(error) = self.set_rate_limit(config.download_rate_limit.as_deref()) {
+            tracing::warn!("Ignoring download rate limit from config: {}", error);
+        }
+        if let Err(error) = self.set_cookies_file(config.cookies_file.as_deref()) {
+            tracing::warn!("Ignoring cookies file from config: {}", error);
+        }
+    }
+
+    /// Définit le nombre maximum de téléchargements simultanés
+    pub fn set_max_concurrent(&self, max: usize) {
+        self.state
+            .max_concurrent
+            .store(max.max(1), Ordering::SeqCst);
+        self.wake_tx.unbounded_send(()).ok();
+    }
+
+    /// Définit le nombre de nouveaux essais après une erreur passagère
+    pub fn set_max_retries(&self, retries: usize) {
+        self.state.max_retries.store(retries, Ordering::SeqCst);
+    }
+
+    /// Limite le débit des prochains téléchargements (`None` = illimité).
+    /// Accepte des valeurs comme "2M" ou "500K".
+    pub fn set_rate_limit(&self, limit: Option<&str>) -> Result<()> {
+        let limit = limit.map(parse_rate_limit).transpose()?;
+        *self.state.rate_limit.lock() = limit;
+        Ok(())
+    }
+
+    /// Utilise un fichier de cookies pour les prochains téléchargements (`None` = aucun).
+    /// Le fichier doit exister.
+    pub fn set_cookies_file(&self, path: Option<&str>) -> Result<()> {
+        let path = path.map(scanner::validate_cookies_file).transpose()?;
+        *self.state.cookies_file.lock() = path;
+        Ok(())
+    }
+
+    /// Intègre la miniature aux prochains téléchargements
+    pub fn set_embed_thumbnail(&self, enabled: bool) {
+        self.state.embed_thumbnail.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Met en pause toute la file: plus aucune tâche ne démarre et les téléchargements
+    /// en cours sont suspendus (SIGSTOP) sans être annulés
+    pub fn pause(&self) {
+        if !self.state.set_paused(true) {
+            return;
+        }
+        tracing::info!("File de téléchargement en pause");
+    }
+
+    /// Reprend les téléchargements suspendus et le démarrage des tâches en attente
+    pub fn resume(&self) {
+        if !self.state.set_paused(false) {
+            return;
+        }
+        tracing::info!("Reprise de la file de téléchargement");
+        self.wake_tx.unbounded_send(()).ok();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.load(Ordering::SeqCst)
+    }
+
+    /// Change la priorité d'une tâche en attente; les téléchargements en cours ne sont pas
+    /// concernés. Retourne `false` si la tâche n'est pas en attente ou ne peut pas bouger.
+    pub fn move_pending(&self, video_url: &str, direction: QueueMove) -> bool {
+        let mut pending = self.state.pending.lock();
+        let Some(index) = pending
+            .iter()
+            .position(|(task, _)| task.video_url == video_url)
+        else {
+            return false;
+        };
+        let Some(target) = direction.target(index, pending.len()) else {
+            return false;
+        };
+        let target_url = pending[target].0.video_url.clone();
+        if let Some(item) = pending.remove(index) {
+            pending.insert(target, item);
+        }
+
+        // Même ordre dans la liste affichée, autour des tâches en cours ou terminées
+        let mut tasks = self.state.tasks.lock();
+        let from = tasks.iter().position(|t| t.video_url == video_url);
+        let to = tasks.iter().position(|t| t.video_url == target_url);
+        if let (Some(from), Some(to)) = (from, to) {
+            let task = tasks.remove(from);
+            tasks.insert(to, task);
+        }
+        true
+    }
+
+    /// Annule un téléchargement en attente ou en cours.
+    /// Le processus yt-dlp est arrêté et les fichiers partiels supprimés.
+    pub fn cancel(&self, video_url: &str) {
+        // En attente: il suffit de le retirer de la file
+        let pending = {
+            let mut pending = self.state.pending.lock();
+            pending
+                .iter()
+                .position(|(task, _)| task.video_url == video_url)
+                .and_then(|index| pending.remove(index))
+        };
+        if let Some((_, done)) = pending {
+            self.state.set_status(video_url, DownloadStatus::Cancelled);
+            done.send(DownloadStatus::Cancelled).ok();
+            return;
+        }
+
+        let is_running = self
+            .state
+            .tasks
+            .lock()
+            .iter()
+            .any(|t| t.video_url == video_url && t.status == DownloadStatus::Downloading);
+        if !is_running {
+            return;
+        }
+
+        self.state.cancelled.lock().insert(video_url.to_string());
+        if let Some(pid) = self.state.processes.lock().get(video_url) {
+            tracing::info!("Arrêt de yt-dlp (pid {}) pour {}", pid, video_url);
+            unsafe {
+                libc::kill(*pid as libc::pid_t, libc::SIGTERM);
+                // Un processus suspendu ne traite le signal qu'une fois relancé
+                if self.is_paused() {
+                    libc::kill(*pid as libc::pid_t, libc::SIGCONT);
                 }
             }
-        })
-        .detach();
+        }
+    }
 
-        Self { tasks, tx }
+    /// Enregistre l'identifiant de la vidéo à côté du fichier pour la reconnaître au prochain scan
+    fn write_video_id(task: &DownloadTask) {
+        let path = scanner::id_sidecar_path(&task.output_path);
+        if let Err(error) = std::fs::write(&path, &task.video_id) {
+            tracing::warn!("Impossible d'écrire {}: {}", path.display(), error);
+        }
+    }
+
+    /// Supprime le fichier final et les fichiers temporaires de yt-dlp (.part, .ytdl)
+    fn remove_partial_files(task: &DownloadTask) {
+        let output_path = &task.output_path;
+        let Some(parent) = output_path.parent() else {
+            return;
+        };
+        // En audio seul, l'extension téléchargée avant conversion est choisie par yt-dlp
+        let any_extension = task.options.quality == DownloadQuality::AudioOnly;
+
+        let Ok(entries) = std::fs::read_dir(parent) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path() == *output_path || is_partial_file(&name, output_path, any_extension) {
+                if let Err(error) = std::fs::remove_file(entry.path()) {
+                    tracing::warn!("Impossible de supprimer {}: {}", name, error);
+                }
+            }
+        }
     }
 
-    /// Ajoute une tâche de téléchargement à la queue
+    /// Ajoute une tâche de téléchargement à la queue.
+    /// Elle démarre dès qu'une place se libère (statut `Queued` en attendant).
+    /// Le récepteur retourné est résolu avec le statut final de la tâche.
     pub async fn add_download(
         &self,
         video_id: String,
         video_url: String,
         title: String,
         output_path: PathBuf,
-    ) -> Result<()> {
+        options: DownloadOptions,
+    ) -> Result<oneshot::Receiver<DownloadStatus>> {
         let task = DownloadTask {
-            video_id: video_id.clone(),
+            video_id,
             video_url,
             title,
             output_path,
+            options,
             status: DownloadStatus::Queued,
             progress: 0.0,
             speed: None,
             eta: None,
         };
 
-        // Ajouter à la liste
+        // Ajouter à la liste, en remplaçant une ancienne tâche terminée pour la même vidéo
         {
-            let mut tasks = self.tasks.lock();
+            let mut tasks = self.state.tasks.lock();
+            tasks.retain(|t| t.video_url != task.video_url || !t.status.is_finished());
             tasks.push(task.clone());
         }
 
-        // Envoyer au worker
-        self.tx.clone().send(task).await?;
+        // Mettre en file d'attente et réveiller le worker
+        let (done_tx, done_rx) = oneshot::channel();
+        self.state.pending.lock().push_back((task, done_tx));
+        self.wake_tx.clone().send(()).await?;
 
-        Ok(())
+        Ok(done_rx)
+    }
+
+    /// Retire de la liste les tâches terminées
+    pub fn clear_finished(&self) {
+        self.state.tasks.lock().retain(|t| !t.status.is_finished());
     }
 
     /// Obtient la liste de toutes les tâches
     pub fn get_tasks(&self) -> Vec<DownloadTask> {
-        let tasks = self.tasks.lock();
+        let tasks = self.state.tasks.lock();
         tasks.clone()
     }
 
     /// Télécharge une vidéo avec yt-dlp
-    async fn download_video<F>(task: &DownloadTask, mut on_progress: F) -> Result<()>
+    async fn download_video<F>(
+        task: &DownloadTask,
+        state: &QueueState,
+        on_progress: &mut F,
+    ) -> Result<()>
     where
-        F: FnMut(f32, Option<String>, Option<String>) + Send + 'static,
+        F: FnMut(f32, Option<String>, Option<String>),
     {
         // Créer le dossier de sortie si nécessaire
+        check_storage_available(&task.output_path)?;
         if let Some(parent) = task.output_path.parent() {
             smol::fs::create_dir_all(parent).await?;
         }
 
-        let output_template = task.output_path.to_string_lossy().to_string();
+        let mut extras = Extras::requested(&task.options);
+        loop {
+            match Self::run_yt_dlp(task, extras, state, on_progress).await {
+                // Des sous-titres indisponibles ne doivent pas faire échouer la vidéo
+                Err(error) if extras.subtitles && error.to_string().contains("subtitles") => {
+                    tracing::warn!(
+                        "Sous-titres indisponibles pour {}, nouvel essai sans: {}",
+                        task.title,
+                        error
+                    );
+                    extras.subtitles = false;
+                }
+                // Conteneur qui n'accepte pas de miniature (webm...): le fichier est déjà
+                // téléchargé, yt-dlp le réutilise au nouvel essai
+                Err(error) if extras.thumbnail && error.to_string().contains("thumbnail") => {
+                    tracing::warn!(
+                        "Miniature non intégrable pour {}, nouvel essai sans: {}",
+                        task.title,
+                        error
+                    );
+                    extras.thumbnail = false;
+                }
+                result => return result,
+            }
+        }
+    }
 
+    async fn run_yt_dlp<F>(
+        task: &DownloadTask,
+        extras: Extras,
+        state: &QueueState,
+        on_progress: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(f32, Option<String>, Option<String>),
+    {
         let mut child = smol::process::Command::new("yt-dlp")
-            .arg("--newline")
-            .arg("-o")
-            .arg(&output_template)
-            .arg("-f")
-            .arg("bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]")
-            .arg(&task.video_url)
+            .args(Self::build_args(task, extras))
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()?;
+            .spawn()
+            .map_err(|error| scanner::spawn_error("yt-dlp", error))?;
+        state.register_process(&task.video_url, child.id());
 
         // Lire la sortie pour extraire la progression
-        if let Some(stdout) = child.stdout.take() {
-            let reader = BufReader::new(stdout);
-            let mut lines = reader.lines();
-
-            while let Some(line) = lines.next().await {
-                if let Ok(line) = line {
-                    let (progress, speed, eta) = Self::parse_download_line(&line);
-                    if let Some(p) = progress {
-                        on_progress(p, speed, eta);
+        let stdout = child.stdout.take();
+        let read_progress = async {
+            if let Some(stdout) = stdout {
+                let reader = BufReader::new(stdout);
+                let mut lines = reader.lines();
+
+                while let Some(line) = lines.next().await {
+                    if let Ok(line) = line {
+                        let (progress, speed, eta) = Self::parse_download_line(&line);
+                        if let Some(p) = progress {
+                            on_progress(p, speed, eta);
+                        }
                     }
                 }
             }
-        }
+        };
 
-        let status = child.status().await?;
-        if !status.success() {
-            anyhow::bail!("yt-dlp a échoué");
+        // Conserver stderr pour expliquer un éventuel échec
+        let stderr = child.stderr.take();
+        let read_errors = async {
+            let mut output = String::new();
+            if let Some(stderr) = stderr {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Some(Ok(line)) = lines.next().await {
+                    output.push_str(&line);
+                    output.push('\n');
+                }
+            }
+            output
+        };
+
+        let ((), stderr_output) = futures::join!(read_progress, read_errors);
+
+        let status = child.status().await;
+        state.processes.lock().remove(&task.video_url);
+
+        if !status?.success() {
+            let error = stderr_output
+                .lines()
+                .rev()
+                .find(|line| line.starts_with("ERROR"))
+                .unwrap_or("yt-dlp a échoué");
+            anyhow::bail!("{error}");
         }
 
         Ok(())
     }
 
+    /// Construit les arguments yt-dlp d'une tâche
+    fn build_args(task: &DownloadTask, extras: Extras) -> Vec<String> {
+        let audio_only = task.options.quality == DownloadQuality::AudioOnly;
+
+        // En audio seul, yt-dlp choisit l'extension puis convertit en mp3
+        let output_template = if audio_only {
+            task.output_path.with_extension("%(ext)s")
+        } else {
+            task.output_path.clone()
+        };
+
+        let mut args: Vec<String> = vec![
+            "--newline".into(),
+            "--progress".into(),
+            // L'URL source écrite dans le fichier permet de le reconnaître au prochain scan
+            "--embed-metadata".into(),
+            "-o".into(),
+            output_template.to_string_lossy().to_string(),
+        ];
+        args.extend(
+            task.options
+                .quality
+                .format_args(task.options.container)
+                .iter()
+                .map(|a| a.to_string()),
+        );
+
+        if let Some(language) = task.options.subtitles.as_ref().filter(|_| extras.subtitles) {
+            args.extend([
+                "--write-subs".into(),
+                "--sub-langs".into(),
+                language.clone(),
+            ]);
+            // Un mp3 ne peut pas contenir de sous-titres, on les garde à côté
+            if !audio_only {
+                args.push("--embed-subs".into());
+            }
+        }
+
+        if extras.thumbnail {
+            args.extend([
+                "--embed-thumbnail".into(),
+                "--convert-thumbnails".into(),
+                "jpg".into(),
+            ]);
+        }
+
+        if let Some(limit) = &task.options.rate_limit {
+            args.extend(["--limit-rate".into(), limit.clone()]);
+        }
+
+        // Le fichier a pu disparaître depuis sa configuration
+        match &task.options.cookies_file {
+            Some(cookies) if cookies.is_file() => {
+                args.extend(["--cookies".into(), cookies.to_string_lossy().to_string()]);
+            }
+            Some(cookies) => {
+                tracing::warn!("Fichier de cookies introuvable: {}", cookies.display());
+            }
+            None => {}
+        }
+
+        args.push(task.video_url.clone());
+        args
+    }
+
     fn parse_download_line(line: &str) -> (Option<f32>, Option<String>, Option<String>) {
         if !line.contains("[download]") {
             return (None, None, None);
@@ -210,6 +900,126 @@ impl DownloadQueue {
     }
 }
 
+/// Valide une limite de débit ("2M", "500k", "1.5M") et la normalise pour yt-dlp
+pub fn parse_rate_limit(value: &str) -> Result<String> {
+    let value = value.trim();
+    let (number, unit) = match value.char_indices().last() {
+        Some((index, c)) if c.is_ascii_alphabetic() => (&value[..index], Some(c)),
+        _ => (value, None),
+    };
+
+    let unit = match unit.map(|c| c.to_ascii_uppercase()) {
+        None => "",
+        Some('K') => "K",
+        Some('M') => "M",
+        Some('G') => "G",
+        Some(_) => anyhow::bail!("Unité de débit invalide: {value} (ex: 2M, 500K)"),
+    };
+    match number.parse::<f64>() {
+        Ok(amount) if amount > 0.0 && amount.is_finite() => Ok(format!("{number}{unit}")),
+        _ => anyhow::bail!("Limite de débit invalide: {value} (ex: 2M, 500K)"),
+    }
+}
+
+/// Une erreur yt-dlp vaut-elle un nouvel essai ? (réseau, timeout, erreur serveur)
+/// Les vidéos privées, supprimées ou bloquées échouent immédiatement.
+fn is_retryable_error(error: &str) -> bool {
+    const PERMANENT: [&str; 6] = [
+        "Private video",
+        "Video unavailable",
+        "removed",
+        "copyright",
+        "members-only",
+        "HTTP Error 404",
+    ];
+    const TRANSIENT: [&str; 9] = [
+        "timed out",
+        "Timeout",
+        "Connection",
+        "connection",
+        "Temporary failure",
+        "Network is unreachable",
+        "IncompleteRead",
+        "HTTP Error 5",
+        "HTTP Error 429",
+    ];
+
+    !PERMANENT.iter().any(|pattern| error.contains(pattern))
+        && TRANSIENT.iter().any(|pattern| error.contains(pattern))
+}
+
+/// Vérifie que le disque de stockage (le dossier au-dessus de celui de la chaîne) est
+/// toujours là: un disque démonté ne doit pas être recréé en simple dossier sur le système
+fn check_storage_available(output_path: &Path) -> Result<()> {
+    let storage = output_path
+        .parent()
+        .and_then(Path::parent)
+        .filter(|storage| !storage.as_os_str().is_empty());
+    match storage {
+        Some(storage) if !scanner::is_storage_available(storage) => {
+            anyhow::bail!(
+                "Disque de stockage inaccessible: {} (démonté ?)",
+                storage.display()
+            )
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Fichier temporaire de yt-dlp pour `output_path`: `{stem}.{ext}.part`,
+/// `{stem}.f<format>.{ext}.part`, leurs `.ytdl` et fragments `.part-FragN`, les formats
+/// séparés `{stem}.f<format>.{ext}` et le `{stem}.temp.{ext}` de la fusion.
+/// Les fichiers d'une autre vidéo au titre qui commence pareil ne sont pas concernés.
+fn is_partial_file(name: &str, output_path: &Path, any_extension: bool) -> bool {
+    let (Some(stem), Some(extension)) = (output_path.file_stem(), output_path.extension()) else {
+        return false;
+    };
+    let (stem, extension) = (stem.to_string_lossy(), extension.to_string_lossy());
+    let Some(rest) = name
+        .strip_prefix(stem.as_ref())
+        .and_then(|rest| rest.strip_prefix('.'))
+    else {
+        return false;
+    };
+
+    let fragment = rest
+        .rsplit_once(".part-Frag")
+        .filter(|(_, index)| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()));
+    let suffix = || {
+        rest.strip_suffix(".part")
+            .or_else(|| rest.strip_suffix(".ytdl"))
+    };
+    let (base, temporary) = match fragment.map(|(base, _)| base).or_else(suffix) {
+        Some(base) => (base, true),
+        None => (rest, false),
+    };
+
+    let is_extension =
+        |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric());
+    // Un identifiant de format contient un chiffre ou un séparateur (137, 251-drc,
+    // audio_only), contrairement à un mot du titre ou à une langue (Title.fr.vtt)
+    let is_format = |part: &str| {
+        part.strip_prefix('f').is_some_and(|id| {
+            id.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                && id
+                    .chars()
+                    .any(|c| c.is_ascii_digit() || c == '-' || c == '_')
+        })
+    };
+    match base.split('.').collect::<Vec<_>>().as_slice() {
+        [ext] => temporary && (*ext == extension || any_extension && is_extension(ext)),
+        ["temp", ext] => *ext == extension,
+        [format, ext] => is_format(format) && is_extension(ext),
+        _ => false,
+    }
+}
+
+/// Délai avant le n-ième nouvel essai: 2s, 4s, 8s...
+fn retry_delay(attempt: usize) -> Duration {
+    Duration::from_secs(1 << attempt.min(6))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,6 +1045,116 @@ mod tests {
         assert_eq!(eta, None);
     }
 
+    #[test]
+    fn test_parse_progress_estimated_size() {
+        let line = "[download]  34.5% of ~  120.00MiB at    2.30MiB/s ETA 00:45";
+        let (progress, speed, eta) = DownloadQueue::parse_download_line(line);
+
+        assert!((progress.unwrap() - 0.345).abs() < 0.001);
+        assert_eq!(speed, Some("2.30MiB/s".to_string()));
+        assert_eq!(eta, Some("00:45".to_string()));
+    }
+
+    fn task(options: DownloadOptions) -> DownloadTask {
+        DownloadTask {
+            options,
+            ..DownloadTask::for_test("title", DownloadStatus::Queued)
+        }
+    }
+
+    #[test]
+    fn test_build_args_with_subtitles() {
+        let task = task(DownloadOptions {
+            subtitles: Some("fr".to_string()),
+            ..Default::default()
+        });
+
+        let args = DownloadQueue::build_args(&task, Extras::requested(&task.options));
+        let joined = args.join(" ");
+        assert!(joined.contains("--write-subs --sub-langs fr --embed-subs"));
+        assert_eq!(args.last(), Some(&task.video_url));
+
+        let without = DownloadQueue::build_args(
+            &task,
+            Extras {
+                subtitles: false,
+                thumbnail: false,
+            },
+        );
+        assert!(!without.iter().any(|a| a == "--write-subs"));
+    }
+
+    #[test]
+    fn test_build_args_audio_only() {
+        let task = task(DownloadOptions {
+            quality: DownloadQuality::AudioOnly,
+            subtitles: Some("en".to_string()),
+            ..Default::default()
+        });
+
+        let args = DownloadQueue::build_args(&task, Extras::requested(&task.options));
+        assert!(args.contains(&"/videos/chan/title.%(ext)s".to_string()));
+        assert!(args.contains(&"--extract-audio".to_string()));
+        // Les métadonnées sont aussi intégrées au mp3 (tags ID3)
+        assert!(args.contains(&"--embed-metadata".to_string()));
+        assert!(!args.contains(&"--embed-subs".to_string()));
+    }
+
+    #[test]
+    fn test_build_args_container() {
+        let default = DownloadQueue::build_args(
+            &task(DownloadOptions::default()),
+            Extras::requested(&DownloadOptions::default()),
+        )
+        .join(" ");
+        assert!(default.contains("[ext=mp4]"));
+        assert!(default.contains("--merge-output-format mp4 --remux-video mp4"));
+
+        let options = DownloadOptions {
+            quality: DownloadQuality::P720,
+            container: VideoContainer::Webm,
+            ..Default::default()
+        };
+        let joined = DownloadQueue::build_args(&task(options.clone()), Extras::requested(&options))
+            .join(" ");
+        assert!(joined.contains("-f bestvideo[height<=720]+bestaudio/best[height<=720]"));
+        assert!(joined.contains("--merge-output-format webm --remux-video webm"));
+        assert_eq!(options.quality.extension(options.container), "webm");
+
+        // L'audio seul reste en mp3 quel que soit le conteneur
+        let audio = DownloadOptions {
+            quality: DownloadQuality::AudioOnly,
+            container: VideoContainer::Mkv,
+            ..Default::default()
+        };
+        let args = DownloadQueue::build_args(&task(audio.clone()), Extras::requested(&audio));
+        assert!(!args.contains(&"--remux-video".to_string()));
+        assert_eq!(audio.quality.extension(audio.container), "mp3");
+    }
+
+    #[test]
+    fn test_build_args_embed_thumbnail() {
+        let task = task(DownloadOptions {
+            embed_thumbnail: true,
+            ..Default::default()
+        });
+
+        let args = DownloadQueue::build_args(&task, Extras::requested(&task.options));
+        assert!(args
+            .join(" ")
+            .contains("--embed-thumbnail --convert-thumbnails jpg"));
+
+        // Nouvel essai sans miniature si le conteneur la refuse
+        let fallback = DownloadQueue::build_args(
+            &task,
+            Extras {
+                thumbnail: false,
+                ..Extras::requested(&task.options)
+            },
+        );
+        assert!(!fallback.contains(&"--embed-thumbnail".to_string()));
+    }
+
     #[test]
     fn test_parse_non_download_line() {
         let line = "[info] Downloading video...";
@@ -244,4 +1164,134 @@ mod tests {
         assert_eq!(speed, None);
         assert_eq!(eta, None);
     }
+
+    #[test]
+    fn test_retryable_errors() {
+        assert!(is_retryable_error(
+            "ERROR: [youtube] abc: Unable to download webpage: The read operation timed out"
+        ));
+        assert!(is_retryable_error(
+            "ERROR: unable to download video data: HTTP Error 503: Service Unavailable"
+        ));
+        assert!(!is_retryable_error(
+            "ERROR: [youtube] abc: Private video. Sign in if you've been granted access"
+        ));
+        assert!(!is_retryable_error(
+            "ERROR: [youtube] abc: Video unavailable. This video has been removed by the uploader"
+        ));
+        assert!(!is_retryable_error("yt-dlp a échoué"));
+    }
+
+    #[test]
+    fn test_check_storage_available() {
+        let dir = tempfile::tempdir().unwrap();
+        // Le dossier de la chaîne est créé au besoin, pas le disque
+        assert!(check_storage_available(&dir.path().join("chan/video.mp4")).is_ok());
+
+        let error = check_storage_available(&dir.path().join("disk/chan/video.mp4")).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Disque de stockage inaccessible"));
+        assert!(!is_retryable_error(&error.to_string()));
+    }
+
+    #[test]
+    fn test_is_partial_file() {
+        let output = Path::new("/videos/chan/Title.mp4");
+        for name in [
+            "Title.mp4.part",
+            "Title.mp4.ytdl",
+            "Title.mp4.part-Frag12",
+            "Title.f137.mp4.part",
+            "Title.f251-drc.webm.part",
+            "Title.f140.m4a",
+            "Title.f137.mp4.ytdl",
+            "Title.faudio_only.mp4.part",
+            "Title.temp.mp4",
+        ] {
+            assert!(is_partial_file(name, output, false), "{name}");
+        }
+        // Autres vidéos dont le titre commence pareil, fichiers terminés
+        for name in [
+            "Title.mp4",
+            "Title (2).mp4.part",
+            "Title.part2.mp4.part",
+            "Title.extended.mp4.part",
+            "Title.final.mp4.part",
+            "Title.mkv.part",
+            "Title.mp4.id",
+            "Title.fr.vtt",
+        ] {
+            assert!(!is_partial_file(name, output, false), "{name}");
+        }
+
+        // Audio seul: yt-dlp télécharge dans son propre format avant la conversion
+        let audio = Path::new("/videos/chan/Title.mp3");
+        assert!(is_partial_file("Title.webm.part", audio, true));
+        assert!(!is_partial_file("Title.webm.part", audio, false));
+    }
+
+    #[test]
+    fn test_retry_delay_doubles() {
+        assert_eq!(retry_delay(1), Duration::from_secs(2));
+        assert_eq!(retry_delay(2), Duration::from_secs(4));
+        assert_eq!(retry_delay(3), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_queue_move_target() {
+        assert_eq!(QueueMove::Up.target(2, 4), Some(1));
+        assert_eq!(QueueMove::Up.target(0, 4), None);
+        assert_eq!(QueueMove::Down.target(2, 4), Some(3));
+        assert_eq!(QueueMove::Down.target(3, 4), None);
+        assert_eq!(QueueMove::First.target(3, 4), Some(0));
+        assert_eq!(QueueMove::First.target(0, 4), None);
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(parse_rate_limit("2M").unwrap(), "2M");
+        assert_eq!(parse_rate_limit(" 500k ").unwrap(), "500K");
+        assert_eq!(parse_rate_limit("1.5M").unwrap(), "1.5M");
+        assert_eq!(parse_rate_limit("100000").unwrap(), "100000");
+
+        assert!(parse_rate_limit("").is_err());
+        assert!(parse_rate_limit("M").is_err());
+        assert!(parse_rate_limit("0K").is_err());
+        assert!(parse_rate_limit("2X").is_err());
+        assert!(parse_rate_limit("-5M").is_err());
+    }
+
+    #[test]
+    fn test_build_args_rate_limit() {
+        let task = task(DownloadOptions {
+            rate_limit: Some("2M".to_string()),
+            ..Default::default()
+        });
+
+        let args = DownloadQueue::build_args(&task, Extras::requested(&task.options));
+        assert!(args.join(" ").contains("--limit-rate 2M"));
+        assert_eq!(args.last(), Some(&task.video_url));
+    }
+
+    #[test]
+    fn test_build_args_cookies() {
+        let cookies = tempfile::NamedTempFile::new().unwrap();
+        let with_cookies = task(DownloadOptions {
+            cookies_file: Some(cookies.path().to_path_buf()),
+            ..Default::default()
+        });
+
+        let args =
+            DownloadQueue::build_args(&with_cookies, Extras::requested(&with_cookies.options));
+        let expected = format!("--cookies {}", cookies.path().display());
+        assert!(args.join(" ").contains(&expected));
+
+        let missing = task(DownloadOptions {
+            cookies_file: Some(PathBuf::from("/nonexistent/cookies.txt")),
+            ..Default::default()
+        });
+        let args = DownloadQueue::build_args(&missing, Extras::requested(&missing.options));
+        assert!(!args.contains(&"--cookies".to_string()));
+    }
 }
@@ -0,0 +1,306 @@
+use std::path::{Path, PathBuf};
+
+/// Caractères interdits dans un nom de fichier sur au moins une plateforme
+const FORBIDDEN_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Noms réservés par Windows, quelle que soit l'extension
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Longueur maximale (en caractères) d'un nom généré
+pub const MAX_LENGTH: usize = 150;
+
+/// Taille maximale (en octets) d'un nom généré. NAME_MAX vaut 255 octets sur ext4, btrfs
+/// et la plupart des systèmes de fichiers, et un caractère japonais ou un emoji en occupe
+/// 3 ou 4: la marge laisse la place aux suffixes ajoutés ensuite (" (2)", ".mp4",
+/// ".f251-drc.webm.part-Frag12" pendant le téléchargement...).
+pub const MAX_BYTES: usize = 200;
+
+/// Coupe une chaîne à `max` octets au plus, sans couper de caractère
+fn truncate_bytes(name: &str, max: usize) -> &str {
+    let end = (0..=max.min(name.len()))
+        .rev()
+        .find(|&index| name.is_char_boundary(index))
+        .unwrap_or(0);
+    &name[..end]
+}
+
+/// Transforme un titre en nom de fichier valide: caractères interdits et de contrôle
+/// remplacés par des espaces, espaces fusionnés, longueur limitée, noms réservés suffixés.
+/// Peut retourner une chaîne vide si le titre ne contient rien d'utilisable (ex: "..").
+pub fn sanitize(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || FORBIDDEN_CHARS.contains(&c) {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    let collapsed = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    let truncated: String = collapsed.chars().take(MAX_LENGTH).collect();
+    let truncated = truncate_bytes(&truncated, MAX_BYTES);
+
+    // Windows refuse les noms terminés par un point ou un espace
+    let name = truncated.trim_end_matches(['.', ' ']);
+
+    let stem = name.split('.').next().unwrap_or(name);
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+    {
+        format!("{stem}_{}", &name[stem.len()..])
+    } else {
+        name.to_string()
+    }
+}
+
+/// Modèle de nom de fichier par défaut: le titre de la vidéo
+pub const DEFAULT_TEMPLATE: &str = "{title}";
+
+/// Jetons reconnus dans un modèle de nom de fichier
+const TEMPLATE_TOKENS: &[&str] = &["title", "id", "date", "channel"];
+
+/// Valeurs substituées aux jetons d'un modèle
+pub struct TemplateValues<'a> {
+    pub title: &'a str,
+    pub id: &'a str,
+    /// Date de mise en ligne yt-dlp (AAAAMMJJ), écrite AAAA-MM-JJ
+    pub date: Option<&'a str>,
+    pub channel: &'a str,
+}
+
+impl TemplateValues<'_> {
+    fn get(&self, token: &str) -> String {
+        match token {
+            "title" => self.title.to_string(),
+            "id" => self.id.to_string(),
+            "date" => self.date.map(format_date).unwrap_or_default(),
+            "channel" => self.channel.to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+fn format_date(date: &str) -> String {
+    if date.len() == 8 && date.chars().all(|c| c.is_ascii_digit()) {
+        format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..])
+    } else {
+        date.to_string()
+    }
+}
+
+enum TemplatePart<'a> {
+    Text(&'a str),
+    Token(&'a str),
+}
+
+/// Découpe un modèle en texte fixe et jetons (`{title}`, `{id}`, `{date}`, `{channel}`)
+fn parse_template(template: &str) -> Result<Vec<TemplatePart<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err("Accolade fermante sans accolade ouvrante".to_string());
+        }
+        parts.push(TemplatePart::Text(&rest[..start]));
+        let Some(length) = rest[start + 1..].find('}') else {
+            return Err("Accolade ouvrante non refermée".to_string());
+        };
+        let token = &rest[start + 1..start + 1 + length];
+        if !TEMPLATE_TOKENS.contains(&token) {
+            return Err(format!(
+                "Jeton inconnu {{{token}}} (disponibles: {{title}}, {{id}}, {{date}}, {{channel}})"
+            ));
+        }
+        parts.push(TemplatePart::Token(token));
+        rest = &rest[start + length + 2..];
+    }
+    parts.push(TemplatePart::Text(rest));
+    if !parts
+        .iter()
+        .any(|part| matches!(part, TemplatePart::Token(_)))
+    {
+        return Err("Le modèle doit contenir au moins un jeton, par exemple {title}".to_string());
+    }
+    Ok(parts)
+}
+
+/// Vérifie un modèle de nom de fichier saisi dans les paramètres
+pub fn validate_template(template: &str) -> Result<(), String> {
+    parse_template(template).map(|_| ())
+}
+
+/// Nom de fichier tiré d'un modèle, nettoyé après substitution. Un modèle invalide
+/// ou un nom vide une fois nettoyé retombent sur le titre seul.
+pub fn from_template(template: &str, values: &TemplateValues) -> String {
+    let name = match parse_template(template) {
+        Ok(parts) => parts
+            .into_iter()
+            .map(|part| match part {
+                TemplatePart::Text(text) => text.to_string(),
+                TemplatePart::Token(token) => values.get(token),
+            })
+            .collect(),
+        Err(error) => {
+            tracing::warn!("Invalid filename template {:?}: {}", template, error);
+            String::new()
+        }
+    };
+    match sanitize(&name) {
+        name if name.is_empty() => sanitize(values.title),
+        name => name,
+    }
+}
+
+/// Nom du dossier d'une chaîne dans un stockage, nettoyé comme un nom de fichier
+/// (titres de playlist et noms de chaîne peuvent contenir `/` ou `:`)
+pub fn directory_name(channel_name: &str) -> String {
+    let name = sanitize(channel_name);
+    if name.is_empty() {
+        "_".to_string()
+    } else {
+        name
+    }
+}
+
+/// Premier chemin libre parmi `nom.ext`, `nom (2).ext`, `nom (3).ext`...
+/// `is_taken` indique si un chemin est déjà utilisé (fichier existant, téléchargement en cours).
+pub fn unique_path(path: &Path, is_taken: impl Fn(&Path) -> bool) -> PathBuf {
+    if !is_taken(path) {
+        return path.to_path_buf();
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+
+    (2..)
+        .map(|n| path.with_file_name(format!("{stem} ({n}){extension}")))
+        .find(|candidate| !is_taken(candidate))
+        .expect("un nom libre finit toujours par être trouvé")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_removes_forbidden_characters() {
+        assert_eq!(sanitize("AC/DC: Live?"), "AC DC Live");
+        assert_eq!(sanitize("a\tb\nc"), "a b c");
+        assert_eq!(sanitize("<|>"), "");
+    }
+
+    #[test]
+    fn test_sanitize_rejects_path_tricks_and_reserved_names() {
+        assert_eq!(sanitize("../../etc/passwd"), ".. .. etc passwd");
+        assert_eq!(sanitize(".."), "");
+        assert_eq!(sanitize("con"), "con_");
+        assert_eq!(sanitize("LPT1.backup"), "LPT1_.backup");
+        assert_eq!(sanitize("Console"), "Console");
+    }
+
+    #[test]
+    fn test_sanitize_collapses_whitespace_and_trims() {
+        assert_eq!(sanitize("  Hello    world  "), "Hello world");
+        assert_eq!(sanitize("Ending dots..."), "Ending dots");
+    }
+
+    #[test]
+    fn test_sanitize_limits_length() {
+        let long = "a".repeat(300);
+        assert_eq!(sanitize(&long).chars().count(), MAX_LENGTH);
+    }
+
+    #[test]
+    fn test_sanitize_limits_bytes_of_multibyte_titles() {
+        for title in [
+            "日本語のタイトル".repeat(40),
+            "🎵".repeat(100),
+            "é".repeat(300),
+        ] {
+            let name = sanitize(&title);
+            assert!(name.len() <= MAX_BYTES, "{}", name.len());
+            assert!(title.starts_with(&name));
+            // Nom le plus long écrit par yt-dlp pendant le téléchargement
+            let partial = format!("{name} (12).f251-drc.webm.part-Frag123");
+            assert!(partial.len() <= 255, "{}", partial.len());
+        }
+    }
+
+    #[test]
+    fn test_directory_name() {
+        assert_eq!(directory_name("Mix: 80/90"), "Mix 80 90");
+        assert_eq!(directory_name("streamer"), "streamer");
+        assert_eq!(directory_name("//"), "_");
+    }
+
+    fn values() -> TemplateValues<'static> {
+        TemplateValues {
+            title: "AC/DC: Live",
+            id: "abc123",
+            date: Some("20250314"),
+            channel: "handle",
+        }
+    }
+
+    #[test]
+    fn test_from_template() {
+        let values = values();
+        assert_eq!(from_template(DEFAULT_TEMPLATE, &values), "AC DC Live");
+        assert_eq!(
+            from_template("{date} - {title}", &values),
+            "2025-03-14 - AC DC Live"
+        );
+        assert_eq!(
+            from_template("[{channel}] {title} ({id})", &values),
+            "[handle] AC DC Live (abc123)"
+        );
+
+        // Date inconnue: le jeton est simplement vide
+        let undated = TemplateValues {
+            date: None,
+            ..values
+        };
+        assert_eq!(from_template("{date}", &undated), "AC DC Live");
+        assert_eq!(from_template("{date} {id}", &undated), "abc123");
+    }
+
+    #[test]
+    fn test_invalid_template_falls_back_to_title() {
+        for template in ["{titre}", "{title", "title}", "sans jeton", ""] {
+            assert!(validate_template(template).is_err(), "{template}");
+            assert_eq!(from_template(template, &values()), "AC DC Live");
+        }
+        assert!(validate_template("{date} - {title}").is_ok());
+    }
+
+    #[test]
+    fn test_unique_path_appends_counter() {
+        let taken = [
+            PathBuf::from("/videos/chan/name.mp4"),
+            PathBuf::from("/videos/chan/name (2).mp4"),
+        ];
+        let is_taken = |path: &Path| taken.iter().any(|t| t == path);
+
+        assert_eq!(
+            unique_path(Path::new("/videos/chan/other.mp4"), is_taken),
+            PathBuf::from("/videos/chan/other.mp4")
+        );
+        assert_eq!(
+            unique_path(Path::new("/videos/chan/name.mp4"), is_taken),
+            PathBuf::from("/videos/chan/name (3).mp4")
+        );
+    }
+}
@@ -1,19 +1,60 @@
 use anyhow::Result;
+use clap::Parser;
 use gpui::*;
+use std::process::ExitCode;
 
 mod cache;
+mod cli;
+mod cli_download;
+mod config;
+mod database;
 mod downloader_queue;
+mod filename;
+mod monitor;
 mod notifications;
+mod paths;
 mod scanner;
+mod throttle;
+mod thumbnails;
 mod ui;
 
 use ui::{actions::*, NDownloaderApp};
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
+    // --help, --version et les arguments invalides s'arrêtent ici avec le message de clap
+    let cli = cli::Cli::parse();
+
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::WARN)
         .init();
 
+    if let Some(path) = cli.config {
+        paths::set_config_file(path);
+    }
+
+    if let Some(cli::Command::Download { url, channel, name }) = cli.command {
+        let status = cli_download::download(&url, &channel, name.as_deref())?;
+        return Ok(cli_download::exit_code(&status));
+    }
+
+    // Ajout de chaînes en ligne de commande, sans interface
+    if !cli.add.is_empty() {
+        let mut failed = 0;
+        for url in &cli.add {
+            match ui::add_channel(url) {
+                Ok(name) => println!("Chaîne ajoutée: {name}"),
+                Err(error) => {
+                    eprintln!("{url}: {error:#}");
+                    failed += 1;
+                }
+            }
+        }
+        if failed > 0 {
+            anyhow::bail!("{failed} URL(s) n'ont pas pu être ajoutée(s)");
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
     Application::new().run(|cx: &mut App| {
         cx.activate(true);
         cx.on_action(quit);
@@ -25,13 +66,23 @@ fn main() -> Result<()> {
             KeyBinding::new("escape", GoBack, None),
             KeyBinding::new("cmd-w", GoBack, None),
             KeyBinding::new("ctrl-w", GoBack, None),
+            KeyBinding::new("cmd-shift-]", GoForward, None),
+            KeyBinding::new("ctrl-shift-]", GoForward, None),
+            KeyBinding::new("cmd-l", FocusUrlInput, None),
+            KeyBinding::new("ctrl-l", FocusUrlInput, None),
+            KeyBinding::new("f5", RefreshVideos, None),
+            KeyBinding::new("ctrl-r", RefreshVideos, None),
         ]);
 
-        let bounds = Bounds::centered(None, size(px(1200.0), px(800.0)), cx);
+        let min_size = gpui::Size {
+            width: px(800.),
+            height: px(600.),
+        };
+        let window_bounds = ui::restored_window_bounds(min_size, cx);
 
         let _window = cx.open_window(
             WindowOptions {
-                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                window_bounds: Some(window_bounds),
                 titlebar: Some(TitlebarOptions {
                     title: Some("NDownloader".into()),
                     appears_transparent: false,
@@ -43,17 +94,14 @@ fn main() -> Result<()> {
                 kind: WindowKind::Normal,
                 is_movable: true,
                 display_id: None,
-                window_min_size: Some(gpui::Size {
-                    width: px(800.),
-                    height: px(600.),
-                }),
+                window_min_size: Some(min_size),
                 ..Default::default()
             },
             |window, cx| cx.new(|cx| NDownloaderApp::new(window, cx)),
         );
     });
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
 fn quit(_: &Quit, cx: &mut App) {
@@ -0,0 +1,140 @@
+use crate::scanner::VideoMetadata;
+use std::collections::{HashMap, HashSet};
+
+/// Mémorise les vidéos déjà vues par la surveillance périodique des chaînes
+#[derive(Default)]
+pub struct ChannelMonitor {
+    known: HashMap<String, HashSet<String>>, // URL de chaîne -> URLs des vidéos vues
+    auto_enqueued: HashSet<String>,          // URLs déjà envoyées au téléchargement automatique
+}
+
+impl ChannelMonitor {
+    /// Retourne les vidéos apparues depuis la vérification précédente et pas encore téléchargées.
+    /// La première vérification d'une chaîne sert de référence et ne signale rien.
+    pub fn new_videos(
+        &mut self,
+        channel_url: &str,
+        videos: &[VideoMetadata],
+        downloaded: &HashSet<String>,
+    ) -> Vec<VideoMetadata> {
+        let Some(known) = self.known.get_mut(channel_url) else {
+            self.known.insert(
+                channel_url.to_string(),
+                videos.iter().map(|video| video.url.clone()).collect(),
+            );
+            return Vec::new();
+        };
+
+        videos
+            .iter()
+            .filter(|video| known.insert(video.url.clone()))
+            .filter(|video| !downloaded.contains(&video.url))
+            .cloned()
+            .collect()
+    }
+
+    /// Vidéos à télécharger automatiquement : publiées depuis `since` (YYYYMMDD, date d'ajout
+    /// de la chaîne), pas encore téléchargées ni déjà proposées pendant cette session.
+    /// Les vidéos sans date de publication sont ignorées par prudence.
+    pub fn auto_download_candidates(
+        &mut self,
+        videos: &[VideoMetadata],
+        downloaded: &HashSet<String>,
+        since: &str,
+    ) -> Vec<VideoMetadata> {
+        videos
+            .iter()
+            .filter(|video| {
+                video
+                    .upload_date
+                    .as_deref()
+                    .is_some_and(|date| date >= since)
+            })
+            .filter(|video| !downloaded.contains(&video.url))
+            .filter(|video| self.auto_enqueued.insert(video.url.clone()))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Message de notification: "3 nouvelles vidéos sur {channel}"
+pub fn new_videos_message(count: usize, channel_name: &str) -> String {
+    if count == 1 {
+        format!("1 nouvelle vidéo sur {channel_name}")
+    } else {
+        format!("{count} nouvelles vidéos sur {channel_name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(url: &str) -> VideoMetadata {
+        dated_video(url, None)
+    }
+
+    fn dated_video(url: &str, upload_date: Option<&str>) -> VideoMetadata {
+        VideoMetadata {
+            id: url.to_string(),
+            title: url.to_string(),
+            url: url.to_string(),
+            duration: None,
+            upload_date: upload_date.map(str::to_string),
+            uploader: None,
+            thumbnail: None,
+            channel_id: None,
+        }
+    }
+
+    #[test]
+    fn test_new_videos_after_baseline() {
+        let mut monitor = ChannelMonitor::default();
+        let downloaded = HashSet::from(["c".to_string()]);
+
+        // Première vérification: référence silencieuse
+        let first = monitor.new_videos("chan", &[video("a"), video("b")], &downloaded);
+        assert!(first.is_empty());
+
+        let second = monitor.new_videos(
+            "chan",
+            &[video("c"), video("d"), video("a"), video("b")],
+            &downloaded,
+        );
+        let urls: Vec<_> = second.iter().map(|v| v.url.as_str()).collect();
+        assert_eq!(urls, ["d"]);
+
+        // Une vidéo déjà signalée ne l'est plus
+        assert!(monitor
+            .new_videos("chan", &[video("d"), video("a")], &downloaded)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_auto_download_skips_back_catalog() {
+        let mut monitor = ChannelMonitor::default();
+        let downloaded = HashSet::from(["done".to_string()]);
+        let videos = [
+            dated_video("old", Some("20240101")),
+            dated_video("same-day", Some("20240601")),
+            dated_video("new", Some("20240615")),
+            dated_video("done", Some("20240620")),
+            dated_video("undated", None),
+        ];
+
+        let candidates = monitor.auto_download_candidates(&videos, &downloaded, "20240601");
+        let urls: Vec<_> = candidates.iter().map(|v| v.url.as_str()).collect();
+        assert_eq!(urls, ["same-day", "new"]);
+
+        // Une vidéo n'est proposée qu'une fois, même si son téléchargement échoue
+        assert!(monitor
+            .auto_download_candidates(&videos, &downloaded, "20240601")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_new_videos_message() {
+        assert_eq!(new_videos_message(1, "Chan"), "1 nouvelle vidéo sur Chan");
+        assert_eq!(new_videos_message(3, "Chan"), "3 nouvelles vidéos sur Chan");
+    }
+}
@@ -1,20 +1,43 @@
-use std::process::Command;
+use futures::channel::mpsc;
+use notify_rust::Notification as DesktopNotification;
+use parking_lot::Mutex;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum NotificationType {
     Success,
     Error,
     Info,
 }
 
+/// Notification transmise à l'interface pour l'affichage dans la fenêtre
+#[derive(Debug, Clone)]
+pub struct NotificationEvent {
+    pub notification_type: NotificationType,
+    pub title: String,
+    pub message: String,
+}
+
+/// Abonné unique recevant une copie de chaque notification
+static SUBSCRIBER: Mutex<Option<mpsc::UnboundedSender<NotificationEvent>>> = Mutex::new(None);
+
+/// Reçoit les notifications envoyées à partir de maintenant (remplace l'abonné précédent)
+pub fn subscribe() -> mpsc::UnboundedReceiver<NotificationEvent> {
+    let (tx, rx) = mpsc::unbounded();
+    *SUBSCRIBER.lock() = Some(tx);
+    rx
+}
+
 pub struct Notification;
 
 impl Notification {
     pub fn send(notification_type: NotificationType, title: &str, message: &str) {
-        let urgency = match notification_type {
-            NotificationType::Success => "normal",
-            NotificationType::Error => "critical",
-            NotificationType::Info => "low",
-        };
+        if let Some(subscriber) = SUBSCRIBER.lock().as_ref() {
+            let _ = subscriber.unbounded_send(NotificationEvent {
+                notification_type,
+                title: title.to_string(),
+                message: message.to_string(),
+            });
+        }
 
         let icon = match notification_type {
             NotificationType::Success => "dialog-information",
@@ -22,21 +45,26 @@ impl Notification {
             NotificationType::Info => "dialog-information",
         };
 
-        // Utiliser notify-send pour les notifications système
-        let result = Command::new("notify-send")
-            .arg("--urgency")
-            .arg(urgency)
-            .arg("--icon")
-            .arg(icon)
-            .arg("--app-name")
-            .arg("NDownloader")
-            .arg(title)
-            .arg(message)
-            .spawn();
-
-        if let Err(error) = result {
-            tracing::warn!("Failed to send system notification: {}", error);
-        }
+        let mut notification = DesktopNotification::new();
+        notification
+            .appname("NDownloader")
+            .summary(title)
+            .body(message)
+            .icon(icon);
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        notification.urgency(match notification_type {
+            NotificationType::Success => notify_rust::Urgency::Normal,
+            NotificationType::Error => notify_rust::Urgency::Critical,
+            NotificationType::Info => notify_rust::Urgency::Low,
+        });
+
+        // L'envoi (D-Bus sous Linux) peut bloquer : ne pas geler l'interface
+        std::thread::spawn(move || {
+            if let Err(error) = notification.show() {
+                tracing::warn!("Failed to send system notification: {}", error);
+            }
+        });
     }
 
     pub fn success(title: &str, message: &str) {
@@ -55,6 +83,7 @@ impl Notification {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::StreamExt;
 
     #[test]
     fn test_notification_methods_dont_panic() {
@@ -63,4 +92,23 @@ mod tests {
         Notification::error("Test", "Error");
         Notification::info("Test", "Info");
     }
+
+    #[test]
+    fn test_subscriber_receives_notifications() {
+        let mut events = subscribe();
+        Notification::error("Scan", "yt-dlp a échoué");
+
+        // D'autres tests peuvent notifier en parallèle
+        let event = smol::block_on(async {
+            loop {
+                let event = events.next().await.unwrap();
+                if event.title == "Scan" {
+                    break event;
+                }
+            }
+        });
+        assert_eq!(event.notification_type, NotificationType::Error);
+        assert_eq!(event.title, "Scan");
+        assert_eq!(event.message, "yt-dlp a échoué");
+    }
 }
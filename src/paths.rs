@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const APP_DIR: &str = "ndownloader";
+
+/// Fichier de configuration choisi en ligne de commande (`--config`)
+static CONFIG_FILE_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Liste des chaînes surveillées : `$XDG_DATA_HOME/ndownloader/channels.json`
+pub fn channels_file() -> PathBuf {
+    resolve(
+        dirs::data_dir(),
+        "channels.json",
+        "/tmp/ndownloader_channels.json",
+    )
+}
+
+/// Historique des téléchargements : `$XDG_DATA_HOME/ndownloader/downloads.db`
+pub fn database_file() -> PathBuf {
+    resolve(
+        dirs::data_dir(),
+        "downloads.db",
+        "/tmp/ndownloader_downloads.db",
+    )
+}
+
+/// Position et taille de la fenêtre : `$XDG_DATA_HOME/ndownloader/window.json`
+pub fn window_state_file() -> PathBuf {
+    resolve(
+        dirs::data_dir(),
+        "window.json",
+        "/tmp/ndownloader_window.json",
+    )
+}
+
+/// Cache des scans yt-dlp : `$XDG_CACHE_HOME/ndownloader/videos_cache.json`
+pub fn videos_cache_file() -> PathBuf {
+    resolve(
+        dirs::cache_dir(),
+        "videos_cache.json",
+        "/tmp/ndownload_videos_cache.json",
+    )
+}
+
+/// Miniatures téléchargées : `$XDG_CACHE_HOME/ndownloader/thumbnails/`
+pub fn thumbnails_dir() -> PathBuf {
+    let dir = resolve(
+        dirs::cache_dir(),
+        "thumbnails",
+        "/tmp/ndownloader_thumbnails",
+    );
+    if let Err(error) = std::fs::create_dir_all(&dir) {
+        tracing::warn!("Failed to create directory {}: {}", dir.display(), error);
+    }
+    dir
+}
+
+/// Remplace l'emplacement du fichier de configuration, avant tout chargement
+pub fn set_config_file(path: PathBuf) {
+    // Chemin absolu: le dossier parent est surveillé pour recharger la configuration
+    let path = std::path::absolute(&path).unwrap_or(path);
+    if CONFIG_FILE_OVERRIDE.set(path).is_err() {
+        tracing::warn!("Config file location already set");
+    }
+}
+
+/// Fichier de configuration : `$XDG_CONFIG_HOME/ndownloader/config.json`,
+/// sauf s'il a été choisi en ligne de commande
+pub fn config_file() -> PathBuf {
+    if let Some(path) = CONFIG_FILE_OVERRIDE.get() {
+        return path.clone();
+    }
+    resolve(
+        dirs::config_dir(),
+        "config.json",
+        "/tmp/ndownloader_config.json",
+    )
+}
+
+/// Construit le chemin dans le dossier de l'application en créant les parents,
+/// ou retombe sur l'ancien emplacement dans `/tmp` si c'est impossible
+fn resolve(base: Option<PathBuf>, file_name: &str, fallback: &str) -> PathBuf {
+    let Some(dir) = base.map(|base| base.join(APP_DIR)) else {
+        return PathBuf::from(fallback);
+    };
+
+    if let Err(error) = std::fs::create_dir_all(&dir) {
+        tracing::warn!("Failed to create directory {}: {}", dir.display(), error);
+        return PathBuf::from(fallback);
+    }
+
+    dir.join(file_name)
+}
@@ -1,11 +1,20 @@
 use crate::cache::Cache;
+use crate::config::Config;
+use crate::database::Database;
+use crate::filename;
+use crate::paths;
+use crate::throttle::{is_rate_limited, Cooldowns, RateLimited, YtDlpGate};
 use anyhow::Result;
+use chrono::NaiveDate;
+use futures::StreamExt;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use smol::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct VideoMetadata {
@@ -18,191 +27,1657 @@ pub struct VideoMetadata {
     pub upload_date: Option<String>,
     #[serde(default)]
     pub uploader: Option<String>,
+    /// URL de la miniature (absente des entrées mises en cache avant son ajout)
+    #[serde(default)]
+    pub thumbnail: Option<String>,
+    /// Identifiant de la chaîne chez la plateforme (ex: UC... sur YouTube)
+    #[serde(default)]
+    pub channel_id: Option<String>,
+}
+
+/// Image associée à une chaîne dans le JSON de yt-dlp
+#[derive(Debug, Clone, Deserialize)]
+struct ThumbnailMetadata {
+    url: String,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+}
+
+/// Informations d'une chaîne ou d'une playlist, sans la liste de ses vidéos
+#[derive(Debug, Deserialize)]
+struct ChannelMetadata {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    thumbnails: Vec<ThumbnailMetadata>,
+}
+
+/// Choisit l'avatar parmi les images d'une chaîne : `avatar_uncropped` sur YouTube,
+/// sinon la plus grande image carrée (les bannières sont larges)
+fn pick_avatar(thumbnails: &[ThumbnailMetadata]) -> Option<String> {
+    if let Some(avatar) = thumbnails
+        .iter()
+        .find(|t| t.id.as_deref() == Some("avatar_uncropped"))
+    {
+        return Some(avatar.url.clone());
+    }
+
+    thumbnails
+        .iter()
+        .filter(|t| matches!((t.width, t.height), (Some(w), Some(h)) if w == h))
+        .max_by_key(|t| t.width)
+        .map(|t| t.url.clone())
+}
+
+/// Auteur d'une vidéo dans le JSON de yt-dlp
+#[derive(Debug, Default, Deserialize)]
+struct VideoOwner {
+    #[serde(default)]
+    uploader_url: Option<String>,
+    #[serde(default)]
+    channel_url: Option<String>,
+    #[serde(default)]
+    uploader_id: Option<String>,
+    #[serde(default)]
+    extractor_key: Option<String>,
+}
+
+/// Vidéo seule décrite par `yt-dlp --dump-json` (son champ `url` est celui du flux)
+#[derive(Debug, Deserialize)]
+struct VideoDetails {
+    id: String,
+    title: String,
+    #[serde(default)]
+    duration: Option<f64>,
+    #[serde(default)]
+    upload_date: Option<String>,
+    #[serde(default)]
+    uploader: Option<String>,
+    #[serde(default)]
+    thumbnail: Option<String>,
+    #[serde(default)]
+    channel_id: Option<String>,
+}
+
+/// URL de la chaîne d'une vidéo : le handle YouTube (`/@nom`) de préférence,
+/// sinon l'URL de chaîne ; pour une VOD Twitch, la chaîne du streamer
+fn owner_channel_url(owner: &VideoOwner) -> Option<String> {
+    if owner.extractor_key.as_deref() == Some("TwitchVod") {
+        return owner
+            .uploader_id
+            .as_ref()
+            .map(|id| format!("https://www.twitch.tv/{id}"));
+    }
+
+    owner
+        .uploader_url
+        .clone()
+        .filter(|url| url.contains("/@"))
+        .or_else(|| owner.channel_url.clone())
+        .or_else(|| owner.uploader_url.clone())
+}
+
+/// Vidéos lues au fil de la sortie de yt-dlp, dans l'ordre et sans doublon d'identifiant
+/// (une vidéo peut apparaître dans plusieurs sections d'une chaîne)
+#[derive(Default)]
+struct ScannedVideos {
+    videos: Vec<VideoMetadata>,
+    ids: HashSet<String>,
+}
+
+impl ScannedVideos {
+    /// Ajoute la vidéo décrite par une ligne JSON et la retourne si elle est nouvelle
+    fn push_line(&mut self, line: &str) -> Option<&VideoMetadata> {
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        let video = match serde_json::from_str::<VideoMetadata>(line) {
+            Ok(video) => video,
+            Err(e) => {
+                tracing::warn!("Erreur parsing JSON: {} - ligne: {}", e, line);
+                return None;
+            }
+        };
+        if !self.ids.insert(video.id.clone()) {
+            tracing::debug!("Vidéo en double ignorée: {}", video.id);
+            return None;
+        }
+
+        tracing::debug!("Vidéo: {} - durée: {:?}", video.title, video.duration);
+        self.videos.push(video);
+        self.videos.last()
+    }
+}
+
+/// Refuse un disque dont l'espace libre est sous `min_free_bytes`.
+/// La taille des vidéos n'étant pas connue avant le téléchargement (directs notamment),
+/// seul ce seuil est vérifié.
+fn check_free_space(storage: &StorageInfo, min_free_bytes: u64) -> Result<()> {
+    const MB: u64 = 1024 * 1024;
+    if storage.available_bytes < min_free_bytes {
+        anyhow::bail!(
+            "Espace disque insuffisant sur {}: {} Mo libres, {} Mo minimum",
+            storage.path,
+            storage.available_bytes / MB,
+            min_free_bytes / MB
+        );
+    }
+    Ok(())
+}
+
+/// Dossiers où udisks monte les disques amovibles: `<dossier>/<utilisateur>/<disque>`,
+/// ou `/media/<disque>` selon la distribution
+const REMOVABLE_MOUNT_ROOTS: [&str; 2] = ["/run/media", "/media"];
+
+/// Points de montage déclarés dans un fstab (hors racine et swap)
+fn parse_fstab_targets(fstab: &str) -> Vec<PathBuf> {
+    fstab
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .filter(|target| target.starts_with('/') && *target != "/")
+        // Les espaces sont écrits en octal dans le fstab
+        .map(|target| PathBuf::from(target.replace("\\040", " ")))
+        .collect()
+}
+
+/// Points de montage dont dépend un chemin de stockage: la cible du fstab la plus
+/// proche qui le contient, sinon les dossiers de disques amovibles qui le contiennent.
+/// Vide pour un simple dossier du système, toujours disponible.
+fn required_mount_points(path: &Path, fstab_targets: &[PathBuf]) -> Vec<PathBuf> {
+    if let Some(target) = fstab_targets
+        .iter()
+        .filter(|target| path.starts_with(target))
+        .max_by_key(|target| target.components().count())
+    {
+        return vec![target.clone()];
+    }
+    REMOVABLE_MOUNT_ROOTS
+        .iter()
+        .find_map(|root| {
+            let relative = path.strip_prefix(root).ok()?;
+            // Le disque est monté sur l'un des deux premiers niveaux sous le dossier
+            let candidates = (1..=relative.components().count().min(2))
+                .map(|level| {
+                    Path::new(root).join(relative.components().take(level).collect::<PathBuf>())
+                })
+                .collect();
+            Some(candidates)
+        })
+        .unwrap_or_default()
+}
+
+/// Le dossier est-il la racine d'un système de fichiers monté ? Un point de montage
+/// vide, disque démonté, est sur le même périphérique que son parent.
+fn is_mount_point(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    let Some(parent) = path.parent() else {
+        return true;
+    };
+    match (std::fs::metadata(path), std::fs::metadata(parent)) {
+        (Ok(metadata), Ok(parent)) => metadata.dev() != parent.dev(),
+        _ => false,
+    }
+}
+
+/// Le disque d'un chemin de stockage est-il là ? Un disque démonté laisse souvent son
+/// point de montage vide derrière lui: le dossier existe mais écrire dedans remplirait
+/// le disque système.
+pub fn is_storage_available(path: &Path) -> bool {
+    if !path.is_dir() {
+        return false;
+    }
+    let fstab_targets = std::fs::read_to_string("/etc/fstab")
+        .map(|fstab| parse_fstab_targets(&fstab))
+        .unwrap_or_default();
+    let mount_points = required_mount_points(path, &fstab_targets);
+    mount_points.is_empty() || mount_points.iter().any(|mount| is_mount_point(mount))
+}
+
+/// Taille de la liste scannée, indiquée par yt-dlp sur chaque vidéo
+#[derive(Debug, Deserialize)]
+struct PlaylistPosition {
+    playlist_count: Option<usize>,
+}
+
+/// Nombre de vidéos attendues pour une page commençant à `playlist_start` (à partir de 1)
+fn expected_videos(
+    playlist_count: Option<usize>,
+    playlist_start: usize,
+    limit: usize,
+) -> Option<usize> {
+    playlist_count.map(|count| count.saturating_sub(playlist_start - 1).min(limit))
+}
+
+/// Clé de cache d'une chaîne, la même quelle que soit la forme de son URL: protocole,
+/// `www.`, `/` final, paramètres de suivi et, pour Twitch, liste `/videos` ou page de la chaîne
+fn channel_cache_key(url: &str) -> String {
+    let url = url.split('#').next().unwrap_or_default();
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let path = path
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let path = path
+        .strip_prefix("www.")
+        .or_else(|| path.strip_prefix("m."))
+        .unwrap_or(path)
+        .trim_end_matches('/');
+
+    if let Some(rest) = path.strip_prefix("twitch.tv/") {
+        let (login, tab) = rest.split_once('/').unwrap_or((rest, ""));
+        // Une VOD seule (twitch.tv/videos/ID) ou un autre onglet garde son chemin
+        if login != "videos" && (tab.is_empty() || tab == "videos") {
+            return format!("twitch.tv/{}", login.to_lowercase());
+        }
+        return path.to_string();
+    }
+
+    // Une playlist YouTube n'est identifiée que par son paramètre `list`
+    match query.split('&').find(|param| param.starts_with("list=")) {
+        Some(list) => format!("{path}?{list}"),
+        None => path.to_string(),
+    }
+}
+
+/// Vidéos d'un scan, avec les avertissements de yt-dlp qui peuvent expliquer
+/// une liste incomplète (formats indisponibles, limitation de débit...)
+#[derive(Debug)]
+pub struct ChannelScan {
+    pub videos: Vec<VideoMetadata>,
+    pub warnings: Vec<String>,
+}
+
+/// Avertissements (`WARNING: ...`) de la sortie d'erreur d'un yt-dlp qui a réussi, sans doublon
+fn parse_warnings(stderr: &str) -> Vec<String> {
+    let mut warnings: Vec<String> = Vec::new();
+    for warning in stderr
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("WARNING:"))
+        .map(str::trim)
+        .filter(|warning| !warning.is_empty())
+    {
+        if !warnings.iter().any(|known| known == warning) {
+            warnings.push(warning.to_string());
+        }
+    }
+    warnings
+}
+
+/// Programme externe (yt-dlp, ffprobe) absent du PATH
+#[derive(Debug)]
+pub struct MissingBinary(pub &'static str);
+
+impl std::fmt::Display for MissingBinary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} introuvable — installez-le et réessayez", self.0)
+    }
+}
+
+impl std::error::Error for MissingBinary {}
+
+/// Convertit l'échec de lancement d'un programme, en distinguant le binaire manquant
+pub fn spawn_error(program: &'static str, error: std::io::Error) -> anyhow::Error {
+    if error.kind() == std::io::ErrorKind::NotFound {
+        MissingBinary(program).into()
+    } else {
+        anyhow::Error::new(error).context(format!("Impossible de lancer {program}"))
+    }
+}
+
+/// Âge au-delà duquel yt-dlp est considéré comme obsolète
+const YT_DLP_MAX_AGE_DAYS: i64 = 183;
+
+/// Lit une version yt-dlp datée ("2024.08.06", "2024.08.06.232815" pour les nightly)
+fn parse_yt_dlp_version(output: &str) -> Option<NaiveDate> {
+    let mut parts = output.trim().split('.');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// Une version de yt-dlp a-t-elle plus de six mois ?
+pub fn is_yt_dlp_outdated(version: NaiveDate, today: NaiveDate) -> bool {
+    (today - version).num_days() > YT_DLP_MAX_AGE_DAYS
+}
+
+/// Vérifie qu'un fichier de cookies (format Netscape, ex: cookies.txt exporté) existe
+pub fn validate_cookies_file(path: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(expand_home(path.trim()));
+    if !path.is_file() {
+        anyhow::bail!("Fichier de cookies introuvable: {}", path.display());
+    }
+    Ok(path)
+}
+
+/// Remplace un `~` initial par le dossier personnel de l'utilisateur
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix('~'), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{home}{rest}")
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// Espace disque d'un chemin de stockage
+#[derive(Debug, Clone)]
+pub struct StorageInfo {
+    pub path: String,
+    pub available_bytes: u64,
+    pub total_bytes: u64,
 }
 
 pub struct VideoScanner {
     storage_paths: Vec<String>,
+    scan_limit: usize,
+    scan_depth: usize,
+    video_extensions: Vec<String>,
+    duration_tolerance: f64,
+    cookies_file: Option<PathBuf>,
+    min_free_bytes: u64,
+    database: Option<Arc<Database>>,
     cache: Cache<Vec<VideoMetadata>>,
-    file_durations_cache: Arc<Mutex<HashMap<String, f64>>>,
+    file_probes_cache: Arc<Mutex<HashMap<String, FileProbe>>>,
+    yt_dlp_version: Mutex<Option<NaiveDate>>,
+    /// Appels à yt-dlp un par un et espacés, pour ne pas déclencher de limitation
+    gate: YtDlpGate,
+    /// Chaînes en pause après une réponse « trop de requêtes », par clé de cache
+    cooldowns: Mutex<Cooldowns>,
+}
+
+/// Nombre maximum de ffprobe lancés en parallèle
+const MAX_CONCURRENT_FFPROBE: usize = 4;
+
+/// Extension du fichier qui mémorise l'identifiant de la vidéo source
+const ID_EXTENSION: &str = "id";
+
+/// Chemin du fichier contenant l'identifiant d'une vidéo téléchargée
+pub fn id_sidecar_path(video_path: &Path) -> PathBuf {
+    video_path.with_extension(ID_EXTENSION)
+}
+
+/// Lit l'identifiant enregistré à côté d'une vidéo, s'il existe
+fn read_video_id(video_path: &Path) -> Option<String> {
+    let id = std::fs::read_to_string(id_sidecar_path(video_path)).ok()?;
+    let id = id.trim();
+    (!id.is_empty()).then(|| id.to_string())
 }
 
+/// Fichiers vidéo d'une chaîne relevés pour une recherche
+struct ChannelFiles {
+    /// Fichiers par identifiant enregistré à côté (`.id`)
+    identified: HashMap<String, PathBuf>,
+    /// Fichiers sans identifiant, reconnus par ffprobe
+    unidentified: Vec<PathBuf>,
+    /// Disques démontés au moment du relevé
+    unavailable_storage: Vec<String>,
+}
+
+/// Informations lues par ffprobe sur un fichier local
+#[derive(Debug, Clone, Default, PartialEq)]
+struct FileProbe {
+    duration: Option<f64>,
+    /// URL de la vidéo source, intégrée au téléchargement par `--embed-metadata`
+    source_url: Option<String>,
+}
+
+/// Sortie JSON de `ffprobe -show_entries format=duration:format_tags`
+#[derive(Debug, Deserialize)]
+struct ProbeOutput {
+    #[serde(default)]
+    format: Option<ProbeFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeFormat {
+    #[serde(default)]
+    duration: Option<String>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+}
+
+/// Extrait durée et URL source de la sortie de ffprobe.
+/// yt-dlp écrit l'URL dans les tags `purl` et `comment` (en majuscules dans un mkv).
+fn parse_probe_output(output: &str) -> Option<FileProbe> {
+    let format = serde_json::from_str::<ProbeOutput>(output).ok()?.format?;
+    let tag = |name: &str| {
+        format
+            .tags
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim().to_string())
+    };
+    let source_url = tag("purl")
+        .or_else(|| tag("comment"))
+        .filter(|url| url.starts_with("http"));
+
+    Some(FileProbe {
+        duration: format.duration.and_then(|d| d.trim().parse().ok()),
+        source_url,
+    })
+}
+
+/// L'URL source d'un fichier désigne-t-elle cette vidéo (watch?v=ID, /videos/ID...) ?
+fn source_matches(source_url: &str, video_id: &str) -> bool {
+    source_url
+        .split(['/', '?', '&', '=', '#'])
+        .any(|part| part == video_id)
+}
+
+/// Extensions reconnues par défaut (mp3 pour les téléchargements audio seul)
+pub const DEFAULT_VIDEO_EXTENSIONS: &[&str] = &["mp4", "mkv", "webm", "mov", "mp3"];
+
+const DEFAULT_STORAGE_PATHS: &[&str] = &[
+    "/run/mount/ve_stock_1",
+    "/run/mount/ve_stock_2",
+    "/run/mount/ve_ext_1",
+];
+
 impl VideoScanner {
-    pub fn new() -> Self {
+    /// Crée un scanner selon les paramètres de la configuration
+    pub fn from_config(config: &Config) -> Self {
+        let storage_paths = if config.storage_paths.is_empty() {
+            DEFAULT_STORAGE_PATHS
+                .iter()
+                .map(|p| p.to_string())
+                .collect()
+        } else {
+            config.storage_paths.clone()
+        };
+
+        let cookies_file = config.cookies_file.as_deref().and_then(|path| {
+            validate_cookies_file(path)
+                .map_err(|error| tracing::warn!("Cookies ignorés: {}", error))
+                .ok()
+        });
+
+        Self::with_paths(storage_paths)
+            .cookies_file(cookies_file)
+            .scan_limit(config.scan_limit)
+            .scan_depth(config.scan_depth)
+            .video_extensions(config.scanned_extensions())
+            .duration_tolerance_secs(config.duration_tolerance_secs)
+            .min_free_space_mb(config.min_free_space_mb)
+            .min_scan_interval_secs(config.min_scan_interval_secs)
+    }
+
+    /// Crée un scanner sur des chemins de stockage donnés.
+    /// Les chemins ne sont vérifiés qu'à l'utilisation, un disque absent n'est pas une erreur.
+    pub fn with_paths(paths: Vec<String>) -> Self {
         Self {
-            storage_paths: vec![
-                "/run/mount/ve_stock_1".to_string(),
-                "/run/mount/ve_stock_2".to_string(),
-                "/run/mount/ve_ext_1".to_string(),
-            ],
-            cache: Cache::new(
-                std::path::PathBuf::from("/tmp/ndownload_videos_cache.json"),
-                Duration::from_secs(300),
-            ),
-            file_durations_cache: Arc::new(Mutex::new(HashMap::new())),
+            storage_paths: paths.into_iter().map(|p| expand_home(&p)).collect(),
+            scan_limit: 30,
+            scan_depth: 3,
+            video_extensions: DEFAULT_VIDEO_EXTENSIONS
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+            duration_tolerance: 5.0,
+            cookies_file: None,
+            min_free_bytes: 0,
+            database: None,
+            cache: Cache::new(paths::videos_cache_file(), Duration::from_secs(300)),
+            file_probes_cache: Arc::new(Mutex::new(HashMap::new())),
+            yt_dlp_version: Mutex::new(None),
+            gate: YtDlpGate::new(Duration::from_secs(2)),
+            cooldowns: Mutex::new(Cooldowns::default()),
         }
     }
 
+    /// Définit le nombre maximum de vidéos récupérées par scan
+    pub fn scan_limit(mut self, limit: usize) -> Self {
+        self.scan_limit = limit.max(1);
+        self
+    }
+
+    /// Définit la profondeur de sous-dossiers parcourue pour retrouver les vidéos téléchargées
+    pub fn scan_depth(mut self, depth: usize) -> Self {
+        self.scan_depth = depth;
+        self
+    }
+
+    /// Définit les extensions des fichiers considérés comme des vidéos téléchargées
+    pub fn video_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.video_extensions = extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.').to_lowercase())
+            .collect();
+        self
+    }
+
+    /// Intervalle minimal entre deux appels à yt-dlp pour lister ou décrire des vidéos
+    pub fn min_scan_interval_secs(mut self, seconds: usize) -> Self {
+        self.gate = YtDlpGate::new(Duration::from_secs(seconds as u64));
+        self
+    }
+
+    /// Écart de durée toléré entre un fichier local et une vidéo scannée (au moins 1 seconde)
+    pub fn duration_tolerance_secs(mut self, seconds: usize) -> Self {
+        self.duration_tolerance = seconds.max(1) as f64;
+        self
+    }
+
+    /// Définit le fichier de cookies passé à yt-dlp (contenus soumis à l'âge ou réservés aux abonnés)
+    pub fn cookies_file(mut self, path: Option<PathBuf>) -> Self {
+        self.cookies_file = path;
+        self
+    }
+
+    /// Espace libre minimal exigé sur le disque d'un nouveau téléchargement (0 = aucun)
+    pub fn min_free_space_mb(mut self, megabytes: usize) -> Self {
+        self.min_free_bytes = megabytes as u64 * 1024 * 1024;
+        self
+    }
+
+    /// Consulte l'historique des téléchargements avant de parcourir les disques
+    pub fn database(mut self, database: Option<Arc<Database>>) -> Self {
+        self.database = database;
+        self
+    }
+
+    /// Nombre de vidéos par page de scan
+    pub fn page_size(&self) -> usize {
+        self.scan_limit
+    }
+
     /// Scanne les vidéos disponibles d'une chaîne avec yt-dlp
     pub async fn scan_channel_videos(&self, channel_url: &str) -> Result<Vec<VideoMetadata>> {
-        tracing::info!("Scan des vidéos de: {}", channel_url);
+        let scan = self.scan(channel_url, 0, false, &mut |_, _| {}).await?;
+        Ok(scan.videos)
+    }
 
-        // Pour Twitch, s'assurer qu'on utilise l'URL /videos pour les VODs
-        let url = if channel_url.contains("twitch.tv") && !channel_url.contains("/videos") {
-            format!("{}/videos", channel_url.trim_end_matches('/'))
-        } else {
-            channel_url.to_string()
-        };
+    /// Comme `scan_channel_videos`, en appelant `on_video` pour chaque vidéo dès que yt-dlp
+    /// l'a décrite, sans attendre la fin du scan. `on_video` reçoit aussi le nombre de vidéos
+    /// attendues quand yt-dlp connaît la taille de la liste. `bypass_cache` ignore le cache
+    /// en lecture (il est tout de même mis à jour). Les avertissements de yt-dlp sont rendus
+    /// avec les vidéos.
+    pub async fn scan_channel_videos_streaming<F>(
+        &self,
+        channel_url: &str,
+        bypass_cache: bool,
+        mut on_video: F,
+    ) -> Result<ChannelScan>
+    where
+        F: FnMut(&VideoMetadata, Option<usize>),
+    {
+        self.scan(channel_url, 0, bypass_cache, &mut on_video).await
+    }
+
+    /// Scanne une page de vidéos d'une chaîne (la page 0 contient les plus récentes)
+    pub async fn scan_channel_page(
+        &self,
+        channel_url: &str,
+        page: usize,
+    ) -> Result<Vec<VideoMetadata>> {
+        let scan = self.scan(channel_url, page, false, &mut |_, _| {}).await?;
+        Ok(scan.videos)
+    }
+
+    /// `url` est déjà l'URL de liste propre à la plateforme (voir `Platform::scan_url`).
+    /// Les vidéos lues depuis le cache sont aussi transmises à `on_video`; seuls les
+    /// scans réels ont des avertissements.
+    async fn scan<F>(
+        &self,
+        url: &str,
+        page: usize,
+        bypass_cache: bool,
+        on_video: &mut F,
+    ) -> Result<ChannelScan>
+    where
+        F: FnMut(&VideoMetadata, Option<usize>),
+    {
+        tracing::info!("Scan des vidéos de: {} (page {})", url, page);
 
-        tracing::info!("URL utilisée: {}", url);
+        let playlist_start = page * self.scan_limit + 1;
+        let playlist_end = (page + 1) * self.scan_limit;
 
-        // Vérifier le cache
-        if let Some(videos) = self.cache.get(&url) {
+        // La plage fait partie de la clé pour ne pas servir une liste tronquée
+        let channel_key = channel_cache_key(url);
+        let cache_key = format!("{channel_key}#{playlist_start}-{playlist_end}");
+
+        // Vérifier le cache; un rescan forcé retire l'ancienne liste même si yt-dlp échoue
+        if bypass_cache {
+            self.cache.invalidate(&cache_key);
+        } else if let Some(videos) = self.cache.get(&cache_key) {
             tracing::info!("Utilisation du cache pour: {}", url);
-            return Ok(videos);
+            for video in &videos {
+                on_video(video, Some(videos.len()));
+            }
+            return Ok(ChannelScan {
+                videos,
+                warnings: Vec::new(),
+            });
         }
 
-        let output = smol::process::Command::new("yt-dlp")
+        if let Some(retry_in) = self
+            .cooldowns
+            .lock()
+            .remaining(&channel_key, Instant::now())
+        {
+            return Err(RateLimited { retry_in }.into());
+        }
+        // Un seul scan à la fois: le tour est gardé jusqu'à la fin de yt-dlp
+        let _turn = self.gate.acquire().await;
+
+        let mut command = smol::process::Command::new("yt-dlp");
+        if let Some(cookies) = self.cookies_file.as_ref().filter(|path| path.is_file()) {
+            command.arg("--cookies").arg(cookies);
+        }
+
+        let mut child = command
             .arg("--skip-download")
             .arg("--no-write-info-json")
             .arg("--no-write-playlist-metafiles")
             .arg("--dump-json")
+            .arg("--playlist-start")
+            .arg(playlist_start.to_string())
             .arg("--playlist-end")
-            .arg("30") // Limiter à 30 vidéos pour garder de la vitesse
-            .arg(&url)
+            .arg(playlist_end.to_string())
+            .arg(url)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
-            .await?;
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|error| spawn_error("yt-dlp", error))?;
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("yt-dlp a échoué: {error}");
-        }
+        // Chaque ligne est un JSON, transmis dès qu'il est lu
+        let stdout = child.stdout.take();
+        let read_videos = async {
+            let mut scanned = ScannedVideos::default();
+            let mut expected = None;
+            if let Some(stdout) = stdout {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Some(Ok(line)) = lines.next().await {
+                    let first = scanned.videos.is_empty();
+                    if let Some(video) = scanned.push_line(&line) {
+                        if first {
+                            let count = serde_json::from_str::<PlaylistPosition>(&line)
+                                .ok()
+                                .and_then(|position| position.playlist_count);
+                            expected = expected_videos(count, playlist_start, self.scan_limit);
+                        }
+                        on_video(video, expected);
+                    }
+                }
+            }
+            scanned.videos
+        };
 
-        let stdout = String::from_utf8(output.stdout)?;
-        let mut videos = Vec::new();
+        let stderr = child.stderr.take();
+        let read_errors = async {
+            let mut output = String::new();
+            if let Some(mut stderr) = stderr {
+                stderr.read_to_string(&mut output).await.ok();
+            }
+            output
+        };
 
-        // Chaque ligne est un JSON
-        for line in stdout.lines() {
-            if line.trim().is_empty() {
-                continue;
+        let (videos, stderr_output) = futures::join!(read_videos, read_errors);
+
+        if !child.status().await?.success() {
+            if is_rate_limited(&stderr_output) {
+                let retry_in = self.cooldowns.lock().start(&channel_key, Instant::now());
+                tracing::warn!(
+                    "Rate limited while scanning {}, pausing for {}s",
+                    url,
+                    retry_in.as_secs()
+                );
+                return Err(RateLimited { retry_in }.into());
             }
+            anyhow::bail!("yt-dlp a échoué: {stderr_output}");
+        }
+        self.cooldowns.lock().clear(&channel_key);
+
+        tracing::info!("Trouvé {} vidéos", videos.len());
+        let warnings = parse_warnings(&stderr_output);
+        for warning in &warnings {
+            tracing::warn!("yt-dlp: {}", warning);
+        }
+
+        // Mettre à jour le cache
+        self.cache.set(cache_key, videos.clone());
+
+        Ok(ChannelScan { videos, warnings })
+    }
+
+    /// Temps restant avant de pouvoir rescanner une chaîne refusée pour trop de requêtes
+    pub fn cooldown_remaining(&self, channel_url: &str) -> Option<Duration> {
+        self.cooldowns
+            .lock()
+            .remaining(&channel_cache_key(channel_url), Instant::now())
+    }
 
-            match serde_json::from_str::<VideoMetadata>(line) {
-                Ok(video) => {
-                    tracing::debug!("Vidéo: {} - durée: {:?}", video.title, video.duration);
-                    videos.push(video);
+    /// Vérifie si une vidéo est déjà téléchargée.
+    /// L'historique des téléchargements est consulté d'abord; pour les fichiers qu'il
+    /// ne connaît pas, l'identifiant enregistré à côté du fichier (`.id`) est comparé,
+    /// puis l'URL source intégrée aux métadonnées du fichier; la durée ne sert
+    /// que pour les fichiers qui n'ont ni l'un ni l'autre.
+    pub async fn is_video_downloaded(
+        &self,
+        channel_name: &str,
+        video_id: &str,
+        duration: Option<f64>,
+    ) -> Result<Option<String>> {
+        let files = self.channel_snapshot(channel_name);
+        self.find_download(&files, video_id, duration).await
+    }
+
+    /// Relève une fois les fichiers d'une chaîne, leurs identifiants et les disques
+    /// démontés, pour rechercher ensuite tout un lot de vidéos
+    fn channel_snapshot(&self, channel_name: &str) -> ChannelFiles {
+        let mut identified = HashMap::new();
+        let mut unidentified = Vec::new();
+        for path in self.channel_files(channel_name) {
+            match read_video_id(&path) {
+                Some(id) => {
+                    identified.insert(id, path);
                 }
-                Err(e) => {
-                    tracing::warn!("Erreur parsing JSON: {} - ligne: {}", e, line);
+                None => unidentified.push(path),
+            }
+        }
+        ChannelFiles {
+            identified,
+            unidentified,
+            unavailable_storage: self.unavailable_storage_paths(),
+        }
+    }
+
+    /// Recherche une vidéo parmi les fichiers relevés d'une chaîne
+    async fn find_download(
+        &self,
+        files: &ChannelFiles,
+        video_id: &str,
+        duration: Option<f64>,
+    ) -> Result<Option<String>> {
+        if let Some(path) = self.recorded_download(video_id, &files.unavailable_storage) {
+            return Ok(Some(path));
+        }
+
+        // Fichiers identifiés: seul l'identifiant compte
+        if let Some(path) = files.identified.get(video_id) {
+            tracing::info!("Match par identifiant: {}", path.display());
+            return Ok(Some(path.to_string_lossy().to_string()));
+        }
+
+        tracing::debug!("Recherche vidéo {} (durée {:?})", video_id, duration);
+
+        // Lancer plusieurs ffprobe en parallèle, arrêter au premier match
+        let mut probes = futures::stream::iter(files.unidentified.iter().cloned())
+            .map(|path| async move {
+                let probe = self.probe_file(&path).await;
+                (path, probe)
+            })
+            .buffer_unordered(MAX_CONCURRENT_FFPROBE);
+
+        while let Some((path, probe)) = probes.next().await {
+            let Some(probe) = probe? else {
+                continue;
+            };
+
+            // Fichier identifié par ses métadonnées: seul l'identifiant compte
+            if let Some(source_url) = &probe.source_url {
+                if source_matches(source_url, video_id) {
+                    tracing::info!("Match par métadonnées: {}", path.display());
+                    return Ok(Some(path.to_string_lossy().to_string()));
                 }
+                continue;
+            }
+
+            let (Some(local_duration), Some(target_duration)) = (probe.duration, duration) else {
+                continue;
+            };
+
+            tracing::debug!("Fichier: {} - durée: {}", path.display(), local_duration);
+            if (local_duration - target_duration).abs() < self.duration_tolerance {
+                tracing::info!(
+                    "Match trouvé: {} (durée: {})",
+                    path.display(),
+                    local_duration
+                );
+                return Ok(Some(path.to_string_lossy().to_string()));
             }
         }
 
-        tracing::info!("Trouvé {} vidéos", videos.len());
+        Ok(None)
+    }
 
-        // Mettre à jour le cache
-        self.cache.set(url.clone(), videos.clone());
+    /// Chemin d'une vidéo connue de l'historique, si son fichier est toujours là
+    /// ou si son disque est momentanément démonté (un fichier déplacé ou supprimé
+    /// renvoie au parcours des disques)
+    fn recorded_download(&self, video_id: &str, unavailable_storage: &[String]) -> Option<String> {
+        let video = match self.database.as_ref()?.find(video_id) {
+            Ok(video) => video?,
+            Err(error) => {
+                tracing::warn!("Download history lookup failed: {}", error);
+                return None;
+            }
+        };
+        if video.path.exists() {
+            return Some(video.path.to_string_lossy().to_string());
+        }
+        let unmounted = unavailable_storage
+            .iter()
+            .any(|storage| video.path.starts_with(storage));
+        if unmounted {
+            tracing::debug!(
+                "Disque indisponible, vidéo gardée comme téléchargée: {}",
+                video.path.display()
+            );
+        }
+        unmounted.then(|| video.path.to_string_lossy().to_string())
+    }
 
-        Ok(videos)
+    /// Filtre les vidéos déjà téléchargées d'une chaîne, retourne leurs URLs
+    pub async fn downloaded_urls(
+        &self,
+        channel_name: &str,
+        videos: &[VideoMetadata],
+    ) -> Result<HashSet<String>> {
+        let videos: Vec<_> = videos
+            .iter()
+            .map(|video| (video.url.clone(), video.id.clone(), video.duration))
+            .collect();
+        self.find_downloaded(channel_name, &videos).await
     }
 
-    /// Vérifie si une vidéo est déjà téléchargée en comparant la durée
-    pub fn is_video_downloaded(&self, channel_name: &str, duration: Option<f64>) -> Option<String> {
-        let Some(target_duration) = duration else {
-            tracing::debug!("Pas de durée cible, impossible de vérifier");
-            return None;
+    /// Vidéos (URL, identifiant, durée) déjà téléchargées parmi celles données, retourne
+    /// leurs URLs. Les disques ne sont parcourus qu'une fois pour tout le lot, mais ce
+    /// parcours bloque: à lancer hors du thread de l'interface.
+    pub async fn find_downloaded(
+        &self,
+        channel_name: &str,
+        videos: &[(String, String, Option<f64>)],
+    ) -> Result<HashSet<String>> {
+        let files = self.channel_snapshot(channel_name);
+        let mut downloaded = HashSet::new();
+        for (url, id, duration) in videos {
+            if self.find_download(&files, id, *duration).await?.is_some() {
+                downloaded.insert(url.clone());
+            }
+        }
+        Ok(downloaded)
+    }
+
+    /// Seuls les fichiers aux extensions vidéo configurées sont comparés,
+    /// ce qui écarte sous-titres, miniatures et fichiers temporaires de yt-dlp (.part, .ytdl)
+    fn is_video_file(&self, path: &Path) -> bool {
+        let Some(extension) = path.extension() else {
+            return false;
         };
+        let extension = extension.to_string_lossy().to_lowercase();
+        self.video_extensions.contains(&extension)
+    }
 
-        tracing::debug!(
-            "Recherche vidéo avec durée {} pour {}",
-            target_duration,
-            channel_name
-        );
+    /// Durée et URL source d'un fichier local, lues dans le cache ou avec ffprobe.
+    /// Échoue seulement si ffprobe n'a pas pu être lancé.
+    async fn probe_file(&self, path: &Path) -> Result<Option<FileProbe>> {
+        let path_str = path.to_string_lossy().to_string();
 
-        for storage_path in &self.storage_paths {
-            let channel_path = format!("{storage_path}/{channel_name}");
+        // Vérifier le cache d'abord
+        if let Some(probe) = self.file_probes_cache.lock().get(&path_str).cloned() {
+            return Ok(Some(probe));
+        }
 
+        // Si pas en cache, lire avec ffprobe
+        let Some(probe) = Self::probe_video(path).await? else {
+            tracing::warn!("Impossible de lire les métadonnées de: {}", path.display());
+            return Ok(None);
+        };
+
+        // Mettre en cache
+        self.file_probes_cache
+            .lock()
+            .insert(path_str, probe.clone());
+        Ok(Some(probe))
+    }
+
+    /// Oublie les durées et URLs sources lues dans les fichiers d'une chaîne, pour que
+    /// les fichiers remplacés ou déplacés depuis soient relus par ffprobe
+    pub fn forget_probes(&self, channel_name: &str) {
+        let directories = self.channel_directories(channel_name);
+        self.file_probes_cache.lock().retain(|path, _| {
+            !directories
+                .iter()
+                .any(|directory| Path::new(path).starts_with(directory))
+        });
+    }
+
+    /// Dossiers possibles d'une chaîne sur tous les disques de stockage
+    fn channel_directories(&self, channel_name: &str) -> Vec<PathBuf> {
+        // Le dossier porte le nom nettoyé de la chaîne; les dossiers créés avant ce
+        // nettoyage, avec le nom brut, restent reconnus
+        let mut directories = vec![filename::directory_name(channel_name)];
+        if directories[0] != channel_name && !channel_name.is_empty() {
+            directories.push(channel_name.to_string());
+        }
+
+        self.storage_paths
+            .iter()
+            .flat_map(|storage_path| {
+                directories
+                    .iter()
+                    .map(move |directory| PathBuf::from(format!("{storage_path}/{directory}")))
+            })
+            .collect()
+    }
+
+    /// Liste les fichiers du dossier d'une chaîne sur tous les disques de stockage,
+    /// sous-dossiers compris jusqu'à `scan_depth` niveaux
+    fn channel_files(&self, channel_name: &str) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+
+        for channel_path in self.channel_directories(channel_name) {
             // Vérifier si le dossier existe
-            if let Ok(entries) = std::fs::read_dir(&channel_path) {
-                tracing::debug!("Scan du dossier: {}", channel_path);
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if !path.is_file() {
-                        continue;
-                    }
+            if !channel_path.is_dir() {
+                tracing::debug!("Dossier n'existe pas: {}", channel_path.display());
+                continue;
+            }
+
+            let mut stack = vec![(channel_path, 0)];
+            while let Some((dir, depth)) = stack.pop() {
+                let Ok(entries) = std::fs::read_dir(&dir) else {
+                    tracing::warn!("Impossible de lire le dossier: {}", dir.display());
+                    continue;
+                };
 
-                    let path_str = path.to_string_lossy().to_string();
-
-                    // Vérifier le cache d'abord
-                    let local_duration = {
-                        let cache = self.file_durations_cache.lock();
-                        cache.get(&path_str).copied()
-                    };
-
-                    // Si pas en cache, lire avec ffprobe
-                    let local_duration = if let Some(dur) = local_duration {
-                        dur
-                    } else if let Some(dur) = Self::get_video_duration(&path) {
-                        // Mettre en cache
-                        self.file_durations_cache
-                            .lock()
-                            .insert(path_str.clone(), dur);
-                        dur
-                    } else {
-                        tracing::warn!("Impossible de lire la durée de: {}", path.display());
-                        continue;
-                    };
-
-                    tracing::debug!("Fichier: {} - durée: {}", path.display(), local_duration);
-                    // Tolérance de 5 secondes
-                    if (local_duration - target_duration).abs() < 5.0 {
-                        tracing::info!(
-                            "Match trouvé: {} (durée: {})",
-                            path.display(),
-                            local_duration
-                        );
-                        return Some(path_str);
+                tracing::debug!("Scan du dossier: {}", dir.display());
+                for path in entries.flatten().map(|entry| entry.path()) {
+                    if path.is_dir() {
+                        if depth < self.scan_depth {
+                            stack.push((path, depth + 1));
+                        }
+                    } else if self.is_video_file(&path) {
+                        files.push(path);
                     }
                 }
-            } else {
-                tracing::debug!("Dossier n'existe pas: {}", channel_path);
             }
         }
 
-        None
+        files
+    }
+
+    /// Récupère l'URL de l'avatar d'une chaîne, sans lister ses vidéos
+    pub async fn channel_avatar_url(&self, channel_url: &str) -> Result<Option<String>> {
+        let channel = self.channel_metadata(channel_url).await?;
+        Ok(pick_avatar(&channel.thumbnails))
+    }
+
+    /// Retrouve la chaîne à laquelle appartient une vidéo
+    pub async fn video_channel_url(&self, video_url: &str) -> Result<Option<String>> {
+        let owner: VideoOwner = serde_json::from_slice(&self.dump_video(video_url).await?)?;
+        Ok(owner_channel_url(&owner))
+    }
+
+    /// Récupère les informations d'une vidéo seule, sans sa chaîne
+    pub async fn video_metadata(&self, video_url: &str) -> Result<VideoMetadata> {
+        let details: VideoDetails = serde_json::from_slice(&self.dump_video(video_url).await?)?;
+        Ok(VideoMetadata {
+            id: details.id,
+            title: details.title,
+            url: video_url.to_string(),
+            duration: details.duration,
+            upload_date: details.upload_date,
+            uploader: details.uploader,
+            thumbnail: details.thumbnail,
+            channel_id: details.channel_id,
+        })
+    }
+
+    /// JSON décrivant une vidéo (`yt-dlp --dump-json`)
+    async fn dump_video(&self, video_url: &str) -> Result<Vec<u8>> {
+        let _turn = self.gate.acquire().await;
+        let mut command = smol::process::Command::new("yt-dlp");
+        if let Some(cookies) = self.cookies_file.as_ref().filter(|path| path.is_file()) {
+            command.arg("--cookies").arg(cookies);
+        }
+
+        let output = command
+            .arg("--skip-download")
+            .arg("--no-playlist")
+            .arg("--dump-json")
+            .arg(video_url)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|error| spawn_error("yt-dlp", error))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("yt-dlp a échoué: {error}");
+        }
+        Ok(output.stdout)
+    }
+
+    /// Récupère le titre d'une playlist
+    pub async fn playlist_title(&self, playlist_url: &str) -> Result<Option<String>> {
+        let playlist = self.channel_metadata(playlist_url).await?;
+        Ok(playlist.title.filter(|title| !title.trim().is_empty()))
+    }
+
+    async fn channel_metadata(&self, channel_url: &str) -> Result<ChannelMetadata> {
+        let _turn = self.gate.acquire().await;
+        let mut command = smol::process::Command::new("yt-dlp");
+        if let Some(cookies) = self.cookies_file.as_ref().filter(|path| path.is_file()) {
+            command.arg("--cookies").arg(cookies);
+        }
+
+        let output = command
+            .arg("--flat-playlist")
+            .arg("--playlist-items")
+            .arg("0")
+            .arg("--dump-single-json")
+            .arg(channel_url)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|error| spawn_error("yt-dlp", error))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("yt-dlp a échoué: {error}");
+        }
+
+        Ok(serde_json::from_slice(&output.stdout)?)
     }
 
-    /// Obtient la durée d'une vidéo locale avec ffprobe
-    fn get_video_duration(path: &std::path::Path) -> Option<f64> {
-        let output = std::process::Command::new("ffprobe")
+    /// Retourne la version de yt-dlp installée (sa date de publication).
+    /// Le résultat est mémorisé, `yt-dlp --version` n'est lancé qu'une fois.
+    pub async fn check_yt_dlp_version(&self) -> Result<NaiveDate> {
+        if let Some(version) = *self.yt_dlp_version.lock() {
+            return Ok(version);
+        }
+
+        let output = smol::process::Command::new("yt-dlp")
+            .arg("--version")
+            .output()
+            .await
+            .map_err(|error| spawn_error("yt-dlp", error))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let version = parse_yt_dlp_version(&stdout)
+            .ok_or_else(|| anyhow::anyhow!("Version de yt-dlp illisible: {}", stdout.trim()))?;
+
+        *self.yt_dlp_version.lock() = Some(version);
+        Ok(version)
+    }
+
+    /// Obtient la durée d'une vidéo locale et son URL source avec ffprobe
+    async fn probe_video(path: &Path) -> Result<Option<FileProbe>> {
+        let output = smol::process::Command::new("ffprobe")
             .arg("-v")
             .arg("error")
             .arg("-show_entries")
-            .arg("format=duration")
+            .arg("format=duration:format_tags=purl,comment")
             .arg("-of")
-            .arg("default=noprint_wrappers=1:nokey=1")
+            .arg("json")
             .arg(path)
+            .kill_on_drop(true)
             .output()
-            .ok()?;
+            .await
+            .map_err(|error| spawn_error("ffprobe", error))?;
 
         if !output.status.success() {
-            return None;
+            return Ok(None);
         }
 
-        let duration_str = String::from_utf8(output.stdout).ok()?;
-        duration_str.trim().parse::<f64>().ok()
+        Ok(parse_probe_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Disque où placer un nouveau téléchargement: le meilleur disque, à condition qu'il
+    /// garde l'espace libre minimal configuré (les autres disques en ont encore moins)
+    pub fn storage_for_download(&self) -> Result<StorageInfo> {
+        let storage = self.find_best_storage_path()?;
+        check_free_space(&storage, self.min_free_bytes)?;
+        Ok(storage)
     }
 
     /// Trouve le meilleur disque de stockage (celui avec le plus d'espace)
-    pub fn find_best_storage_path(&self) -> Result<String> {
-        // Pour l'instant, retourner le premier disponible
-        for path in &self.storage_paths {
-            if std::path::Path::new(path).exists() {
-                return Ok(path.clone());
+    pub fn find_best_storage_path(&self) -> Result<StorageInfo> {
+        self.storage_infos()
+            .into_iter()
+            .max_by_key(|info| info.available_bytes)
+            .ok_or_else(|| anyhow::anyhow!("Aucun disque de stockage disponible"))
+    }
+
+    /// Chemins de stockage configurés mais absents (disque démonté ou débranché)
+    pub fn unavailable_storage_paths(&self) -> Vec<String> {
+        self.storage_paths
+            .iter()
+            .filter(|path| !is_storage_available(Path::new(path)))
+            .cloned()
+            .collect()
+    }
+
+    /// Liste l'espace disque de chaque chemin de stockage existant
+    pub fn storage_infos(&self) -> Vec<StorageInfo> {
+        self.storage_paths
+            .iter()
+            .filter(|path| is_storage_available(Path::new(path)))
+            .filter_map(|path| {
+                let Some((available_bytes, total_bytes)) = Self::disk_space(path) else {
+                    tracing::warn!("Impossible de lire l'espace disque de: {}", path);
+                    return None;
+                };
+                Some(StorageInfo {
+                    path: path.clone(),
+                    available_bytes,
+                    total_bytes,
+                })
+            })
+            .collect()
+    }
+
+    /// Retourne (espace disponible, espace total) en octets via statvfs
+    #[allow(clippy::unnecessary_cast)]
+    fn disk_space(path: &str) -> Option<(u64, u64)> {
+        let c_path = std::ffi::CString::new(path).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+
+        let block_size = stat.f_frsize as u64;
+        Some((
+            stat.f_bavail as u64 * block_size,
+            stat.f_blocks as u64 * block_size,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downloaded_video_found_in_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = Database::open(&dir.path().join("downloads.db")).unwrap();
+        // Fichier rangé hors du dossier de la chaîne: seul l'historique le retrouve
+        let moved = dir.path().join("elsewhere.mp4");
+        std::fs::write(&moved, b"").unwrap();
+        for (id, path) in [
+            ("abc123", moved.clone()),
+            ("gone", dir.path().join("gone.mp4")),
+        ] {
+            database
+                .record(&crate::database::Video {
+                    id: id.to_string(),
+                    channel: "chan".to_string(),
+                    title: id.to_string(),
+                    path,
+                    duration: None,
+                    downloaded_at: 0,
+                })
+                .unwrap();
+        }
+
+        let scanner = VideoScanner::with_paths(vec![dir.path().to_string_lossy().to_string()])
+            .database(Some(Arc::new(database)));
+
+        assert_eq!(
+            smol::block_on(scanner.is_video_downloaded("chan", "abc123", None)).unwrap(),
+            Some(moved.to_string_lossy().to_string())
+        );
+        // Fichier supprimé depuis: retour au parcours des disques, qui ne trouve rien
+        assert_eq!(
+            smol::block_on(scanner.is_video_downloaded("chan", "gone", None)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_fstab_targets() {
+        let fstab = "# /etc/fstab\n\
+            UUID=1 / ext4 defaults 0 1\n\
+            UUID=2 /mnt/videos ext4 defaults,nofail 0 2\n\
+            UUID=3 none swap sw 0 0\n\
+            //nas/share /mnt/nas\\040films cifs noauto 0 0\n";
+        assert_eq!(
+            parse_fstab_targets(fstab),
+            [
+                PathBuf::from("/mnt/videos"),
+                PathBuf::from("/mnt/nas films")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_required_mount_points() {
+        let fstab = [PathBuf::from("/mnt"), PathBuf::from("/mnt/videos")];
+        assert_eq!(
+            required_mount_points(Path::new("/mnt/videos/chaînes"), &fstab),
+            [PathBuf::from("/mnt/videos")]
+        );
+        assert_eq!(
+            required_mount_points(Path::new("/run/media/alex/USB/videos"), &fstab),
+            [
+                PathBuf::from("/run/media/alex"),
+                PathBuf::from("/run/media/alex/USB")
+            ]
+        );
+        assert_eq!(
+            required_mount_points(Path::new("/media/USB"), &fstab),
+            [PathBuf::from("/media/USB")]
+        );
+        assert!(required_mount_points(Path::new("/home/alex/Vidéos"), &fstab).is_empty());
+
+        // Simple dossier: sur le même périphérique que son parent
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_mount_point(dir.path()));
+        assert!(is_storage_available(dir.path()));
+        assert!(!is_storage_available(&dir.path().join("absent")));
+    }
+
+    #[test]
+    fn test_recorded_video_kept_while_storage_unmounted() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = Database::open(&dir.path().join("downloads.db")).unwrap();
+        let mounted = dir.path().join("disk1");
+        let unmounted = dir.path().join("disk2");
+        std::fs::create_dir(&mounted).unwrap();
+        for (id, path) in [
+            ("away", unmounted.join("chan/away.mp4")),
+            ("deleted", mounted.join("chan/deleted.mp4")),
+        ] {
+            database
+                .record(&crate::database::Video {
+                    id: id.to_string(),
+                    channel: "chan".to_string(),
+                    title: id.to_string(),
+                    path,
+                    duration: None,
+                    downloaded_at: 0,
+                })
+                .unwrap();
+        }
+
+        let storage_paths = [&mounted, &unmounted]
+            .map(|path| path.to_string_lossy().to_string())
+            .to_vec();
+        let scanner =
+            VideoScanner::with_paths(storage_paths.clone()).database(Some(Arc::new(database)));
+        assert_eq!(
+            scanner.unavailable_storage_paths(),
+            [storage_paths[1].clone()]
+        );
+        assert!(scanner
+            .storage_infos()
+            .iter()
+            .all(|storage| storage.path == storage_paths[0]));
+
+        // Disque absent: la fiche de l'historique fait foi
+        assert_eq!(
+            smol::block_on(scanner.is_video_downloaded("chan", "away", None)).unwrap(),
+            Some(
+                unmounted
+                    .join("chan/away.mp4")
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+        // Disque présent mais fichier supprimé: la vidéo n'est plus téléchargée
+        assert_eq!(
+            smol::block_on(scanner.is_video_downloaded("chan", "deleted", None)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_downloaded_video_matched_by_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let channel_dir = dir.path().join("chan");
+        std::fs::create_dir(&channel_dir).unwrap();
+        let video = channel_dir.join("video.mp4");
+        std::fs::write(&video, b"").unwrap();
+        std::fs::write(id_sidecar_path(&video), "abc123\n").unwrap();
+
+        let scanner = VideoScanner::with_paths(vec![dir.path().to_string_lossy().to_string()]);
+
+        assert_eq!(
+            smol::block_on(scanner.is_video_downloaded("chan", "abc123", None)).unwrap(),
+            Some(video.to_string_lossy().to_string())
+        );
+        assert_eq!(
+            smol::block_on(scanner.is_video_downloaded("chan", "other", None)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_downloaded_in_one_pass() {
+        let dir = tempfile::tempdir().unwrap();
+        let channel_dir = dir.path().join("chan/2024");
+        std::fs::create_dir_all(&channel_dir).unwrap();
+        for id in ["a", "b"] {
+            let video = channel_dir.join(format!("{id}.mp4"));
+            std::fs::write(&video, b"").unwrap();
+            std::fs::write(id_sidecar_path(&video), id).unwrap();
+        }
+
+        let scanner = VideoScanner::with_paths(vec![dir.path().to_string_lossy().to_string()]);
+        let videos = [("a", None), ("b", Some(60.0)), ("c", None)].map(|(id, duration)| {
+            (
+                format!("https://example.com/{id}"),
+                id.to_string(),
+                duration,
+            )
+        });
+        assert_eq!(
+            smol::block_on(scanner.find_downloaded("chan", &videos)).unwrap(),
+            HashSet::from(["https://example.com/a", "https://example.com/b"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn test_forget_probes_of_one_channel() {
+        let scanner = VideoScanner::with_paths(vec!["/disk1".to_string(), "/disk2".to_string()]);
+        for path in [
+            "/disk1/chan/a.mp4",
+            "/disk2/chan/2024/b.mp4",
+            "/disk1/chan 2/c.mp4",
+            "/disk1/other/d.mp4",
+        ] {
+            scanner.file_probes_cache.lock().insert(
+                path.to_string(),
+                FileProbe {
+                    duration: Some(60.0),
+                    source_url: None,
+                },
+            );
+        }
+
+        scanner.forget_probes("chan");
+        let mut remaining: Vec<_> = scanner.file_probes_cache.lock().keys().cloned().collect();
+        remaining.sort();
+        assert_eq!(remaining, ["/disk1/chan 2/c.mp4", "/disk1/other/d.mp4"]);
+    }
+
+    #[test]
+    fn test_parse_probe_output() {
+        let output = r#"{
+            "format": {
+                "duration": "634.120000",
+                "tags": {
+                    "PURL": "https://www.youtube.com/watch?v=abc123",
+                    "COMMENT": "https://www.youtube.com/watch?v=abc123"
+                }
             }
+        }"#;
+        let probe = parse_probe_output(output).unwrap();
+        assert_eq!(probe.duration, Some(634.12));
+        assert_eq!(
+            probe.source_url.as_deref(),
+            Some("https://www.youtube.com/watch?v=abc123")
+        );
+
+        // Fichier téléchargé sans --embed-metadata: pas de source, la durée reste utilisable
+        let probe = parse_probe_output(r#"{"format": {"duration": "12.5"}}"#).unwrap();
+        assert_eq!(probe.source_url, None);
+        assert_eq!(probe.duration, Some(12.5));
+
+        // Un commentaire qui n'est pas une URL n'identifie pas la vidéo
+        let output = r#"{"format": {"tags": {"comment": "Ma vidéo de vacances"}}}"#;
+        assert_eq!(parse_probe_output(output).unwrap().source_url, None);
+    }
+
+    #[test]
+    fn test_source_matches() {
+        assert!(source_matches(
+            "https://www.youtube.com/watch?v=abc123",
+            "abc123"
+        ));
+        assert!(source_matches(
+            "https://www.twitch.tv/videos/987654",
+            "987654"
+        ));
+        assert!(!source_matches(
+            "https://www.youtube.com/watch?v=abc1234",
+            "abc123"
+        ));
+    }
+
+    #[test]
+    fn test_channel_files_recursive_with_depth_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("chan/2024/series");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(dir.path().join("chan/top.mp4"), b"").unwrap();
+        std::fs::write(dir.path().join("chan/2024/year.mp4"), b"").unwrap();
+        std::fs::write(nested.join("deep.mp4"), b"").unwrap();
+
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+        let names = |scanner: &VideoScanner| {
+            let mut names: Vec<_> = scanner
+                .channel_files("chan")
+                .iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let scanner = VideoScanner::with_paths(paths.clone());
+        assert_eq!(names(&scanner), ["deep.mp4", "top.mp4", "year.mp4"]);
+
+        let shallow = VideoScanner::with_paths(paths).scan_depth(1);
+        assert_eq!(names(&shallow), ["top.mp4", "year.mp4"]);
+    }
+
+    #[test]
+    fn test_channel_files_only_keeps_video_extensions() {
+        let dir = tempfile::tempdir().unwrap();
+        let channel_dir = dir.path().join("chan");
+        std::fs::create_dir(&channel_dir).unwrap();
+        for name in [
+            "a.mp4",
+            "b.MKV",
+            "c.mp4.part",
+            "c.mp4.ytdl",
+            "a.id",
+            "a.en.srt",
+            "a.jpg",
+            "a.info.json",
+        ] {
+            std::fs::write(channel_dir.join(name), b"").unwrap();
         }
 
-        anyhow::bail!("Aucun disque de stockage disponible")
+        let scanner = VideoScanner::with_paths(vec![dir.path().to_string_lossy().to_string()]);
+        let mut names: Vec<_> = scanner
+            .channel_files("chan")
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["a.mp4", "b.MKV"]);
+    }
+
+    #[test]
+    fn test_parse_yt_dlp_version() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(parse_yt_dlp_version("2024.08.06\n"), Some(date(2024, 8, 6)));
+        assert_eq!(
+            parse_yt_dlp_version("2025.01.15.232815"),
+            Some(date(2025, 1, 15))
+        );
+        assert_eq!(parse_yt_dlp_version("not a version"), None);
+
+        assert!(is_yt_dlp_outdated(date(2024, 1, 1), date(2024, 12, 1)));
+        assert!(!is_yt_dlp_outdated(date(2024, 10, 1), date(2024, 12, 1)));
+    }
+
+    #[test]
+    fn test_pick_avatar() {
+        let thumbnail = |url: &str, id: Option<&str>, size: Option<(u32, u32)>| ThumbnailMetadata {
+            url: url.to_string(),
+            id: id.map(str::to_string),
+            width: size.map(|(w, _)| w),
+            height: size.map(|(_, h)| h),
+        };
+
+        let youtube = [
+            thumbnail("banner", Some("banner_uncropped"), None),
+            thumbnail("small", None, Some((88, 88))),
+            thumbnail("avatar", Some("avatar_uncropped"), None),
+        ];
+        assert_eq!(pick_avatar(&youtube), Some("avatar".to_string()));
+
+        let generic = [
+            thumbnail("wide", None, Some((1280, 720))),
+            thumbnail("small", None, Some((88, 88))),
+            thumbnail("large", None, Some((300, 300))),
+        ];
+        assert_eq!(pick_avatar(&generic), Some("large".to_string()));
+
+        assert_eq!(
+            pick_avatar(&[thumbnail("wide", None, Some((1280, 720)))]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scanned_videos_skip_duplicate_ids() {
+        let mut scanned = ScannedVideos::default();
+        let lines = [
+            r#"{"id": "a", "title": "First", "url": "https://example.com/a"}"#,
+            r#"{"id": "b", "title": "B", "url": "https://example.com/b"}"#,
+            "",
+            r#"{"id": "a", "title": "Again", "url": "https://example.com/a"}"#,
+            "not json",
+            r#"{"id": "c", "title": "C", "url": "https://example.com/c"}"#,
+        ];
+
+        let added: Vec<bool> = lines
+            .iter()
+            .map(|line| scanned.push_line(line).is_some())
+            .collect();
+        assert_eq!(added, [true, true, false, false, false, true]);
+
+        let ids: Vec<_> = scanned.videos.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert_eq!(scanned.videos[0].title, "First");
+    }
+
+    #[test]
+    fn test_check_free_space() {
+        let storage = StorageInfo {
+            path: "/mnt/videos".to_string(),
+            available_bytes: 500 * 1024 * 1024,
+            total_bytes: 1000 * 1024 * 1024,
+        };
+
+        assert!(check_free_space(&storage, 0).is_ok());
+        assert!(check_free_space(&storage, 100 * 1024 * 1024).is_ok());
+        let error = check_free_space(&storage, 1024 * 1024 * 1024).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Espace disque insuffisant sur /mnt/videos: 500 Mo libres, 1024 Mo minimum"
+        );
+    }
+
+    #[test]
+    fn test_channel_cache_key_twitch_variants() {
+        for url in [
+            "https://www.twitch.tv/streamer",
+            "https://www.twitch.tv/streamer/",
+            "https://www.twitch.tv/streamer/videos",
+            "https://twitch.tv/Streamer/videos/",
+            "https://m.twitch.tv/streamer/videos?filter=archives&sort=time",
+            "twitch.tv/streamer#videos",
+        ] {
+            assert_eq!(channel_cache_key(url), "twitch.tv/streamer", "{url}");
+        }
+        assert_eq!(
+            channel_cache_key("https://www.twitch.tv/videos/123456"),
+            "twitch.tv/videos/123456"
+        );
+        assert_eq!(
+            channel_cache_key("https://www.twitch.tv/streamer/clips"),
+            "twitch.tv/streamer/clips"
+        );
+    }
+
+    #[test]
+    fn test_channel_cache_key_youtube() {
+        assert_eq!(
+            channel_cache_key("https://www.youtube.com/@handle/videos/?si=abc"),
+            "youtube.com/@handle/videos"
+        );
+        assert_eq!(
+            channel_cache_key("https://youtube.com/playlist?si=abc&list=PLabc"),
+            "youtube.com/playlist?list=PLabc"
+        );
+    }
+
+    #[test]
+    fn test_parse_warnings() {
+        let stderr = "\
+WARNING: [youtube] abc: Some formats are unavailable
+[youtube] Extracting URL
+ERROR: not a warning
+WARNING: [youtube] abc: Some formats are unavailable
+  WARNING: HTTP Error 429: Too Many Requests, retrying
+WARNING:
+";
+        assert_eq!(
+            parse_warnings(stderr),
+            [
+                "[youtube] abc: Some formats are unavailable",
+                "HTTP Error 429: Too Many Requests, retrying",
+            ]
+        );
+        assert!(parse_warnings("").is_empty());
+    }
+
+    #[test]
+    fn test_expected_videos() {
+        assert_eq!(expected_videos(Some(500), 1, 30), Some(30));
+        assert_eq!(expected_videos(Some(12), 1, 30), Some(12));
+        assert_eq!(expected_videos(Some(40), 31, 30), Some(10));
+        assert_eq!(expected_videos(None, 1, 30), None);
+    }
+
+    #[test]
+    fn test_owner_channel_url() {
+        let youtube = VideoOwner {
+            uploader_url: Some("https://www.youtube.com/@handle".to_string()),
+            channel_url: Some("https://www.youtube.com/channel/UC123".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            owner_channel_url(&youtube).as_deref(),
+            Some("https://www.youtube.com/@handle")
+        );
+
+        let legacy = VideoOwner {
+            uploader_url: Some("https://www.youtube.com/user/name".to_string()),
+            channel_url: Some("https://www.youtube.com/channel/UC123".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            owner_channel_url(&legacy).as_deref(),
+            Some("https://www.youtube.com/channel/UC123")
+        );
+
+        let twitch = VideoOwner {
+            uploader_id: Some("streamer".to_string()),
+            extractor_key: Some("TwitchVod".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            owner_channel_url(&twitch).as_deref(),
+            Some("https://www.twitch.tv/streamer")
+        );
     }
 }
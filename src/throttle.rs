@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Pause imposée à une chaîne au premier refus pour trop de requêtes
+const BASE_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Pause maximale, atteinte quand les refus se répètent
+const MAX_COOLDOWN: Duration = Duration::from_secs(30 * 60);
+
+/// Réponse « trop de requêtes » (HTTP 429) dans la sortie de yt-dlp
+pub fn is_rate_limited(output: &str) -> bool {
+    output.contains("HTTP Error 429") || output.to_lowercase().contains("too many requests")
+}
+
+/// Attente lisible: "45 s", "3 min"
+fn format_wait(wait: Duration) -> String {
+    let seconds = wait.as_secs().max(1);
+    if seconds < 60 {
+        format!("{seconds} s")
+    } else {
+        format!("{} min", seconds.div_ceil(60))
+    }
+}
+
+/// Scan refusé: la plateforme limite les requêtes, la chaîne est en pause
+#[derive(Debug)]
+pub struct RateLimited {
+    pub retry_in: Duration,
+}
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Trop de requêtes vers la plateforme, nouvel essai possible dans {}",
+            format_wait(self.retry_in)
+        )
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+#[derive(Debug, Clone, Copy)]
+struct Cooldown {
+    until: Instant,
+    /// Refus d'affilée, pour allonger la pause suivante
+    strikes: u32,
+}
+
+/// Chaînes en pause après un refus pour trop de requêtes. La pause double à chaque
+/// nouveau refus et est oubliée au premier scan réussi.
+#[derive(Debug, Default)]
+pub struct Cooldowns {
+    channels: HashMap<String, Cooldown>,
+}
+
+impl Cooldowns {
+    /// Temps restant avant de pouvoir rescanner la chaîne
+    pub fn remaining(&self, key: &str, now: Instant) -> Option<Duration> {
+        let cooldown = self.channels.get(key)?;
+        cooldown
+            .until
+            .checked_duration_since(now)
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Met la chaîne en pause après un refus et retourne la durée de la pause
+    pub fn start(&mut self, key: &str, now: Instant) -> Duration {
+        let strikes = self
+            .channels
+            .get(key)
+            .map_or(1, |cooldown| cooldown.strikes + 1);
+        let delay = BASE_COOLDOWN
+            .saturating_mul(1 << (strikes - 1).min(10))
+            .min(MAX_COOLDOWN);
+        self.channels.insert(
+            key.to_string(),
+            Cooldown {
+                until: now + delay,
+                strikes,
+            },
+        );
+        delay
+    }
+
+    pub fn clear(&mut self, key: &str) {
+        self.channels.remove(key);
+    }
+}
+
+/// Fait passer les appels à yt-dlp un par un, espacés d'un intervalle minimal
+pub struct YtDlpGate {
+    /// Début du dernier appel
+    last_start: smol::lock::Mutex<Option<Instant>>,
+    min_interval: Duration,
+}
+
+impl YtDlpGate {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            last_start: smol::lock::Mutex::new(None),
+            min_interval,
+        }
+    }
+
+    /// Attend son tour, puis l'intervalle minimal depuis l'appel précédent.
+    /// Le tour est gardé tant que le garde retourné existe.
+    pub async fn acquire(&self) -> smol::lock::MutexGuard<'_, Option<Instant>> {
+        let mut last_start = self.last_start.lock().await;
+        if let Some(wait) =
+            last_start.and_then(|last| self.min_interval.checked_sub(last.elapsed()))
+        {
+            smol::Timer::after(wait).await;
+        }
+        *last_start = Some(Instant::now());
+        last_start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_rate_limited() {
+        assert!(is_rate_limited(
+            "ERROR: [youtube] abc: Unable to download webpage: HTTP Error 429: Too Many Requests"
+        ));
+        assert!(is_rate_limited("ERROR: [twitch] too many requests"));
+        assert!(!is_rate_limited("ERROR: [youtube] abc: Video unavailable"));
+    }
+
+    #[test]
+    fn test_cooldown_doubles_until_cleared() {
+        let now = Instant::now();
+        let mut cooldowns = Cooldowns::default();
+        assert_eq!(cooldowns.remaining("chan", now), None);
+
+        assert_eq!(cooldowns.start("chan", now), BASE_COOLDOWN);
+        assert_eq!(cooldowns.remaining("chan", now), Some(BASE_COOLDOWN));
+        assert_eq!(cooldowns.remaining("other", now), None);
+        // Pause terminée: le scan peut reprendre
+        assert_eq!(cooldowns.remaining("chan", now + BASE_COOLDOWN), None);
+
+        assert_eq!(cooldowns.start("chan", now), BASE_COOLDOWN * 2);
+        assert_eq!(cooldowns.start("chan", now), BASE_COOLDOWN * 4);
+        for _ in 0..10 {
+            cooldowns.start("chan", now);
+        }
+        assert_eq!(cooldowns.remaining("chan", now), Some(MAX_COOLDOWN));
+
+        cooldowns.clear("chan");
+        assert_eq!(cooldowns.start("chan", now), BASE_COOLDOWN);
+    }
+
+    #[test]
+    fn test_rate_limited_message() {
+        let error = RateLimited {
+            retry_in: Duration::from_secs(90),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Trop de requêtes vers la plateforme, nouvel essai possible dans 2 min"
+        );
+        assert_eq!(format_wait(Duration::from_millis(300)), "1 s");
+    }
+
+    #[test]
+    fn test_gate_spaces_calls() {
+        let gate = YtDlpGate::new(Duration::from_millis(50));
+        let start = Instant::now();
+        smol::block_on(async {
+            drop(gate.acquire().await);
+            drop(gate.acquire().await);
+        });
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::paths;
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Délai maximal de téléchargement d'une image
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Runtime tokio dédié aux requêtes HTTP : reqwest ne fonctionne pas
+/// directement sur l'exécuteur de gpui
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("Failed to start the HTTP runtime")
+    })
+}
+
+/// Nom de fichier stable dérivé d'une clé (FNV-1a 64 bits)
+fn file_name(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// Cache disque des images distantes (miniatures, avatars)
+pub struct ThumbnailCache {
+    dir: PathBuf,
+    client: reqwest::Client,
+}
+
+impl ThumbnailCache {
+    pub fn new() -> Self {
+        Self::with_dir(paths::thumbnails_dir())
+    }
+
+    pub fn with_dir(dir: PathBuf) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { dir, client }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(file_name(key))
+    }
+
+    /// Image déjà présente sur disque pour `key`
+    pub fn cached(&self, key: &str) -> Option<PathBuf> {
+        let path = self.path(key);
+        path.is_file().then_some(path)
+    }
+
+    /// Retourne le chemin local de l'image associée à `key`,
+    /// en la téléchargeant depuis `url` si elle n'est pas encore en cache
+    pub async fn fetch(&self, key: &str, url: &str) -> Result<PathBuf> {
+        if let Some(path) = self.cached(key) {
+            return Ok(path);
+        }
+
+        let path = self.path(key);
+        let client = self.client.clone();
+        let url = url.to_string();
+        let bytes = runtime()
+            .spawn(async move {
+                client
+                    .get(&url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .bytes()
+                    .await
+            })
+            .await??;
+
+        // Écriture atomique pour ne jamais afficher une image tronquée
+        let partial = path.with_extension("part");
+        smol::fs::write(&partial, &bytes).await?;
+        smol::fs::rename(&partial, &path).await?;
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name_is_stable_per_key() {
+        assert_eq!(file_name("abc123"), file_name("abc123"));
+        assert_ne!(file_name("abc123"), file_name("abc124"));
+        assert_eq!(file_name("abc123").len(), 16);
+    }
+
+    #[test]
+    fn test_fetch_uses_cached_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ThumbnailCache::with_dir(dir.path().to_path_buf());
+        std::fs::write(cache.path("abc123"), b"image").unwrap();
+
+        // L'URL n'est pas contactée quand l'image est déjà sur disque
+        let path = smol::block_on(cache.fetch("abc123", "http://invalid.invalid/a.jpg")).unwrap();
+        assert_eq!(path, cache.path("abc123"));
+    }
+}
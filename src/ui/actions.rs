@@ -5,8 +5,10 @@ actions!(
     [
         // Navigation
         GoBack,
+        GoForward,
         // Channel management
         AddChannel,
+        FocusUrlInput,
         RefreshChannels,
         // Video management
         RefreshVideos,
@@ -0,0 +1,271 @@
+use crate::downloader_queue::{DownloadStatus, DownloadTask};
+use crate::notifications::Notification;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use std::collections::HashSet;
+
+use super::components::ProgressBar;
+use super::theme::Theme;
+use super::{NDownloaderApp, VideoInfo, VideoStatus};
+
+/// Au-delà de ce nombre de vidéos, « Tout télécharger » demande une confirmation
+const CONFIRM_THRESHOLD: usize = 20;
+
+/// Vidéos d'une chaîne lancées par « Tout télécharger », pour suivre leur avancement global
+pub struct ChannelBatch {
+    channel_name: String,
+    /// Vidéos du lot pas encore terminées
+    remaining: HashSet<String>,
+    total: usize,
+    failed: usize,
+}
+
+impl ChannelBatch {
+    fn new(channel_name: String) -> Self {
+        Self {
+            channel_name,
+            remaining: HashSet::new(),
+            total: 0,
+            failed: 0,
+        }
+    }
+
+    fn add(&mut self, urls: impl IntoIterator<Item = String>) {
+        for url in urls {
+            if self.remaining.insert(url) {
+                self.total += 1;
+            }
+        }
+    }
+
+    fn done(&self) -> usize {
+        self.total - self.remaining.len()
+    }
+
+    /// Avancement de 0 à 1: vidéos terminées plus la progression de celles en cours
+    fn progress(&self, tasks: &[DownloadTask]) -> f32 {
+        let running: f32 = tasks
+            .iter()
+            .filter(|task| {
+                task.status == DownloadStatus::Downloading
+                    && self.remaining.contains(&task.video_url)
+            })
+            .map(|task| task.progress)
+            .sum();
+        (self.done() as f32 + running) / self.total.max(1) as f32
+    }
+
+    /// Note la fin d'une vidéo du lot. Retourne `true` si c'était la dernière.
+    fn finish(&mut self, video_url: &str, succeeded: bool) -> bool {
+        if !self.remaining.remove(video_url) {
+            return false;
+        }
+        if !succeeded {
+            self.failed += 1;
+        }
+        self.remaining.is_empty()
+    }
+
+    fn message(&self) -> String {
+        let mut message = format!(
+            "{} vidéo(s) de {} téléchargée(s)",
+            self.total - self.failed,
+            self.channel_name
+        );
+        if self.failed > 0 {
+            message.push_str(&format!(", {} en échec", self.failed));
+        }
+        message
+    }
+}
+
+impl NDownloaderApp {
+    /// Vidéos affichées qui ne sont ni téléchargées ni déjà en file
+    fn downloadable_videos(&self) -> Vec<VideoInfo> {
+        self.videos
+            .iter()
+            .filter(|video| video.status == VideoStatus::NotDownloaded)
+            .cloned()
+            .collect()
+    }
+
+    fn request_download_all(&mut self, cx: &mut Context<Self>) {
+        if self.downloadable_videos().len() >= CONFIRM_THRESHOLD {
+            self.pending_download_all = true;
+            cx.notify();
+        } else {
+            self.download_all(cx);
+        }
+    }
+
+    /// Met en file toutes les vidéos de la chaîne sélectionnée pas encore téléchargées
+    fn download_all(&mut self, cx: &mut Context<Self>) {
+        self.pending_download_all = false;
+        let Some(channel) = self
+            .selected_channel
+            .and_then(|index| self.channels.get(index))
+            .cloned()
+        else {
+            return;
+        };
+        let videos = self.downloadable_videos();
+        if videos.is_empty() || !self.enqueue_videos(&channel.name, &videos, cx) {
+            cx.notify();
+            return;
+        }
+
+        self.channel_batches
+            .entry(channel.url)
+            .or_insert_with(|| ChannelBatch::new(channel.name.clone()))
+            .add(videos.iter().map(|video| video.url.clone()));
+        Notification::info(
+            "Téléchargements ajoutés",
+            &format!(
+                "{} vidéo(s) de {} ajoutée(s) à la file de téléchargement",
+                videos.len(),
+                channel.name
+            ),
+        );
+    }
+
+    /// Retire une vidéo terminée de son lot et annonce la fin du lot
+    pub(super) fn finish_batch_video(&mut self, video_url: &str, succeeded: bool) {
+        let finished = self
+            .channel_batches
+            .iter_mut()
+            .find_map(|(url, batch)| batch.finish(video_url, succeeded).then(|| url.clone()));
+        if let Some(batch) = finished.and_then(|url| self.channel_batches.remove(&url)) {
+            if batch.failed > 0 {
+                Notification::error("Chaîne téléchargée", &batch.message());
+            } else {
+                Notification::success("Chaîne téléchargée", &batch.message());
+            }
+        }
+    }
+
+    /// Bouton « Tout télécharger » et progression du lot de la chaîne affichée
+    pub(super) fn render_download_all(
+        &self,
+        channel_index: usize,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        let count = self.downloadable_videos().len();
+        let batch = self
+            .channels
+            .get(channel_index)
+            .and_then(|channel| self.channel_batches.get(&channel.url));
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .when_some(batch, |this, batch| {
+                let progress = batch.progress(&self.download_queue.get_tasks());
+                this.child(
+                    div()
+                        .text_color(rgb(theme.text_muted))
+                        .text_size(px(12.0))
+                        .child(format!("{}/{}", batch.done(), batch.total)),
+                )
+                .child(div().w(px(100.0)).child(ProgressBar::new(progress)))
+            })
+            .when(self.pending_download_all && count > 0, |this| {
+                this.child(
+                    div()
+                        .text_color(rgb(theme.text_muted))
+                        .text_size(px(12.0))
+                        .child(format!("Télécharger les {count} vidéos ?")),
+                )
+                .child(download_all_button(
+                    "Confirmer",
+                    theme.primary,
+                    theme.text_on_accent,
+                    cx.listener(|this, _event, _window, cx| {
+                        this.download_all(cx);
+                    }),
+                ))
+                .child(download_all_button(
+                    "Annuler",
+                    theme.element,
+                    theme.text,
+                    cx.listener(|this, _event, _window, cx| {
+                        this.pending_download_all = false;
+                        cx.notify();
+                    }),
+                ))
+            })
+            .when(!self.pending_download_all && count > 0, |this| {
+                this.child(download_all_button(
+                    format!("Tout télécharger ({count})"),
+                    theme.primary,
+                    theme.text_on_accent,
+                    cx.listener(|this, _event, _window, cx| {
+                        this.request_download_all(cx);
+                    }),
+                ))
+            })
+    }
+}
+
+fn download_all_button(
+    label: impl Into<SharedString>,
+    background: u32,
+    text_color: u32,
+    on_click: impl Fn(&MouseDownEvent, &mut Window, &mut App) + 'static,
+) -> impl IntoElement {
+    div()
+        .px_3()
+        .py_1()
+        .bg(rgb(background))
+        .rounded_md()
+        .cursor_pointer()
+        .hover(|style| style.opacity(0.85))
+        .on_mouse_down(MouseButton::Left, on_click)
+        .child(
+            div()
+                .text_color(rgb(text_color))
+                .text_size(px(13.0))
+                .font_weight(FontWeight::SEMIBOLD)
+                .child(label.into()),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(url: &str, status: DownloadStatus, progress: f32) -> DownloadTask {
+        DownloadTask {
+            progress,
+            ..DownloadTask::for_test(url, status)
+        }
+    }
+
+    #[test]
+    fn test_batch_progress_and_completion() {
+        let mut batch = ChannelBatch::new("chan".to_string());
+        batch.add(["a", "b", "c", "d"].map(str::to_string));
+        // Une vidéo déjà dans le lot n'est pas comptée deux fois
+        batch.add(["a".to_string()]);
+        assert_eq!(batch.total, 4);
+
+        assert!(!batch.finish("a", true));
+        assert!(!batch.finish("other", true));
+        let tasks = [
+            task("b", DownloadStatus::Downloading, 0.5),
+            task("c", DownloadStatus::Queued, 0.0),
+            task("x", DownloadStatus::Downloading, 0.9),
+        ];
+        assert_eq!(batch.progress(&tasks), 1.5 / 4.0);
+
+        assert!(!batch.finish("b", false));
+        assert!(!batch.finish("c", true));
+        assert!(batch.finish("d", true));
+        assert_eq!(batch.done(), 4);
+        assert_eq!(
+            batch.message(),
+            "3 vidéo(s) de chan téléchargée(s), 1 en échec"
+        );
+    }
+}
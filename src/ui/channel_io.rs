@@ -0,0 +1,237 @@
+use crate::notifications::Notification;
+use gpui::*;
+use std::path::{Path, PathBuf};
+
+use super::{find_duplicate, save_channels, Channel, NDownloaderApp, Platform};
+
+/// Nom proposé pour le fichier d'export
+const EXPORT_FILE_NAME: &str = "ndownloader-chaines.json";
+
+/// Bilan d'un import de chaînes
+#[derive(Debug, Default, PartialEq)]
+struct ImportSummary {
+    imported: usize,
+    /// Entrées mal formées ou dont l'URL ne correspond pas à la plateforme
+    invalid: usize,
+    /// Chaînes déjà suivies
+    duplicates: usize,
+}
+
+impl ImportSummary {
+    fn message(&self) -> String {
+        let mut message = format!(
+            "Importé {} chaîne(s), {} ignorée(s)",
+            self.imported, self.invalid
+        );
+        if self.duplicates > 0 {
+            message.push_str(&format!(", {} déjà suivie(s)", self.duplicates));
+        }
+        message
+    }
+}
+
+/// Une chaîne importée doit pointer vers une URL que l'application sait suivre
+fn is_valid(channel: &Channel) -> bool {
+    if channel.name.trim().is_empty()
+        || Platform::from_url(&channel.url).as_ref() != Some(&channel.platform)
+    {
+        return false;
+    }
+    if channel.is_playlist {
+        Platform::extract_playlist_id(&channel.url).is_some()
+    } else {
+        Platform::extract_channel_name(&channel.url).is_some()
+    }
+}
+
+/// Ajoute à la liste les entrées valides d'un export, sans doublon avec les chaînes suivies
+fn merge_channels(channels: &mut Vec<Channel>, entries: Vec<serde_json::Value>) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+    for entry in entries {
+        let channel = match serde_json::from_value::<Channel>(entry) {
+            Ok(channel) if is_valid(&channel) => channel,
+            _ => {
+                summary.invalid += 1;
+                continue;
+            }
+        };
+        if find_duplicate(channels, &channel.url, &channel.platform, &channel.name).is_some() {
+            summary.duplicates += 1;
+            continue;
+        }
+        channels.push(channel);
+        summary.imported += 1;
+    }
+    summary
+}
+
+fn write_export(path: &Path, channels: &[Channel]) -> anyhow::Result<()> {
+    let content = serde_json::to_string_pretty(channels)?;
+    std::fs::write(path, content)
+        .map_err(|error| anyhow::Error::new(error).context(format!("{}", path.display())))
+}
+
+/// Lit un export: un tableau JSON dont chaque entrée est validée séparément
+fn read_export(path: &Path) -> anyhow::Result<Vec<serde_json::Value>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| anyhow::Error::new(error).context(format!("{}", path.display())))?;
+    serde_json::from_str(&content).map_err(|error| {
+        anyhow::Error::new(error).context("Le fichier n'est pas une liste de chaînes")
+    })
+}
+
+impl NDownloaderApp {
+    /// Enregistre la liste des chaînes dans un fichier choisi par l'utilisateur
+    pub(super) fn export_channels(&mut self, cx: &mut Context<Self>) {
+        let directory = dirs::home_dir().unwrap_or_else(std::env::temp_dir);
+        let path = cx.prompt_for_new_path(&directory, Some(EXPORT_FILE_NAME));
+        let channels = self.channels.clone();
+
+        cx.spawn(async move |_, _| {
+            let path = match path.await {
+                Ok(Ok(Some(path))) => path,
+                Ok(Ok(None)) | Err(_) => return,
+                Ok(Err(error)) => {
+                    tracing::warn!("Export dialog failed: {}", error);
+                    return;
+                }
+            };
+            match write_export(&path, &channels) {
+                Ok(()) => Notification::success(
+                    "Chaînes exportées",
+                    &format!(
+                        "{} chaîne(s) enregistrée(s) dans {}",
+                        channels.len(),
+                        path.display()
+                    ),
+                ),
+                Err(error) => {
+                    tracing::warn!("Failed to export channels: {:#}", error);
+                    Notification::error("Export impossible", &format!("{error:#}"));
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Fusionne dans la liste les chaînes d'un fichier exporté
+    pub(super) fn import_channels(&mut self, cx: &mut Context<Self>) {
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: None,
+        });
+
+        cx.spawn(async move |this, cx| {
+            let path: PathBuf = match paths.await {
+                Ok(Ok(Some(paths))) => match paths.into_iter().next() {
+                    Some(path) => path,
+                    None => return,
+                },
+                Ok(Ok(None)) | Err(_) => return,
+                Ok(Err(error)) => {
+                    tracing::warn!("Import dialog failed: {}", error);
+                    return;
+                }
+            };
+            let entries = match read_export(&path) {
+                Ok(entries) => entries,
+                Err(error) => {
+                    tracing::warn!("Failed to import channels: {:#}", error);
+                    Notification::error("Import impossible", &format!("{error:#}"));
+                    return;
+                }
+            };
+
+            this.update(cx, |this, cx| {
+                let summary = merge_channels(&mut this.channels, entries);
+                if summary.imported > 0 {
+                    save_channels(&this.channels);
+                    this.load_avatars(cx);
+                }
+                Notification::info("Import des chaînes", &summary.message());
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(name: &str, platform: &str, url: &str) -> serde_json::Value {
+        json!({ "name": name, "platform": platform, "url": url })
+    }
+
+    #[test]
+    fn test_merge_skips_invalid_and_duplicates() {
+        let mut channels = Vec::new();
+        let summary = merge_channels(
+            &mut channels,
+            vec![
+                entry("handle", "YouTube", "https://www.youtube.com/@handle"),
+                entry("streamer", "Twitch", "https://www.twitch.tv/streamer"),
+                // Plateforme incohérente avec l'URL
+                entry("other", "Twitch", "https://www.youtube.com/@other"),
+                // URL qui n'est pas une chaîne
+                entry("video", "YouTube", "https://www.youtube.com/watch?v=abc"),
+                json!({ "name": "incomplet" }),
+                json!(42),
+                // Même chaîne écrite différemment
+                entry(
+                    "Handle",
+                    "YouTube",
+                    "https://www.youtube.com/@Handle/videos",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 2,
+                invalid: 4,
+                duplicates: 1,
+            }
+        );
+        assert_eq!(channels.len(), 2);
+        assert_eq!(
+            summary.message(),
+            "Importé 2 chaîne(s), 4 ignorée(s), 1 déjà suivie(s)"
+        );
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let mut channels = Vec::new();
+        merge_channels(
+            &mut channels,
+            vec![json!({
+                "name": "Ma playlist",
+                "platform": "YouTube",
+                "url": "https://www.youtube.com/playlist?list=PLabc",
+                "is_playlist": true,
+            })],
+        );
+        assert_eq!(channels.len(), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(EXPORT_FILE_NAME);
+        write_export(&path, &channels).unwrap();
+
+        let mut imported = Vec::new();
+        let summary = merge_channels(&mut imported, read_export(&path).unwrap());
+        assert_eq!(summary.imported, 1);
+        assert!(imported[0].is_playlist);
+
+        // Réimporter dans la même liste ne crée pas de doublon
+        let summary = merge_channels(&mut imported, read_export(&path).unwrap());
+        assert_eq!(summary.duplicates, 1);
+        assert_eq!(imported.len(), 1);
+    }
+}
@@ -1,37 +1,100 @@
+use gpui::prelude::FluentBuilder;
 use gpui::*;
+use std::path::PathBuf;
 
-use super::super::{Channel, Platform, NORD11, NORD15, NORD6};
+use super::super::theme::Theme;
+use super::super::{Channel, Platform};
+
+const AVATAR_SIZE: f32 = 28.0;
+
+/// Ancienneté du dernier scan: "vérifiée il y a 5 min"
+fn format_last_scanned(elapsed_secs: i64) -> String {
+    let minutes = elapsed_secs.max(0) / 60;
+    match minutes {
+        0 => "vérifiée à l'instant".to_string(),
+        1..=59 => format!("vérifiée il y a {minutes} min"),
+        60..=1439 => format!("vérifiée il y a {} h", minutes / 60),
+        _ => format!("vérifiée il y a {} j", minutes / 1440),
+    }
+}
 
 #[derive(IntoElement)]
 pub struct ChannelItem {
     channel: Channel,
+    avatar: Option<PathBuf>,
 }
 
 impl ChannelItem {
     pub fn new(channel: Channel) -> Self {
-        Self { channel }
+        Self {
+            channel,
+            avatar: None,
+        }
+    }
+
+    /// Avatar de la chaîne, remplacé par son initiale s'il est absent
+    pub fn avatar(mut self, avatar: Option<PathBuf>) -> Self {
+        self.avatar = avatar;
+        self
     }
 }
 
 impl RenderOnce for ChannelItem {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = Theme::get(cx);
         let platform_color = match self.channel.platform {
-            Platform::YouTube => rgb(NORD11),
-            Platform::Twitch => rgb(NORD15),
+            Platform::YouTube => rgb(theme.error),
+            Platform::Twitch => rgb(theme.highlight),
         };
-        let platform_name = match self.channel.platform {
-            Platform::YouTube => "YouTube",
-            Platform::Twitch => "Twitch",
+        let last_scanned = self
+            .channel
+            .last_scanned
+            .map(|timestamp| format_last_scanned(chrono::Utc::now().timestamp() - timestamp));
+        let summary = self.channel.download_summary;
+        let platform_name = if self.channel.is_playlist {
+            format!("{} · Playlist", self.channel.platform.name())
+        } else {
+            self.channel.platform.name().to_string()
+        };
+
+        let avatar = match self.avatar {
+            Some(path) => img(path)
+                .size(px(AVATAR_SIZE))
+                .rounded_full()
+                .object_fit(ObjectFit::Cover)
+                .into_any_element(),
+            None => {
+                let initial: String = self
+                    .channel
+                    .name
+                    .chars()
+                    .next()
+                    .map(|c| c.to_uppercase().collect())
+                    .unwrap_or_default();
+                div()
+                    .size(px(AVATAR_SIZE))
+                    .rounded_full()
+                    .bg(platform_color)
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .text_color(rgb(theme.text_on_accent))
+                    .text_size(px(14.0))
+                    .font_weight(FontWeight::BOLD)
+                    .child(initial)
+                    .into_any_element()
+            }
         };
 
         div()
             .flex()
             .items_center()
             .gap_3()
+            .child(avatar)
             .child(
                 div().px_2().py_1().bg(platform_color).rounded_sm().child(
                     div()
-                        .text_color(rgb(NORD6))
+                        .text_color(rgb(theme.text_on_accent))
                         .text_size(px(12.0))
                         .font_weight(FontWeight::BOLD)
                         .child(platform_name),
@@ -39,9 +102,47 @@ impl RenderOnce for ChannelItem {
             )
             .child(
                 div()
-                    .text_color(rgb(NORD6))
-                    .text_size(px(14.0))
-                    .child(self.channel.name.clone()),
+                    .flex()
+                    .flex_col()
+                    .child(
+                        div()
+                            .text_color(rgb(theme.text))
+                            .text_size(px(14.0))
+                            .child(self.channel.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .text_size(px(11.0))
+                            .when_some(summary, |this, summary| {
+                                this.child(
+                                    div()
+                                        .text_color(rgb(if summary.is_complete() {
+                                            theme.success
+                                        } else {
+                                            theme.text_muted
+                                        }))
+                                        .child(format!("{} téléchargées", summary.label())),
+                                )
+                            })
+                            .when_some(last_scanned, |this, label| {
+                                this.child(div().text_color(rgb(theme.text_muted)).child(label))
+                            }),
+                    ),
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_last_scanned() {
+        assert_eq!(format_last_scanned(30), "vérifiée à l'instant");
+        assert_eq!(format_last_scanned(5 * 60), "vérifiée il y a 5 min");
+        assert_eq!(format_last_scanned(3 * 3600 + 120), "vérifiée il y a 3 h");
+        assert_eq!(format_last_scanned(2 * 86400), "vérifiée il y a 2 j");
+    }
+}
@@ -1,7 +1,9 @@
 mod channel_item;
 mod progress_bar;
+mod spinner;
 mod video_item;
 
 pub use channel_item::ChannelItem;
 pub use progress_bar::ProgressBar;
+pub use spinner::Spinner;
 pub use video_item::VideoItem;
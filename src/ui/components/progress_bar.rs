@@ -1,5 +1,7 @@
 use gpui::*;
 
+use super::super::theme::Theme;
+
 #[derive(IntoElement)]
 pub struct ProgressBar {
     progress: f32, // 0.0 to 1.0
@@ -14,17 +16,18 @@ impl ProgressBar {
 }
 
 impl RenderOnce for ProgressBar {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = Theme::get(cx);
         div()
             .w_full()
             .h(px(4.0))
-            .bg(rgb(0x3b4252))
+            .bg(rgb(theme.surface))
             .rounded(px(2.0))
             .child(
                 div()
                     .h_full()
                     .w(relative(self.progress))
-                    .bg(rgb(0x88c0d0))
+                    .bg(rgb(theme.accent))
                     .rounded(px(2.0)),
             )
     }
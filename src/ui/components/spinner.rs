@@ -0,0 +1,34 @@
+use gpui::*;
+use std::time::Duration;
+
+use super::super::theme::Theme;
+
+const FRAMES: [&str; 8] = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
+
+#[derive(IntoElement)]
+pub struct Spinner {
+    id: ElementId,
+}
+
+impl Spinner {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl RenderOnce for Spinner {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .text_color(rgb(theme.accent))
+            .text_size(px(14.0))
+            .with_animation(
+                self.id,
+                Animation::new(Duration::from_millis(800)).repeat(),
+                |this, delta| {
+                    let frame = ((delta * FRAMES.len() as f32) as usize).min(FRAMES.len() - 1);
+                    this.child(FRAMES[frame])
+                },
+            )
+    }
+}
@@ -1,13 +1,20 @@
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 
-use super::super::{VideoInfo, VideoStatus, NORD13, NORD14, NORD6, NORD9};
+use super::super::theme::Theme;
+use super::super::{Thumbnail, VideoInfo, VideoStatus};
 use super::ProgressBar;
 
+/// Taille des miniatures (16:9)
+const THUMBNAIL_WIDTH: f32 = 96.0;
+const THUMBNAIL_HEIGHT: f32 = 54.0;
+
 #[derive(IntoElement)]
 pub struct VideoItem {
     video: VideoInfo,
     progress: Option<f32>,
+    queued: bool,
+    thumbnail: Option<Thumbnail>,
 }
 
 impl VideoItem {
@@ -15,6 +22,8 @@ impl VideoItem {
         Self {
             video,
             progress: None,
+            queued: false,
+            thumbnail: None,
         }
     }
 
@@ -22,21 +31,85 @@ impl VideoItem {
         self.progress = Some(progress);
         self
     }
+
+    /// Le téléchargement attend qu'une place se libère dans la queue
+    pub fn queued(mut self) -> Self {
+        self.queued = true;
+        self
+    }
+
+    /// Miniature affichée à gauche du titre (espace réservé pendant le chargement)
+    pub fn thumbnail(mut self, thumbnail: Option<Thumbnail>) -> Self {
+        self.thumbnail = thumbnail;
+        self
+    }
+}
+
+/// Formate une durée en secondes: "1:23:45" ou "12:34"
+fn format_duration(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Formate une date yt-dlp (YYYYMMDD) en JJ/MM/AAAA
+fn format_upload_date(date: &str) -> Option<String> {
+    if date.len() != 8 || !date.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{}/{}/{}", &date[6..8], &date[4..6], &date[..4]))
 }
 
 impl RenderOnce for VideoItem {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = Theme::get(cx);
         let (indicator_color, status_text, status_color) = match self.video.status {
-            VideoStatus::Downloaded => (rgb(NORD14), "Téléchargé", rgb(NORD14)),
-            VideoStatus::Downloading => (rgb(NORD9), "En cours...", rgb(NORD9)),
-            VideoStatus::NotDownloaded => (rgb(NORD13), "Non téléchargé", rgb(NORD13)),
+            VideoStatus::Downloaded => (rgb(theme.success), "Téléchargé", rgb(theme.success)),
+            VideoStatus::Downloading if self.queued => {
+                (rgb(theme.text_muted), "En attente", rgb(theme.text_muted))
+            }
+            VideoStatus::Downloading => (rgb(theme.info), "En cours...", rgb(theme.info)),
+            VideoStatus::NotDownloaded => {
+                (rgb(theme.warning), "Non téléchargé", rgb(theme.warning))
+            }
         };
 
+        let details: Vec<String> = [
+            self.video.duration.map(format_duration),
+            self.video
+                .upload_date
+                .as_deref()
+                .and_then(format_upload_date),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
         div()
             .flex()
             .items_center()
             .gap_3()
             .child(div().w_3().h_3().rounded_full().bg(indicator_color))
+            .when_some(self.thumbnail, |this, thumbnail| {
+                this.child(match thumbnail {
+                    Thumbnail::Loading => div()
+                        .w(px(THUMBNAIL_WIDTH))
+                        .h(px(THUMBNAIL_HEIGHT))
+                        .rounded_sm()
+                        .bg(rgb(theme.surface))
+                        .into_any_element(),
+                    Thumbnail::Loaded(path) => img(path)
+                        .w(px(THUMBNAIL_WIDTH))
+                        .h(px(THUMBNAIL_HEIGHT))
+                        .rounded_sm()
+                        .object_fit(ObjectFit::Cover)
+                        .into_any_element(),
+                })
+            })
             .child(
                 div()
                     .flex()
@@ -45,11 +118,19 @@ impl RenderOnce for VideoItem {
                     .flex_1()
                     .child(
                         div()
-                            .text_color(rgb(NORD6))
+                            .text_color(rgb(theme.text))
                             .text_size(px(14.0))
                             .font_weight(FontWeight::SEMIBOLD)
                             .child(self.video.title.clone()),
                     )
+                    .when(!details.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_color(rgb(theme.text_muted))
+                                .text_size(px(12.0))
+                                .child(details.join(" · ")),
+                        )
+                    })
                     .child(
                         div()
                             .text_color(status_color)
@@ -68,3 +149,24 @@ impl RenderOnce for VideoItem {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(5.0), "0:05");
+        assert_eq!(format_duration(754.4), "12:34");
+        assert_eq!(format_duration(5025.0), "1:23:45");
+    }
+
+    #[test]
+    fn test_format_upload_date() {
+        assert_eq!(
+            format_upload_date("20240806"),
+            Some("06/08/2024".to_string())
+        );
+        assert_eq!(format_upload_date("2024-08-06"), None);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::config::Config;
+use crate::notifications::Notification;
+use crate::scanner::VideoScanner;
+use futures::StreamExt;
+use gpui::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::theme::Theme;
+use super::NDownloaderApp;
+
+/// Délai laissé aux éditeurs pour finir d'écrire avant de relire le fichier
+const RELOAD_DELAY: Duration = Duration::from_millis(500);
+
+impl NDownloaderApp {
+    /// Relit la configuration dès que le fichier est modifié à la main
+    pub(super) fn watch_config(&mut self, cx: &mut Context<Self>) {
+        let (change_tx, mut change_rx) = futures::channel::mpsc::unbounded();
+        match Config::watch(move || {
+            change_tx.unbounded_send(()).ok();
+        }) {
+            Ok(watcher) => self.config_watcher = Some(watcher),
+            Err(error) => {
+                tracing::warn!("Failed to watch config file: {}", error);
+                return;
+            }
+        }
+
+        cx.spawn(async move |this, cx| {
+            while change_rx.next().await.is_some() {
+                // Une sauvegarde produit souvent plusieurs événements: n'en garder qu'un
+                cx.background_executor().timer(RELOAD_DELAY).await;
+                while let Ok(Some(())) = change_rx.try_next() {}
+
+                if this.update(cx, |this, cx| this.reload_config(cx)).is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn reload_config(&mut self, cx: &mut Context<Self>) {
+        let reloaded = match Config::reload() {
+            Ok(config) => config,
+            Err(error) => {
+                tracing::warn!("Failed to reload config file: {:#}", error);
+                Notification::error(
+                    "Configuration invalide",
+                    &format!("Le fichier modifié n'a pas été appliqué: {error:#}"),
+                );
+                return;
+            }
+        };
+        // Les écritures de l'application déclenchent aussi le watcher
+        if reloaded == self.config {
+            return;
+        }
+
+        let downloads_active = !self.downloading_videos.is_empty();
+        let (config, deferred) = self.config.apply_reloaded(reloaded, downloads_active);
+        tracing::info!("Config file changed, applying new settings");
+        if deferred.is_some() {
+            Notification::info(
+                "Configuration rechargée",
+                "Les nouveaux dossiers de stockage seront utilisés à la fin des téléchargements en cours",
+            );
+        }
+        self.deferred_storage_paths = deferred;
+        self.apply_config(config, cx);
+    }
+
+    /// Applique les dossiers de stockage mis de côté pendant des téléchargements
+    pub(super) fn apply_deferred_storage_paths(&mut self, cx: &mut Context<Self>) {
+        if !self.downloading_videos.is_empty() {
+            return;
+        }
+        let Some(storage_paths) = self.deferred_storage_paths.take() else {
+            return;
+        };
+        let mut config = self.config.clone();
+        config.storage_paths = storage_paths;
+        self.apply_config(config, cx);
+        Notification::info(
+            "Configuration appliquée",
+            "Les nouveaux dossiers de stockage sont utilisés",
+        );
+    }
+
+    /// Remplace la configuration courante et met à jour tout ce qui en dépend
+    pub(super) fn apply_config(&mut self, config: Config, cx: &mut Context<Self>) {
+        if config.theme != self.config.theme {
+            cx.set_global(Theme::for_mode(config.theme));
+            cx.refresh_windows();
+        }
+        self.download_queue.configure(&config);
+        self.scanner = Arc::new(VideoScanner::from_config(&config).database(self.database.clone()));
+        self.reset_unavailable_storage();
+        let restart_monitoring =
+            config.monitor_interval_minutes != self.config.monitor_interval_minutes;
+        self.config = config;
+        if restart_monitoring {
+            self.start_monitoring(cx);
+        }
+        cx.notify();
+    }
+}
@@ -0,0 +1,288 @@
+use crate::notifications::Notification;
+use crate::scanner;
+use gpui::*;
+use std::path::PathBuf;
+
+use super::theme::Theme;
+use super::{save_channels, NDownloaderApp, VideoInfo, VideoStatus};
+
+/// Élément visé par un clic droit
+#[derive(Clone)]
+pub(super) enum MenuTarget {
+    /// Chaîne repérée par son URL: sa position peut changer pendant que le menu est ouvert
+    Channel(String),
+    Video(VideoInfo),
+}
+
+/// Menu ouvert par un clic droit sur une chaîne ou une vidéo
+pub(super) struct ContextMenu {
+    position: Point<Pixels>,
+    target: MenuTarget,
+    /// « Supprimer le fichier » attend une confirmation
+    confirm_delete: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MenuAction {
+    Open,
+    Refresh,
+    CopyLink,
+    DeleteChannel,
+    Download,
+    OpenFolder,
+    DeleteFile,
+}
+
+impl MenuAction {
+    fn label(&self, confirm_delete: bool) -> &'static str {
+        match self {
+            MenuAction::Open => "Ouvrir",
+            MenuAction::Refresh => "Rafraîchir",
+            MenuAction::CopyLink => "Copier le lien",
+            MenuAction::DeleteChannel => "Supprimer",
+            MenuAction::Download => "Télécharger",
+            MenuAction::OpenFolder => "Ouvrir le dossier",
+            MenuAction::DeleteFile if confirm_delete => "Confirmer la suppression",
+            MenuAction::DeleteFile => "Supprimer le fichier",
+        }
+    }
+
+    fn is_destructive(&self) -> bool {
+        matches!(self, MenuAction::DeleteChannel | MenuAction::DeleteFile)
+    }
+}
+
+/// Actions proposées pour un élément, dans l'ordre du menu
+fn menu_actions(target: &MenuTarget) -> Vec<MenuAction> {
+    match target {
+        MenuTarget::Channel(_) => vec![
+            MenuAction::Open,
+            MenuAction::Refresh,
+            MenuAction::CopyLink,
+            MenuAction::DeleteChannel,
+        ],
+        MenuTarget::Video(video) => match video.status {
+            VideoStatus::NotDownloaded => vec![MenuAction::Download, MenuAction::CopyLink],
+            VideoStatus::Downloading => vec![MenuAction::CopyLink],
+            VideoStatus::Downloaded => vec![
+                MenuAction::CopyLink,
+                MenuAction::OpenFolder,
+                MenuAction::DeleteFile,
+            ],
+        },
+    }
+}
+
+impl NDownloaderApp {
+    pub(super) fn open_context_menu(
+        &mut self,
+        target: MenuTarget,
+        position: Point<Pixels>,
+        cx: &mut Context<Self>,
+    ) {
+        self.context_menu = Some(ContextMenu {
+            position,
+            target,
+            confirm_delete: false,
+        });
+        cx.notify();
+    }
+
+    pub(super) fn close_context_menu(&mut self, cx: &mut Context<Self>) {
+        if self.context_menu.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    fn run_menu_action(&mut self, action: MenuAction, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(menu) = self.context_menu.as_mut() else {
+            return;
+        };
+        // La suppression d'un fichier demande un second clic, menu ouvert
+        if action == MenuAction::DeleteFile && !menu.confirm_delete {
+            menu.confirm_delete = true;
+            cx.notify();
+            return;
+        }
+        let Some(menu) = self.context_menu.take() else {
+            return;
+        };
+        cx.notify();
+
+        match menu.target {
+            MenuTarget::Channel(url) => {
+                let Some(index) = self.channels.iter().position(|channel| channel.url == url)
+                else {
+                    return;
+                };
+                match action {
+                    MenuAction::Open => self.select_channel(index, window, cx),
+                    MenuAction::Refresh => self.open_channel(index, true, window, cx),
+                    MenuAction::CopyLink => self.copy_link(url, cx),
+                    MenuAction::DeleteChannel => self.delete_channel(index, cx),
+                    _ => {}
+                }
+            }
+            MenuTarget::Video(video) => match action {
+                MenuAction::Download => {
+                    let Some(channel) = self
+                        .selected_channel
+                        .and_then(|index| self.channels.get(index))
+                    else {
+                        return;
+                    };
+                    let channel_name = channel.name.clone();
+                    self.start_download(video.id, video.url, channel_name, cx);
+                }
+                MenuAction::CopyLink => self.copy_link(video.url, cx),
+                MenuAction::OpenFolder => self.open_video_folder(video, cx),
+                MenuAction::DeleteFile => self.delete_video_file(video, cx),
+                _ => {}
+            },
+        }
+    }
+
+    /// Supprime le fichier d'une vidéo téléchargée (et son identifiant enregistré à côté)
+    fn delete_video_file(&mut self, video: VideoInfo, cx: &mut Context<Self>) {
+        let Some(channel) = self
+            .selected_channel
+            .and_then(|index| self.channels.get(index))
+        else {
+            return;
+        };
+        let channel_url = channel.url.clone();
+        let channel_name = channel.name.clone();
+        let scanner = self.scanner.clone();
+
+        cx.spawn(async move |this, cx| {
+            let path = match scanner
+                .is_video_downloaded(&channel_name, &video.id, video.duration)
+                .await
+            {
+                Ok(Some(path)) => PathBuf::from(path),
+                Ok(None) => {
+                    Notification::error(
+                        "Fichier introuvable",
+                        &format!("{} a été déplacé ou supprimé", video.title),
+                    );
+                    return;
+                }
+                Err(error) => {
+                    Notification::error("Fichier introuvable", &error.to_string());
+                    return;
+                }
+            };
+            if let Err(error) = std::fs::remove_file(&path) {
+                tracing::warn!("Failed to delete {}: {}", path.display(), error);
+                Notification::error(
+                    "Suppression impossible",
+                    &format!("{}: {error}", path.display()),
+                );
+                return;
+            }
+            // Pas d'identifiant à conserver pour un fichier qui n'existe plus
+            std::fs::remove_file(scanner::id_sidecar_path(&path)).ok();
+            Notification::info("Fichier supprimé", &video.title);
+
+            this.update(cx, |this, cx| {
+                if let Some(v) = this.videos.iter_mut().find(|v| v.url == video.url) {
+                    v.status = VideoStatus::NotDownloaded;
+                }
+                if let Some(summary) = this
+                    .channels
+                    .iter_mut()
+                    .find(|channel| channel.url == channel_url)
+                    .and_then(|channel| channel.download_summary.as_mut())
+                {
+                    summary.downloaded = summary.downloaded.saturating_sub(1);
+                    save_channels(&this.channels);
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    pub(super) fn render_context_menu(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let menu = self.context_menu.as_ref()?;
+        let theme = Theme::get(cx);
+        let confirm_delete = menu.confirm_delete;
+
+        Some(deferred(
+            anchored()
+                .position(menu.position)
+                .snap_to_window_with_margin(px(8.0))
+                .child(
+                    div()
+                        .min_w(px(180.0))
+                        .flex()
+                        .flex_col()
+                        .py_1()
+                        .bg(rgb(theme.surface))
+                        .border_1()
+                        .border_color(rgb(theme.border))
+                        .rounded_md()
+                        .shadow_lg()
+                        // Un clic ailleurs ferme le menu
+                        .on_mouse_down_out(cx.listener(|this, _event, _window, cx| {
+                            this.close_context_menu(cx);
+                        }))
+                        .children(menu_actions(&menu.target).into_iter().map(|action| {
+                            let color = if action.is_destructive() {
+                                theme.error
+                            } else {
+                                theme.text
+                            };
+                            div()
+                                .px_3()
+                                .py_1()
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(theme.border)))
+                                .on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(move |this, _event, window, cx| {
+                                        this.run_menu_action(action, window, cx);
+                                        cx.stop_propagation();
+                                    }),
+                                )
+                                .child(
+                                    div()
+                                        .text_color(rgb(color))
+                                        .text_size(px(13.0))
+                                        .child(action.label(confirm_delete)),
+                                )
+                        })),
+                ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support;
+    use super::*;
+
+    fn video(status: VideoStatus) -> MenuTarget {
+        MenuTarget::Video(VideoInfo {
+            status,
+            ..test_support::video("abc", None, None)
+        })
+    }
+
+    #[test]
+    fn test_menu_actions() {
+        let channel = MenuTarget::Channel("https://www.youtube.com/@handle".to_string());
+        assert_eq!(menu_actions(&channel).len(), 4);
+        assert_eq!(
+            menu_actions(&video(VideoStatus::NotDownloaded)),
+            [MenuAction::Download, MenuAction::CopyLink]
+        );
+        // Ni dossier ni suppression tant que le fichier n'est pas là
+        assert_eq!(
+            menu_actions(&video(VideoStatus::Downloading)),
+            [MenuAction::CopyLink]
+        );
+        assert!(menu_actions(&video(VideoStatus::Downloaded)).contains(&MenuAction::DeleteFile));
+    }
+}
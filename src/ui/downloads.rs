@@ -0,0 +1,444 @@
+use crate::downloader_queue::{DownloadStatus, DownloadTask, QueueMove};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use super::components::ProgressBar;
+use super::theme::Theme;
+use super::{GoBack, NDownloaderApp};
+
+/// Libellé du statut; une tâche en attente ou en cours est suspendue tant que la file est en pause
+fn status_label(status: &DownloadStatus, paused: bool) -> &'static str {
+    match status {
+        DownloadStatus::Queued | DownloadStatus::Downloading if paused => "En pause",
+        DownloadStatus::Queued => "En attente",
+        DownloadStatus::Downloading => "En cours",
+        DownloadStatus::Completed => "Terminé",
+        DownloadStatus::Failed(_) => "Échec",
+        DownloadStatus::Cancelled => "Annulé",
+    }
+}
+
+impl NDownloaderApp {
+    pub(super) fn open_downloads(&mut self, cx: &mut Context<Self>) {
+        self.show_downloads = true;
+        cx.notify();
+    }
+
+    /// Relance une tâche échouée ou annulée avec les mêmes paramètres
+    fn retry_download(&mut self, task: DownloadTask, cx: &mut Context<Self>) {
+        self.enqueue_download(
+            task.video_id,
+            task.video_url,
+            task.title,
+            task.output_path,
+            task.options,
+            cx,
+        );
+    }
+
+    fn clear_finished_downloads(&mut self, cx: &mut Context<Self>) {
+        self.download_queue.clear_finished();
+        cx.notify();
+    }
+
+    fn move_download(&mut self, video_url: &str, direction: QueueMove, cx: &mut Context<Self>) {
+        if self.download_queue.move_pending(video_url, direction) {
+            cx.notify();
+        }
+    }
+
+    fn toggle_queue_pause(&mut self, cx: &mut Context<Self>) {
+        if self.download_queue.is_paused() {
+            self.download_queue.resume();
+        } else {
+            self.download_queue.pause();
+        }
+        cx.notify();
+    }
+
+    pub(super) fn render_downloads(&mut self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = Theme::get(cx);
+        let tasks = self.download_queue.get_tasks();
+        let has_finished = tasks.iter().any(|task| task.status.is_finished());
+        let has_active = tasks.iter().any(|task| !task.status.is_finished());
+        let paused = self.download_queue.is_paused();
+        let queued = tasks
+            .iter()
+            .filter(|task| task.status == DownloadStatus::Queued)
+            .count();
+
+        div()
+            .on_action(cx.listener(Self::go_back))
+            .on_action(cx.listener(Self::handle_quit))
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(rgb(theme.background))
+            .gap_4()
+            .p_4()
+            .child(
+                // Header avec bouton retour
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_4()
+                            .child(
+                                div()
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(theme.surface))
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.border)))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _event, window, cx| {
+                                            this.go_back(&GoBack, window, cx);
+                                            cx.notify();
+                                        }),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_color(rgb(theme.text))
+                                            .text_size(px(14.0))
+                                            .child("← Retour"),
+                                    ),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .text_size(px(20.0))
+                                    .font_weight(FontWeight::BOLD)
+                                    .child(format!("Téléchargements ({})", tasks.len())),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .when(has_active || paused, |this| {
+                                this.child(
+                                    div()
+                                        .px_4()
+                                        .py_2()
+                                        .bg(rgb(if paused { theme.primary } else { theme.surface }))
+                                        .rounded_md()
+                                        .cursor_pointer()
+                                        .hover(|style| style.bg(rgb(theme.border)))
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener(|this, _event, _window, cx| {
+                                                this.toggle_queue_pause(cx);
+                                            }),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_color(rgb(if paused {
+                                                    theme.text_on_accent
+                                                } else {
+                                                    theme.text
+                                                }))
+                                                .text_size(px(14.0))
+                                                .child(if paused {
+                                                    "Reprendre"
+                                                } else {
+                                                    "Tout mettre en pause"
+                                                }),
+                                        ),
+                                )
+                            })
+                            .when(has_finished, |this| {
+                                this.child(
+                                    div()
+                                        .px_4()
+                                        .py_2()
+                                        .bg(rgb(theme.surface))
+                                        .rounded_md()
+                                        .cursor_pointer()
+                                        .hover(|style| style.bg(rgb(theme.border)))
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener(|this, _event, _window, cx| {
+                                                this.clear_finished_downloads(cx);
+                                            }),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_color(rgb(theme.text))
+                                                .text_size(px(14.0))
+                                                .child("Effacer les terminés"),
+                                        ),
+                                )
+                            }),
+                    ),
+            )
+            .when(paused, |this| {
+                this.child(
+                    div()
+                        .px_4()
+                        .py_2()
+                        .bg(rgb(theme.element))
+                        .border_1()
+                        .border_color(rgb(theme.warning))
+                        .rounded_md()
+                        .text_color(rgb(theme.warning))
+                        .text_size(px(14.0))
+                        .child(
+                            "Téléchargements en pause: les tâches en cours sont suspendues \
+                             et aucune nouvelle ne démarre",
+                        ),
+                )
+            })
+            .child(
+                div()
+                    .id("downloads-list")
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .gap_2()
+                    .p_4()
+                    .bg(rgb(theme.surface))
+                    .rounded_md()
+                    .overflow_y_scroll()
+                    .when(tasks.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_color(rgb(theme.border))
+                                .text_size(px(14.0))
+                                .child("Aucun téléchargement"),
+                        )
+                    })
+                    .children(
+                        tasks
+                            .into_iter()
+                            .scan(0, |queue_index, task| {
+                                // Position dans la file d'attente des tâches pas encore démarrées
+                                let position = (task.status == DownloadStatus::Queued).then(|| {
+                                    *queue_index += 1;
+                                    *queue_index - 1
+                                });
+                                Some((task, position))
+                            })
+                            .map(|(task, position)| {
+                                self.render_download_row(task, paused, position, queued, cx)
+                            }),
+                    ),
+            )
+            .into_any_element()
+    }
+
+    fn render_download_row(
+        &self,
+        task: DownloadTask,
+        paused: bool,
+        queue_position: Option<usize>,
+        queued: usize,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        let status_color = match task.status {
+            DownloadStatus::Queued | DownloadStatus::Downloading if paused => theme.warning,
+            DownloadStatus::Queued => theme.text_muted,
+            DownloadStatus::Downloading => theme.info,
+            DownloadStatus::Completed => theme.success,
+            DownloadStatus::Failed(_) => theme.error,
+            DownloadStatus::Cancelled => theme.warning,
+        };
+        let details: Vec<String> = [
+            task.speed.clone(),
+            task.eta.clone().map(|eta| format!("ETA {eta}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let is_active = !task.status.is_finished();
+        let can_retry = matches!(
+            task.status,
+            DownloadStatus::Failed(_) | DownloadStatus::Cancelled
+        );
+        let error = match &task.status {
+            DownloadStatus::Failed(error) => Some(error.clone()),
+            _ => None,
+        };
+        let video_url = task.video_url.clone();
+
+        div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .p_3()
+            .bg(rgb(theme.element))
+            .rounded_md()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_color(rgb(theme.text))
+                            .text_size(px(14.0))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(task.title.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_3()
+                            .text_size(px(12.0))
+                            .child(
+                                div()
+                                    .text_color(rgb(status_color))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .child(status_label(&task.status, paused)),
+                            )
+                            .when(task.status == DownloadStatus::Downloading, |this| {
+                                this.child(
+                                    div()
+                                        .text_color(rgb(theme.text_muted))
+                                        .child(format!("{:.0}%", task.progress * 100.0)),
+                                )
+                                .when(
+                                    !details.is_empty(),
+                                    |this| {
+                                        this.child(
+                                            div()
+                                                .text_color(rgb(theme.text_muted))
+                                                .child(details.join(" · ")),
+                                        )
+                                    },
+                                )
+                            }),
+                    )
+                    .when_some(error, |this, error| {
+                        this.child(
+                            div()
+                                .text_color(rgb(theme.error))
+                                .text_size(px(12.0))
+                                .child(error),
+                        )
+                    })
+                    .when(task.status == DownloadStatus::Downloading, |this| {
+                        this.child(div().w_full().mt_1().child(ProgressBar::new(task.progress)))
+                    }),
+            )
+            .when_some(queue_position, |this, position| {
+                this.child(
+                    div()
+                        .flex()
+                        .gap_1()
+                        .child(self.render_move_button(
+                            "⤒",
+                            &task.video_url,
+                            QueueMove::First,
+                            position > 0,
+                            cx,
+                        ))
+                        .child(self.render_move_button(
+                            "↑",
+                            &task.video_url,
+                            QueueMove::Up,
+                            position > 0,
+                            cx,
+                        ))
+                        .child(self.render_move_button(
+                            "↓",
+                            &task.video_url,
+                            QueueMove::Down,
+                            position + 1 < queued,
+                            cx,
+                        )),
+                )
+            })
+            .when(is_active, |this| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_1()
+                        .bg(rgb(theme.surface))
+                        .rounded_md()
+                        .cursor_pointer()
+                        .hover(|style| style.bg(rgb(theme.border)))
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(move |this, _event, _window, cx| {
+                                this.download_queue.cancel(&video_url);
+                                cx.notify();
+                            }),
+                        )
+                        .child(
+                            div()
+                                .text_color(rgb(theme.text))
+                                .text_size(px(12.0))
+                                .child("Annuler"),
+                        ),
+                )
+            })
+            .when(can_retry, |this| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_1()
+                        .bg(rgb(theme.primary))
+                        .rounded_md()
+                        .cursor_pointer()
+                        .hover(|style| style.bg(rgb(theme.info)))
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(move |this, _event, _window, cx| {
+                                this.retry_download(task.clone(), cx);
+                            }),
+                        )
+                        .child(
+                            div()
+                                .text_color(rgb(theme.text_on_accent))
+                                .text_size(px(12.0))
+                                .child("Réessayer"),
+                        ),
+                )
+            })
+    }
+
+    /// Bouton de déplacement d'une tâche en attente, grisé s'il est sans effet
+    fn render_move_button(
+        &self,
+        label: &'static str,
+        video_url: &str,
+        direction: QueueMove,
+        enabled: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        let video_url = video_url.to_string();
+
+        div()
+            .px_2()
+            .py_1()
+            .bg(rgb(theme.surface))
+            .rounded_md()
+            .text_size(px(12.0))
+            .map(|this| {
+                if enabled {
+                    this.text_color(rgb(theme.text))
+                        .cursor_pointer()
+                        .hover(|style| style.bg(rgb(theme.border)))
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(move |this, _event, _window, cx| {
+                                this.move_download(&video_url, direction, cx);
+                            }),
+                        )
+                } else {
+                    this.text_color(rgb(theme.border))
+                }
+            })
+            .child(label)
+    }
+}
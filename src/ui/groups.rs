@@ -0,0 +1,434 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use super::text_input::TextInputView;
+use super::theme::Theme;
+use super::{save_channels, Channel, DraggedChannel, NDownloaderApp};
+
+/// Titre de la section des chaînes sans groupe
+const UNGROUPED_LABEL: &str = "Sans groupe";
+
+/// Modification de groupe en cours de saisie
+pub enum GroupEdit {
+    /// Choix du groupe d'une chaîne, désignée par son URL: la liste peut être réordonnée
+    /// pendant la saisie
+    Assign(String),
+    /// Nouveau nom d'un groupe existant
+    Rename(String),
+}
+
+pub struct GroupEditor {
+    pub edit: GroupEdit,
+    pub input: Entity<TextInputView>,
+}
+
+/// Nom de groupe saisi, `None` pour retirer la chaîne de son groupe
+fn normalize_group_name(text: &str) -> Option<String> {
+    let name = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!name.is_empty()).then_some(name)
+}
+
+/// Regroupe les chaînes par groupe, dans l'ordre alphabétique des groupes.
+/// Les chaînes sans groupe forment la dernière section; l'ordre de la liste est conservé
+/// à l'intérieur de chaque section.
+fn group_sections(channels: Vec<(usize, Channel)>) -> Vec<(Option<String>, Vec<(usize, Channel)>)> {
+    let mut sections: Vec<(Option<String>, Vec<(usize, Channel)>)> = Vec::new();
+    for (index, channel) in channels {
+        match sections
+            .iter_mut()
+            .find(|(group, _)| *group == channel.group)
+        {
+            Some((_, members)) => members.push((index, channel)),
+            None => sections.push((channel.group.clone(), vec![(index, channel)])),
+        }
+    }
+
+    sections.sort_by_key(|(group, _)| (group.is_none(), group.as_deref().map(str::to_lowercase)));
+    sections
+}
+
+/// Petit bouton des en-têtes de groupe et de la saisie
+fn group_button(
+    label: &'static str,
+    background: u32,
+    text: u32,
+    on_click: impl Fn(&MouseDownEvent, &mut Window, &mut App) + 'static,
+) -> impl IntoElement {
+    div()
+        .px_2()
+        .py_1()
+        .bg(rgb(background))
+        .rounded_sm()
+        .cursor_pointer()
+        .hover(|style| style.opacity(0.8))
+        .on_mouse_down(MouseButton::Left, on_click)
+        .child(div().text_color(rgb(text)).text_size(px(12.0)).child(label))
+}
+
+impl NDownloaderApp {
+    /// Ouvre la saisie du groupe d'une chaîne
+    pub(super) fn start_assign_group(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(channel) = self.channels.get(index) else {
+            return;
+        };
+        let current = channel.group.clone().unwrap_or_default();
+        let edit = GroupEdit::Assign(channel.url.clone());
+        self.open_group_editor(edit, current, cx);
+    }
+
+    fn start_rename_group(&mut self, group: String, cx: &mut Context<Self>) {
+        self.open_group_editor(GroupEdit::Rename(group.clone()), group, cx);
+    }
+
+    fn open_group_editor(&mut self, edit: GroupEdit, value: String, cx: &mut Context<Self>) {
+        let input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .placeholder("Nom du groupe (vide = sans groupe)...")
+                .default_value(value)
+        });
+        self.group_editor = Some(GroupEditor { edit, input });
+        self.pending_group_delete = None;
+        cx.notify();
+    }
+
+    fn cancel_group_edit(&mut self, cx: &mut Context<Self>) {
+        self.group_editor = None;
+        cx.notify();
+    }
+
+    fn confirm_group_edit(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = self.group_editor.take() else {
+            return;
+        };
+        let name = normalize_group_name(&editor.input.read(cx).value());
+
+        match editor.edit {
+            GroupEdit::Assign(url) => {
+                if let Some(index) = self.channels.iter().position(|c| c.url == url) {
+                    self.set_channel_group(index, name, cx);
+                }
+            }
+            GroupEdit::Rename(old) => {
+                // Un nom vide équivaut à supprimer le groupe
+                for channel in self.channels.iter_mut() {
+                    if channel.group.as_deref() == Some(old.as_str()) {
+                        channel.group = name.clone();
+                    }
+                }
+                if self.collapsed_groups.remove(&Some(old)) {
+                    self.collapsed_groups.insert(name);
+                }
+                save_channels(&self.channels);
+            }
+        }
+        cx.notify();
+    }
+
+    pub(super) fn set_channel_group(
+        &mut self,
+        index: usize,
+        group: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(channel) = self.channels.get_mut(index) else {
+            return;
+        };
+        if channel.group == group {
+            return;
+        }
+        channel.group = group;
+        save_channels(&self.channels);
+        cx.notify();
+    }
+
+    fn toggle_group(&mut self, group: Option<String>, cx: &mut Context<Self>) {
+        if !self.collapsed_groups.remove(&group) {
+            self.collapsed_groups.insert(group);
+        }
+        cx.notify();
+    }
+
+    /// Supprime un groupe: ses chaînes sont conservées, sans groupe
+    fn delete_group(&mut self, group: String, cx: &mut Context<Self>) {
+        for channel in self.channels.iter_mut() {
+            if channel.group.as_deref() == Some(group.as_str()) {
+                channel.group = None;
+            }
+        }
+        self.collapsed_groups.remove(&Some(group));
+        self.pending_group_delete = None;
+        save_channels(&self.channels);
+        cx.notify();
+    }
+
+    /// Lignes de la liste des chaînes, sous des en-têtes repliables dès qu'un groupe existe
+    pub(super) fn render_channel_sections(
+        &self,
+        channels: Vec<(usize, Channel)>,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        if channels.iter().all(|(_, channel)| channel.group.is_none()) {
+            return channels
+                .into_iter()
+                .map(|(index, channel)| {
+                    self.render_channel_row(index, channel, cx)
+                        .into_any_element()
+                })
+                .collect();
+        }
+
+        let mut elements = Vec::new();
+        for (group, members) in group_sections(channels) {
+            let collapsed = self.collapsed_groups.contains(&group);
+            elements.push(
+                self.render_group_header(group, members.len(), collapsed, cx)
+                    .into_any_element(),
+            );
+            if !collapsed {
+                elements.extend(members.into_iter().map(|(index, channel)| {
+                    self.render_channel_row(index, channel, cx)
+                        .into_any_element()
+                }));
+            }
+        }
+        elements
+    }
+
+    /// En-tête d'un groupe: clic pour replier, dépôt d'une chaîne pour l'y ajouter
+    fn render_group_header(
+        &self,
+        group: Option<String>,
+        count: usize,
+        collapsed: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        let label = group.clone().unwrap_or_else(|| UNGROUPED_LABEL.to_string());
+        let id = format!("group-{label}");
+        let confirm_delete = group.is_some() && self.pending_group_delete == group;
+        let toggle_group = group.clone();
+        let drop_group = group.clone();
+
+        div()
+            .id(SharedString::from(id))
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .mt_2()
+            .rounded_sm()
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(theme.element)))
+            .on_click(cx.listener(move |this, _event: &ClickEvent, _window, cx| {
+                this.toggle_group(toggle_group.clone(), cx);
+            }))
+            .drag_over::<DraggedChannel>(move |style, _dragged, _window, _cx| {
+                style
+                    .bg(rgb(theme.element))
+                    .border_1()
+                    .border_color(rgb(theme.accent))
+            })
+            .on_drop(
+                cx.listener(move |this, dragged: &DraggedChannel, _window, cx| {
+                    this.set_channel_group(dragged.index, drop_group.clone(), cx);
+                }),
+            )
+            .child(
+                div()
+                    .text_color(rgb(theme.text_muted))
+                    .text_size(px(12.0))
+                    .child(if collapsed { "▸" } else { "▾" }),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .text_color(rgb(theme.text))
+                    .text_size(px(13.0))
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child(format!("{label} ({count})")),
+            )
+            .when_some(group.filter(|_| !confirm_delete), |this, group| {
+                let rename_group = group.clone();
+                this.child(group_button(
+                    "Renommer",
+                    theme.surface,
+                    theme.text,
+                    cx.listener(move |this, _event, _window, cx| {
+                        this.start_rename_group(rename_group.clone(), cx);
+                        cx.stop_propagation();
+                    }),
+                ))
+                .child(group_button(
+                    "✕",
+                    theme.surface,
+                    theme.text,
+                    cx.listener(move |this, _event, _window, cx| {
+                        this.pending_group_delete = Some(group.clone());
+                        cx.notify();
+                        cx.stop_propagation();
+                    }),
+                ))
+            })
+            .when(confirm_delete, |this| {
+                let group = self.pending_group_delete.clone().unwrap_or_default();
+                this.child(
+                    div()
+                        .text_color(rgb(theme.text_muted))
+                        .text_size(px(12.0))
+                        .child(format!(
+                            "Supprimer le groupe ? Ses chaînes iront dans « {UNGROUPED_LABEL} »"
+                        )),
+                )
+                .child(group_button(
+                    "Supprimer",
+                    theme.error,
+                    theme.text_on_accent,
+                    cx.listener(move |this, _event, _window, cx| {
+                        this.delete_group(group.clone(), cx);
+                        cx.stop_propagation();
+                    }),
+                ))
+                .child(group_button(
+                    "Annuler",
+                    theme.surface,
+                    theme.text,
+                    cx.listener(|this, _event, _window, cx| {
+                        this.pending_group_delete = None;
+                        cx.notify();
+                        cx.stop_propagation();
+                    }),
+                ))
+            })
+    }
+
+    /// Saisie du groupe d'une chaîne ou du nouveau nom d'un groupe
+    pub(super) fn render_group_editor(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let editor = self.group_editor.as_ref()?;
+        let theme = Theme::get(cx);
+        let title = match &editor.edit {
+            GroupEdit::Assign(url) => {
+                let name = self
+                    .channels
+                    .iter()
+                    .find(|c| c.url == *url)
+                    .map(|c| c.name.as_str())
+                    .unwrap_or_default();
+                format!("Groupe de « {name} »")
+            }
+            GroupEdit::Rename(group) => format!("Renommer le groupe « {group} »"),
+        };
+
+        Some(
+            div()
+                .flex()
+                .flex_col()
+                .gap_2()
+                .p_3()
+                .bg(rgb(theme.element))
+                .rounded_md()
+                .child(
+                    div()
+                        .text_color(rgb(theme.text))
+                        .text_size(px(13.0))
+                        .child(title),
+                )
+                .child(
+                    div()
+                        .flex()
+                        .gap_2()
+                        .child(
+                            div()
+                                .flex_1()
+                                .h_8()
+                                .px_3()
+                                .bg(rgb(theme.background))
+                                .border_1()
+                                .border_color(rgb(theme.border))
+                                .rounded_md()
+                                .on_key_down(cx.listener(
+                                    |this, event: &KeyDownEvent, _window, cx| {
+                                        if event.keystroke.key == "enter" {
+                                            this.confirm_group_edit(cx);
+                                        } else if event.keystroke.key == "escape" {
+                                            this.cancel_group_edit(cx);
+                                        }
+                                    },
+                                ))
+                                .child(editor.input.clone()),
+                        )
+                        .child(group_button(
+                            "Valider",
+                            theme.accent,
+                            theme.text_on_accent,
+                            cx.listener(|this, _event, _window, cx| {
+                                this.confirm_group_edit(cx);
+                            }),
+                        ))
+                        .child(group_button(
+                            "Annuler",
+                            theme.surface,
+                            theme.text,
+                            cx.listener(|this, _event, _window, cx| {
+                                this.cancel_group_edit(cx);
+                            }),
+                        )),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Platform;
+    use super::*;
+
+    fn channel(name: &str, group: Option<&str>) -> Channel {
+        Channel {
+            group: group.map(str::to_string),
+            ..Channel::new(
+                name.to_string(),
+                Platform::YouTube,
+                format!("https://www.youtube.com/@{name}"),
+            )
+        }
+    }
+
+    #[test]
+    fn test_normalize_group_name() {
+        assert_eq!(
+            normalize_group_name("  Jeux   vidéo "),
+            Some("Jeux vidéo".to_string())
+        );
+        assert_eq!(normalize_group_name("   "), None);
+    }
+
+    #[test]
+    fn test_group_sections() {
+        let channels = vec![
+            channel("a", None),
+            channel("b", Some("musique")),
+            channel("c", Some("Jeux")),
+            channel("d", Some("musique")),
+        ];
+        let sections = group_sections(channels.into_iter().enumerate().collect());
+
+        let summary: Vec<(Option<&str>, Vec<usize>)> = sections
+            .iter()
+            .map(|(group, members)| {
+                (
+                    group.as_deref(),
+                    members.iter().map(|(index, _)| *index).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Some("Jeux"), vec![2]),
+                (Some("musique"), vec![1, 3]),
+                (None, vec![0]),
+            ]
+        );
+    }
+}
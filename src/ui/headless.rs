@@ -0,0 +1,61 @@
+use crate::config::Config;
+use crate::filename;
+use crate::scanner::VideoScanner;
+use anyhow::{anyhow, bail, Result};
+
+use super::{find_duplicate, load_channels, save_channels, Channel, Platform};
+
+/// Ajoute une chaîne à la liste sans ouvrir la fenêtre (`--add`), comme le champ d'ajout:
+/// un lien de vidéo est remplacé par sa chaîne et une playlist prend son titre,
+/// tous deux demandés à yt-dlp. Retourne le nom de la chaîne ajoutée.
+pub fn add_channel(url: &str) -> Result<String> {
+    let url = url.trim();
+    if Platform::from_url(url).is_none() {
+        bail!("Seules les chaînes YouTube et Twitch sont reconnues");
+    }
+    let scanner = VideoScanner::from_config(&Config::load());
+
+    let url = if Platform::extract_video_id(url).is_some() {
+        smol::block_on(scanner.video_channel_url(url))?
+            .ok_or_else(|| anyhow!("yt-dlp n'indique pas la chaîne de cette vidéo"))?
+    } else {
+        url.to_string()
+    };
+    let Some(platform) = Platform::from_url(&url) else {
+        bail!("URL de chaîne non reconnue: {url}");
+    };
+
+    let mut channels = load_channels();
+    let channel = if let Some(playlist_id) = Platform::extract_playlist_id(&url) {
+        if let Some(existing) = channels
+            .iter()
+            .find(|c| c.is_playlist && c.url.contains(&playlist_id))
+        {
+            bail!("{} est déjà dans la liste", existing.name);
+        }
+        let title = smol::block_on(scanner.playlist_title(&url))
+            .unwrap_or_else(|error| {
+                tracing::warn!("Failed to fetch playlist title for {}: {}", url, error);
+                None
+            })
+            .map(|title| filename::sanitize(&title))
+            .filter(|title| !title.is_empty());
+        Channel {
+            is_playlist: true,
+            ..Channel::new(title.unwrap_or(playlist_id), platform, url)
+        }
+    } else {
+        let Some(name) = Platform::extract_channel_name(&url) else {
+            bail!("Collez l'URL d'une chaîne (youtube.com/@nom, twitch.tv/nom), d'une playlist ou d'une vidéo");
+        };
+        if let Some(existing) = find_duplicate(&channels, &url, &platform, &name) {
+            bail!("{} est déjà dans la liste", existing.name);
+        }
+        Channel::new(name, platform, url)
+    };
+
+    let name = channel.name.clone();
+    channels.push(channel);
+    save_channels(&channels);
+    Ok(name)
+}
@@ -0,0 +1,312 @@
+use crate::database::{Database, Video};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use std::path::PathBuf;
+
+use super::theme::Theme;
+use super::{open_in_file_manager, GoBack, NDownloaderApp};
+
+/// Nombre maximum de résultats affichés
+const MAX_RESULTS: usize = 200;
+
+/// Date de téléchargement: "14/03/2025 18:42"
+fn format_downloaded_at(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|date| {
+            date.with_timezone(&chrono::Local)
+                .format("%d/%m/%Y %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// Vidéo de l'historique, avec l'état de son fichier relevé à la recherche
+pub(super) struct HistoryRow {
+    video: Video,
+    /// Fichier toujours là (sinon supprimé, ou disque démonté depuis le téléchargement)
+    exists: bool,
+    /// Dossier de la vidéo, s'il existe encore
+    folder: Option<PathBuf>,
+}
+
+/// Recherche dans l'historique et vérifie les fichiers trouvés. Bloquant: les disques
+/// lents ou réseau ne doivent pas figer l'interface.
+fn search_history(database: Option<&Database>, query: &str) -> Result<Vec<HistoryRow>, String> {
+    let Some(database) = database else {
+        return Err("L'historique des téléchargements n'a pas pu être ouvert".to_string());
+    };
+    let videos = database.search(query, MAX_RESULTS).map_err(|error| {
+        tracing::warn!("Download history search failed: {}", error);
+        error.to_string()
+    })?;
+    Ok(videos
+        .into_iter()
+        .map(|video| HistoryRow {
+            exists: video.path.exists(),
+            folder: video
+                .path
+                .parent()
+                .filter(|parent| parent.is_dir())
+                .map(|parent| parent.to_path_buf()),
+            video,
+        })
+        .collect())
+}
+
+impl NDownloaderApp {
+    pub(super) fn open_history(&mut self, cx: &mut Context<Self>) {
+        self.show_history = true;
+        self.refresh_history(cx);
+        cx.notify();
+    }
+
+    /// Relance la recherche dans l'historique hors du thread de l'interface. Les résultats
+    /// affichés restent en place jusqu'à l'arrivée des nouveaux.
+    pub(super) fn refresh_history(&mut self, cx: &mut Context<Self>) {
+        let database = self.database.clone();
+        let query = self.history_query.clone();
+        self.history_search = Some(cx.spawn(async move |this, cx| {
+            let results = cx
+                .background_executor()
+                .spawn(async move { search_history(database.as_deref(), &query) })
+                .await;
+            this.update(cx, |this, cx| {
+                this.history_results = Some(results);
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Recherche dans toutes les vidéos téléchargées, toutes chaînes confondues
+    pub(super) fn render_history(&mut self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = Theme::get(cx);
+        let empty_message = if self.history_query.trim().is_empty() {
+            "Aucune vidéo téléchargée"
+        } else {
+            "Aucune vidéo téléchargée ne correspond à la recherche"
+        };
+
+        div()
+            .on_action(cx.listener(Self::go_back))
+            .on_action(cx.listener(Self::handle_quit))
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(rgb(theme.background))
+            .gap_4()
+            .p_4()
+            .child(
+                // Header avec bouton retour
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .child(
+                        div()
+                            .px_4()
+                            .py_2()
+                            .bg(rgb(theme.surface))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.border)))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(|this, _event, window, cx| {
+                                    this.go_back(&GoBack, window, cx);
+                                    cx.notify();
+                                }),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .text_size(px(14.0))
+                                    .child("← Retour"),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(theme.text))
+                            .text_size(px(20.0))
+                            .font_weight(FontWeight::BOLD)
+                            .child("Vidéos téléchargées"),
+                    ),
+            )
+            .child(
+                div()
+                    .h_10()
+                    .px_3()
+                    .bg(rgb(theme.element))
+                    .border_1()
+                    .border_color(rgb(theme.border))
+                    .rounded_md()
+                    .child(self.history_filter.clone()),
+            )
+            .child(
+                div()
+                    .id("history-list")
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .gap_2()
+                    .p_4()
+                    .bg(rgb(theme.surface))
+                    .rounded_md()
+                    .overflow_y_scroll()
+                    .map(|this| match &self.history_results {
+                        None => this.child(
+                            div()
+                                .text_color(rgb(theme.border))
+                                .text_size(px(14.0))
+                                .child("Recherche..."),
+                        ),
+                        Some(Err(error)) => this.child(
+                            div()
+                                .text_color(rgb(theme.error))
+                                .text_size(px(14.0))
+                                .child(error.clone()),
+                        ),
+                        Some(Ok(rows)) if rows.is_empty() => this.child(
+                            div()
+                                .text_color(rgb(theme.border))
+                                .text_size(px(14.0))
+                                .child(empty_message),
+                        ),
+                        Some(Ok(rows)) => {
+                            this.children(rows.iter().map(|row| self.render_history_row(row, cx)))
+                        }
+                    }),
+            )
+            .into_any_element()
+    }
+
+    fn render_history_row(&self, row: &HistoryRow, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        let HistoryRow {
+            video,
+            exists,
+            folder,
+        } = row;
+        let exists = *exists;
+
+        div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .p_3()
+            .bg(rgb(theme.element))
+            .rounded_md()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_color(rgb(theme.text))
+                            .text_size(px(14.0))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(video.title.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(theme.text_muted))
+                            .text_size(px(12.0))
+                            .child(format!(
+                                "{} · {}",
+                                video.channel,
+                                format_downloaded_at(video.downloaded_at)
+                            )),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(if exists {
+                                theme.text_muted
+                            } else {
+                                theme.warning
+                            }))
+                            .text_size(px(12.0))
+                            .child(if exists {
+                                video.path.display().to_string()
+                            } else {
+                                format!("Fichier introuvable: {}", video.path.display())
+                            }),
+                    ),
+            )
+            .when_some(folder.clone(), |this, folder| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .px_3()
+                        .py_1()
+                        .bg(rgb(theme.surface))
+                        .rounded_md()
+                        .cursor_pointer()
+                        .hover(|style| style.bg(rgb(theme.border)))
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(move |_this, _event, _window, _cx| {
+                                open_in_file_manager(&folder);
+                            }),
+                        )
+                        .child(
+                            div()
+                                .text_color(rgb(theme.text))
+                                .text_size(px(13.0))
+                                .child("Ouvrir le dossier"),
+                        ),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_downloaded_at() {
+        let timestamp = chrono::NaiveDate::from_ymd_opt(2025, 3, 14)
+            .unwrap()
+            .and_hms_opt(18, 42, 0)
+            .unwrap()
+            .and_local_timezone(chrono::Local)
+            .single()
+            .unwrap()
+            .timestamp();
+        assert_eq!(format_downloaded_at(timestamp), "14/03/2025 18:42");
+    }
+
+    #[test]
+    fn test_search_history_checks_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = Database::open(&dir.path().join("downloads.db")).unwrap();
+        std::fs::write(dir.path().join("kept.mp4"), "").unwrap();
+        for (id, path) in [
+            ("kept", dir.path().join("kept.mp4")),
+            ("gone", dir.path().join("unmounted/gone.mp4")),
+        ] {
+            database
+                .record(&Video {
+                    id: id.to_string(),
+                    channel: "chan".to_string(),
+                    title: id.to_string(),
+                    path,
+                    duration: None,
+                    downloaded_at: 0,
+                })
+                .unwrap();
+        }
+
+        let rows = search_history(Some(&database), "").unwrap();
+        let states: Vec<_> = rows
+            .iter()
+            .map(|row| (row.video.id.as_str(), row.exists, row.folder.is_some()))
+            .collect();
+        assert_eq!(states.len(), 2);
+        assert!(states.contains(&("kept", true, true)));
+        assert!(states.contains(&("gone", false, false)));
+        assert!(search_history(None, "").is_err());
+    }
+}
@@ -1,54 +1,171 @@
-use crate::downloader_queue::DownloadQueue;
-use crate::notifications::Notification;
-use crate::scanner::VideoScanner;
+use crate::config::Config;
+use crate::database::{self, Database};
+use crate::downloader_queue::{
+    DownloadOptions, DownloadQuality, DownloadQueue, DownloadStatus, VideoContainer,
+};
+use crate::filename;
+use crate::monitor::ChannelMonitor;
+use crate::notifications::{self, Notification, NotificationEvent, NotificationType};
+use crate::paths;
+use crate::scanner::{is_yt_dlp_outdated, ChannelScan, MissingBinary, VideoMetadata, VideoScanner};
+use crate::thumbnails::ThumbnailCache;
+use futures::StreamExt;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 pub mod actions;
+mod channel_download;
+mod channel_io;
 mod components;
+mod config_reload;
+mod context_menu;
+mod downloads;
+mod groups;
+mod headless;
+mod history;
+mod monitoring;
+mod navigation;
+mod scan_warnings;
+mod settings;
+mod status_check;
+mod storage;
 mod text_input;
+mod theme;
+mod toasts;
+mod window_state;
 
 pub use actions::*;
-use components::{ChannelItem, VideoItem};
+use channel_download::ChannelBatch;
+use components::{ChannelItem, Spinner, VideoItem};
+use context_menu::{ContextMenu, MenuTarget};
+use groups::GroupEditor;
+pub use headless::add_channel;
+use history::HistoryRow;
+use navigation::ForwardStack;
+use settings::SettingsForm;
 use text_input::TextInputView;
+use theme::Theme;
+use toasts::{Toast, ToastAction};
+pub use window_state::restored_window_bounds;
+use window_state::WindowState;
 
-// Palette Nord
-const NORD0: u32 = 0x2e3440; // Polar Night - darkest
-const NORD1: u32 = 0x3b4252; // Polar Night
-const NORD2: u32 = 0x434c5e; // Polar Night
-const NORD3: u32 = 0x4c566a; // Polar Night - lightest
-const NORD4: u32 = 0xd8dee9; // Snow Storm - darkest
-const NORD6: u32 = 0xeceff4; // Snow Storm - lightest
-const NORD8: u32 = 0x88c0d0; // Frost - bright cyan
-const NORD9: u32 = 0x81a1c1; // Frost - blue
-const NORD10: u32 = 0x5e81ac; // Frost - dark blue
-const NORD11: u32 = 0xbf616a; // Aurora - red
-const NORD13: u32 = 0xebcb8b; // Aurora - yellow
-const NORD14: u32 = 0xa3be8c; // Aurora - green
-const NORD15: u32 = 0xb48ead; // Aurora - purple
+/// Titre de la fenêtre sans chaîne affichée ni téléchargement
+const APP_NAME: &str = "NDownloader";
+
+/// Pause dans la frappe après laquelle les recherches sont appliquées aux listes
+const SEARCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(150);
+
+/// Nombre maximum de miniatures téléchargées en parallèle
+const MAX_CONCURRENT_THUMBNAILS: usize = 4;
 
 pub struct NDownloaderApp {
+    config: Config,
     url_input: Entity<TextInputView>,
+    channel_filter: Entity<TextInputView>,
+    video_filter: Entity<TextInputView>,
     channels: Vec<Channel>,
     selected_channel: Option<usize>,
     videos: Vec<VideoInfo>,
+    video_sort: VideoSort,
+    status_filter: StatusFilter,
     scanner: Arc<VideoScanner>,
+    /// Historique des téléchargements, absent si la base n'a pas pu être ouverte
+    database: Option<Arc<Database>>,
     download_queue: Arc<DownloadQueue>,
     loading: bool,
+    next_page: usize,
+    has_more_videos: bool,
+    loading_more: bool,
+    /// Scan en cours de la chaîne affichée; le remplacer ou le vider l'annule
+    scan_task: Option<Task<()>>,
+    /// Vérification en cours du statut des vidéos affichées, sans nouveau scan
+    status_check: Option<Task<()>>,
+    /// Nombre de vidéos attendues par le scan en cours, s'il est connu
+    scan_expected: Option<usize>,
+    /// Erreur du dernier scan de la chaîne affichée
+    scan_error: Option<String>,
+    /// Avertissements de yt-dlp au dernier scan réussi de la chaîne affichée
+    scan_warnings: Vec<String>,
+    /// Liste des avertissements dépliée
+    show_scan_warnings: bool,
     download_input: Option<Entity<TextInputView>>,
     download_video: Option<DownloadingVideo>,
     downloading_videos: std::collections::HashSet<String>, // URLs des vidéos en cours de téléchargement
+    selected_videos: std::collections::HashSet<String>, // URLs cochées pour un téléchargement groupé
+    error_banner: Option<String>,
+    settings: Option<SettingsForm>,
+    thumbnail_cache: Arc<ThumbnailCache>,
+    thumbnails: HashMap<String, Thumbnail>, // Par identifiant de vidéo
+    avatars: HashMap<String, Option<PathBuf>>, // Par URL de chaîne, `None` tant qu'absent
+    toasts: Vec<Toast>,
+    next_toast_id: usize,
+    /// Dernière chaîne supprimée, restaurable tant que sa notification est affichée
+    deleted_channel: Option<DeletedChannel>,
+    show_downloads: bool,
+    show_history: bool,
+    /// Recherche dans l'historique des téléchargements
+    history_filter: Entity<TextInputView>,
+    /// Recherches appliquées aux listes, recopiées des champs après une pause dans la frappe
+    channel_query: String,
+    video_query: String,
+    history_query: String,
+    /// Dernière recherche dans l'historique, `None` avant la première
+    history_results: Option<Result<Vec<HistoryRow>, String>>,
+    history_search: Option<Task<()>>,
+    search_debounce: Option<Task<()>>,
+    /// Menu ouvert par un clic droit sur une chaîne ou une vidéo
+    context_menu: Option<ContextMenu>,
+    monitor: ChannelMonitor,
+    monitor_task: Option<Task<()>>,
+    /// Groupes repliés dans la liste des chaînes (`None` = sans groupe)
+    collapsed_groups: HashSet<Option<String>>,
+    group_editor: Option<GroupEditor>,
+    /// Groupe dont la suppression attend une confirmation
+    pending_group_delete: Option<String>,
+    /// « Tout télécharger » attend une confirmation
+    pending_download_all: bool,
+    /// Lots « Tout télécharger » en cours, par URL de chaîne
+    channel_batches: HashMap<String, ChannelBatch>,
+    /// Chaînes quittées avec « Retour », pour « Suivant »
+    forward_channels: ForwardStack,
+    /// Dernier titre donné à la fenêtre, pour ne le changer que s'il diffère
+    window_title: String,
+    /// Position courante de la fenêtre, enregistrée en quittant
+    window_state: WindowState,
+    /// Surveillance du fichier de configuration (arrêtée quand elle est libérée)
+    config_watcher: Option<notify::RecommendedWatcher>,
+    /// Dossiers de stockage relus du fichier, appliqués à la fin des téléchargements en cours
+    deferred_storage_paths: Option<Vec<String>>,
+    /// Disques de stockage absents à la dernière vérification
+    unavailable_storage: HashSet<String>,
 }
 
 #[derive(Clone)]
 struct DownloadingVideo {
+    id: String,
     url: String,
     channel_name: String,
+    started: bool,
+    quality: DownloadQuality,
+    container: VideoContainer,
+    subtitles: bool,
     progress: f32, // 0.0 to 1.0
     speed: Option<String>,
     eta: Option<String>,
+    /// Nom de fichier refusé, affiché sous le champ
+    filename_error: Option<String>,
+}
+
+/// Chaîne supprimée et sa position, pour annuler la suppression
+struct DeletedChannel {
+    channel: Channel,
+    index: usize,
+    /// Notification proposant l'annulation
+    toast_id: usize,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -56,15 +173,250 @@ struct Channel {
     name: String,
     platform: Platform,
     url: String,
+    /// Télécharger automatiquement les nouvelles vidéos détectées
+    #[serde(default)]
+    auto_download: bool,
+    /// Date d'ajout (YYYYMMDD) : seules les vidéos publiées depuis sont téléchargées automatiquement
+    #[serde(default)]
+    added_on: Option<String>,
+    /// Playlist YouTube suivie comme une chaîne (nom = titre de la playlist)
+    #[serde(default)]
+    is_playlist: bool,
+    /// Groupe sous lequel la chaîne est rangée dans la liste
+    #[serde(default)]
+    group: Option<String>,
+    /// Identifiant de la chaîne chez la plateforme, relevé lors d'un scan
+    #[serde(default)]
+    platform_id: Option<String>,
+    /// Date du dernier scan réussi (secondes Unix)
+    #[serde(default)]
+    last_scanned: Option<i64>,
+    /// Vidéos déjà téléchargées parmi celles listées au dernier scan
+    #[serde(default)]
+    download_summary: Option<DownloadSummary>,
+}
+
+/// Avancement d'une chaîne: "12/30" vidéos téléchargées
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct DownloadSummary {
+    downloaded: usize,
+    total: usize,
+}
+
+impl DownloadSummary {
+    fn new(videos: &[VideoMetadata], downloaded: &HashSet<String>) -> Self {
+        Self {
+            downloaded: videos
+                .iter()
+                .filter(|video| downloaded.contains(&video.url))
+                .count(),
+            total: videos.len(),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.downloaded >= self.total
+    }
+
+    fn label(&self) -> String {
+        format!("{}/{}", self.downloaded, self.total)
+    }
+}
+
+impl Channel {
+    /// Chaîne ajoutée aujourd'hui, sans groupe ni téléchargement automatique
+    fn new(name: String, platform: Platform, url: String) -> Self {
+        Self {
+            name,
+            platform,
+            url,
+            auto_download: false,
+            added_on: Some(today()),
+            is_playlist: false,
+            group: None,
+            platform_id: None,
+            last_scanned: None,
+            download_summary: None,
+        }
+    }
+
+    /// Correspondance insensible à la casse sur le nom, la plateforme ou le groupe
+    fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        self.name.to_lowercase().contains(&query)
+            || self.platform.name().to_lowercase().contains(&query)
+            || self
+                .group
+                .as_ref()
+                .is_some_and(|group| group.to_lowercase().contains(&query))
+    }
+}
+
+/// Chaîne en cours de déplacement dans la liste, affichée sous le curseur
+#[derive(Clone)]
+struct DraggedChannel {
+    index: usize,
+    name: String,
+}
+
+impl Render for DraggedChannel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .px_3()
+            .py_2()
+            .bg(rgb(theme.surface))
+            .border_1()
+            .border_color(rgb(theme.accent))
+            .rounded_md()
+            .opacity(0.9)
+            .text_color(rgb(theme.text))
+            .text_size(px(14.0))
+            .child(self.name.clone())
+    }
+}
+
+/// Nouvelle position d'un élément à `index` quand l'élément `from` est déplacé en `to`
+fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < to && (from + 1..=to).contains(&index) {
+        index - 1
+    } else if to < from && (to..from).contains(&index) {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Miniature d'une vidéo, absente si elle n'existe pas ou n'a pas pu être chargée
+#[derive(Clone, Debug)]
+enum Thumbnail {
+    Loading,
+    Loaded(PathBuf),
 }
 
 #[derive(Clone, Debug)]
 struct VideoInfo {
+    id: String,
     title: String,
     url: String,
+    upload_date: Option<String>, // YYYYMMDD
+    duration: Option<f64>,
+    thumbnail: Option<String>, // URL distante
     status: VideoStatus,
 }
 
+impl VideoInfo {
+    /// Nom de fichier proposé d'après le modèle de la configuration
+    fn file_name(&self, template: &str, channel_name: &str) -> String {
+        filename::from_template(
+            template,
+            &filename::TemplateValues {
+                title: &self.title,
+                id: &self.id,
+                date: self.upload_date.as_deref(),
+                channel: channel_name,
+            },
+        )
+    }
+
+    /// Correspondance insensible à la casse sur le titre
+    fn matches(&self, query: &str) -> bool {
+        self.title
+            .to_lowercase()
+            .contains(&query.trim().to_lowercase())
+    }
+}
+
+/// Ordre d'affichage de la liste des vidéos
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum VideoSort {
+    #[default]
+    Date,
+    Title,
+    Duration,
+    Status,
+}
+
+impl VideoSort {
+    const ALL: [VideoSort; 4] = [
+        VideoSort::Date,
+        VideoSort::Title,
+        VideoSort::Duration,
+        VideoSort::Status,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            VideoSort::Date => "Date",
+            VideoSort::Title => "Titre",
+            VideoSort::Duration => "Durée",
+            VideoSort::Status => "Statut",
+        }
+    }
+
+    /// Plus récentes, plus longues et non téléchargées d'abord; titres de A à Z.
+    /// Les vidéos sans date ou sans durée sont placées à la fin.
+    fn compare(&self, a: &VideoInfo, b: &VideoInfo) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+
+        fn missing_last<T>(a: Option<T>, b: Option<T>, cmp: impl Fn(T, T) -> Ordering) -> Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) => cmp(a, b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+
+        match self {
+            VideoSort::Date => {
+                missing_last(a.upload_date.as_ref(), b.upload_date.as_ref(), |a, b| {
+                    b.cmp(a)
+                })
+            }
+            VideoSort::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            VideoSort::Duration => missing_last(a.duration, b.duration, |a, b| b.total_cmp(&a)),
+            VideoSort::Status => a.status.sort_rank().cmp(&b.status.sort_rank()),
+        }
+    }
+}
+
+/// Filtre de la liste des vidéos selon leur statut, combiné à la recherche
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum StatusFilter {
+    #[default]
+    All,
+    /// Pas encore téléchargées (téléchargement en cours compris)
+    ToDownload,
+    Downloaded,
+}
+
+impl StatusFilter {
+    const ALL: [StatusFilter; 3] = [
+        StatusFilter::All,
+        StatusFilter::ToDownload,
+        StatusFilter::Downloaded,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            StatusFilter::All => "Toutes",
+            StatusFilter::ToDownload => "À télécharger",
+            StatusFilter::Downloaded => "Téléchargées",
+        }
+    }
+
+    fn accepts(&self, status: &VideoStatus) -> bool {
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::ToDownload => *status != VideoStatus::Downloaded,
+            StatusFilter::Downloaded => *status == VideoStatus::Downloaded,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 enum VideoStatus {
     NotDownloaded,
@@ -72,6 +424,16 @@ enum VideoStatus {
     Downloaded,
 }
 
+impl VideoStatus {
+    fn sort_rank(&self) -> u8 {
+        match self {
+            VideoStatus::NotDownloaded => 0,
+            VideoStatus::Downloading => 1,
+            VideoStatus::Downloaded => 2,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 enum Platform {
     YouTube,
@@ -79,6 +441,13 @@ enum Platform {
 }
 
 impl Platform {
+    fn name(&self) -> &'static str {
+        match self {
+            Platform::YouTube => "YouTube",
+            Platform::Twitch => "Twitch",
+        }
+    }
+
     fn from_url(url: &str) -> Option<Self> {
         if url.contains("youtube.com") || url.contains("youtu.be") {
             Some(Platform::YouTube)
@@ -89,20 +458,81 @@ impl Platform {
         }
     }
 
-    fn extract_channel_name(url: &str) -> Option<String> {
-        // Pour YouTube: youtube.com/@channel ou youtube.com/c/channel
-        if url.contains("youtube.com") {
-            if let Some(idx) = url.find("/@") {
-                let rest = &url[idx + 2..];
-                return Some(rest.split('/').next()?.to_string());
-            } else if let Some(idx) = url.find("/c/") {
-                let rest = &url[idx + 3..];
-                return Some(rest.split('/').next()?.to_string());
-            } else if let Some(idx) = url.find("/channel/") {
-                let rest = &url[idx + 9..];
-                return Some(rest.split('/').next()?.to_string());
+    /// Identifiant d'une vidéo seule: youtu.be/ID, youtube.com/watch?v=ID,
+    /// youtube.com/shorts/ID ou twitch.tv/videos/ID
+    fn extract_video_id(url: &str) -> Option<String> {
+        let url = url.split('#').next()?;
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+
+        let id = if path.trim_end_matches('/').ends_with("youtube.com/watch") {
+            query
+                .split('&')
+                .find_map(|param| param.strip_prefix("v="))?
+        } else {
+            let idx = ["youtu.be/", "youtube.com/shorts/", "twitch.tv/videos/"]
+                .iter()
+                .find_map(|prefix| path.find(prefix).map(|idx| idx + prefix.len()))?;
+            path[idx..].split('/').next()?
+        };
+
+        (!id.is_empty()).then(|| id.to_string())
+    }
+
+    /// Identifiant d'une playlist YouTube: youtube.com/playlist?list=ID
+    fn extract_playlist_id(url: &str) -> Option<String> {
+        let (path, query) = url.split('#').next()?.split_once('?')?;
+        if !path.trim_end_matches('/').ends_with("youtube.com/playlist") {
+            return None;
+        }
+        query
+            .split('&')
+            .find_map(|param| param.strip_prefix("list="))
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+    }
+
+    /// URL à donner à yt-dlp pour lister les vidéos d'une chaîne enregistrée.
+    /// Pour Twitch, la page d'une chaîne est redirigée vers sa liste de VODs (/videos);
+    /// une URL pointant déjà vers une liste ou une VOD est conservée telle quelle.
+    fn scan_url(&self, stored_url: &str) -> String {
+        match self {
+            Platform::YouTube => stored_url.to_string(),
+            Platform::Twitch => {
+                let path = stored_url.split(['?', '#']).next().unwrap_or(stored_url);
+                let is_channel_root = path.find("twitch.tv/").is_some_and(|idx| {
+                    path[idx + 10..]
+                        .split('/')
+                        .filter(|s| !s.is_empty())
+                        .count()
+                        == 1
+                });
+                if is_channel_root {
+                    format!("{}/videos", path.trim_end_matches('/'))
+                } else {
+                    stored_url.to_string()
+                }
             }
         }
+    }
+
+    fn extract_channel_name(url: &str) -> Option<String> {
+        // Ignorer les paramètres (?si=...) et fragments
+        let url = url.split(['?', '#']).next()?;
+
+        // Pour YouTube: youtube.com/@channel, /c/channel, /channel/ID ou /user/channel,
+        // éventuellement suivis de /videos, /streams, /featured...
+        if let Some(idx) = url.find("youtube.com") {
+            let mut segments = url[idx + 11..].split('/').filter(|s| !s.is_empty());
+            let first = segments.next()?;
+            let name = match first.strip_prefix('@') {
+                Some(handle) => handle,
+                None => match first {
+                    "c" | "channel" | "user" => segments.next()?,
+                    _ => return None,
+                },
+            };
+            return (!name.is_empty()).then(|| name.to_string());
+        }
 
         // Pour Twitch: twitch.tv/channel
         if url.contains("twitch.tv/") {
@@ -119,10 +549,185 @@ impl Platform {
     }
 }
 
-const CHANNELS_CACHE_FILE: &str = "/tmp/ndownloader_channels.json";
+/// Résultat de l'ajout d'une chaîne à partir d'une URL collée
+#[derive(Debug, PartialEq)]
+enum AddChannelOutcome {
+    /// Chaîne ajoutée (nom)
+    Added(String),
+    /// Lien vers une vidéo: la chaîne est recherchée en arrière-plan
+    Resolving,
+    UnsupportedPlatform,
+    Unparseable,
+    /// Déjà suivie (nom de la chaîne existante)
+    Duplicate(String),
+}
+
+impl AddChannelOutcome {
+    /// Notifie l'utilisateur du résultat, retourne `true` si l'URL a été acceptée
+    fn notify(&self) -> bool {
+        match self {
+            AddChannelOutcome::Added(name) => {
+                Notification::success("Chaîne ajoutée", &format!("{name} est maintenant suivie"));
+                true
+            }
+            AddChannelOutcome::Resolving => true,
+            AddChannelOutcome::UnsupportedPlatform => {
+                Notification::error(
+                    "URL non prise en charge",
+                    "Seules les chaînes YouTube et Twitch sont reconnues",
+                );
+                false
+            }
+            AddChannelOutcome::Unparseable => {
+                Notification::error(
+                    "URL non reconnue",
+                    "Collez l'URL d'une chaîne (youtube.com/@nom, twitch.tv/nom), d'une playlist ou d'une vidéo",
+                );
+                false
+            }
+            AddChannelOutcome::Duplicate(name) => {
+                Notification::info(
+                    "Chaîne déjà suivie",
+                    &format!("{name} est déjà dans la liste"),
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Bilan de l'ajout de plusieurs URLs collées d'un coup
+#[derive(Debug, Default, PartialEq)]
+struct BulkAddSummary {
+    added: usize,
+    resolving: usize,
+    duplicates: usize,
+    /// URLs refusées (plateforme inconnue ou chaîne non reconnue)
+    rejected: usize,
+}
+
+impl BulkAddSummary {
+    /// Compte un résultat, retourne `false` si l'URL doit rester dans le champ pour être corrigée
+    fn record(&mut self, outcome: &AddChannelOutcome) -> bool {
+        match outcome {
+            AddChannelOutcome::Added(_) => self.added += 1,
+            AddChannelOutcome::Resolving => self.resolving += 1,
+            AddChannelOutcome::Duplicate(_) => self.duplicates += 1,
+            AddChannelOutcome::UnsupportedPlatform | AddChannelOutcome::Unparseable => {
+                self.rejected += 1;
+                return false;
+            }
+        }
+        true
+    }
+
+    fn message(&self) -> String {
+        let mut parts = vec![format!("{} ajoutée(s)", self.added)];
+        if self.resolving > 0 {
+            parts.push(format!("{} en cours de recherche", self.resolving));
+        }
+        if self.duplicates > 0 {
+            parts.push(format!("{} déjà suivie(s)", self.duplicates));
+        }
+        if self.rejected > 0 {
+            parts.push(format!("{} non reconnue(s)", self.rejected));
+        }
+        parts.join(", ")
+    }
+
+    fn notify(&self) {
+        if self.added + self.resolving > 0 {
+            Notification::success("Chaînes ajoutées", &self.message());
+        } else if self.rejected > 0 {
+            Notification::error("Aucune chaîne ajoutée", &self.message());
+        } else {
+            Notification::info("Aucune chaîne ajoutée", &self.message());
+        }
+    }
+}
+
+/// Texte du champ d'ajout: chaque lien doit venir d'une plateforme prise en charge.
+/// Un champ vide est valide, pour ne pas l'afficher en erreur avant la saisie.
+fn is_supported_urls(text: &str) -> bool {
+    text.split_whitespace()
+        .all(|url| Platform::from_url(url).is_some())
+}
+
+/// Texte de progression d'un scan, avec le total seulement s'il est connu
+fn scan_progress_label(count: usize, expected: Option<usize>) -> String {
+    match (count, expected) {
+        (0, _) => "Chargement des vidéos...".to_string(),
+        (count, Some(expected)) => format!("{count} / {expected} vidéos..."),
+        (count, None) => format!("{count} vidéos..."),
+    }
+}
+
+/// Titre de la fenêtre: chaîne affichée et nombre de téléchargements en cours,
+/// visibles aussi quand la fenêtre est réduite
+fn window_title(channel_name: Option<&str>, active_downloads: usize) -> String {
+    let mut title = match channel_name {
+        Some(name) => format!("{name} - {APP_NAME}"),
+        None => APP_NAME.to_string(),
+    };
+    match active_downloads {
+        0 => {}
+        1 => title.push_str(" (1 téléchargement)"),
+        count => title.push_str(&format!(" ({count} téléchargements)")),
+    }
+    title
+}
+
+/// Chaîne déjà suivie correspondant à une URL, même écrite différemment (/videos, casse du nom)
+fn find_duplicate<'a>(
+    channels: &'a [Channel],
+    url: &str,
+    platform: &Platform,
+    name: &str,
+) -> Option<&'a Channel> {
+    channels.iter().find(|c| {
+        c.url == url
+            || (!c.is_playlist && c.platform == *platform && c.name.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Ouvre un dossier avec le gestionnaire de fichiers du système
+fn open_in_file_manager(folder: &std::path::Path) {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+
+    if let Err(error) = std::process::Command::new(opener).arg(folder).spawn() {
+        tracing::warn!("Failed to open folder {}: {}", folder.display(), error);
+        Notification::error(
+            "Impossible d'ouvrir le dossier",
+            &format!("{}: {error}", folder.display()),
+        );
+    }
+}
+
+/// Repère les vidéos déjà téléchargées d'une chaîne sur l'exécuteur d'arrière-plan:
+/// le parcours des disques et ffprobe ne doivent pas figer l'interface
+fn detect_downloaded(
+    executor: &BackgroundExecutor,
+    scanner: &Arc<VideoScanner>,
+    channel_name: &str,
+    videos: &[VideoMetadata],
+) -> Task<anyhow::Result<HashSet<String>>> {
+    let scanner = scanner.clone();
+    let channel_name = channel_name.to_string();
+    let videos = videos.to_vec();
+    executor.spawn(async move { scanner.downloaded_urls(&channel_name, &videos).await })
+}
+
+/// Date du jour au format des dates de publication yt-dlp (YYYYMMDD)
+fn today() -> String {
+    chrono::Local::now().format("%Y%m%d").to_string()
+}
 
 fn load_channels() -> Vec<Channel> {
-    match std::fs::read_to_string(CHANNELS_CACHE_FILE) {
+    match std::fs::read_to_string(paths::channels_file()) {
         Ok(content) => match serde_json::from_str(&content) {
             Ok(channels) => channels,
             Err(error) => {
@@ -140,7 +745,7 @@ fn load_channels() -> Vec<Channel> {
 fn save_channels(channels: &[Channel]) {
     match serde_json::to_string_pretty(channels) {
         Ok(content) => {
-            if let Err(error) = std::fs::write(CHANNELS_CACHE_FILE, content) {
+            if let Err(error) = std::fs::write(paths::channels_file(), content) {
                 tracing::error!("Failed to save channels cache: {}", error);
             }
         }
@@ -151,140 +756,827 @@ fn save_channels(channels: &[Channel]) {
 }
 
 impl NDownloaderApp {
-    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         let url_input = cx.new(|cx| {
             TextInputView::new(cx)
                 .placeholder("Collez un lien YouTube ou Twitch...")
                 .on_enter(move |_text| {
                     // L'action sera gérée directement par handle_add_channel
                 })
+                .validator(is_supported_urls)
         });
+        // Mettre à jour la bordure d'erreur à chaque frappe
+        cx.observe(&url_input, |_, _, cx| cx.notify()).detach();
+
+        let channel_filter =
+            cx.new(|cx| TextInputView::new(cx).placeholder("Rechercher une chaîne..."));
+        // Refiltrer la liste à chaque frappe
+        cx.observe(&channel_filter, |this, _, cx| this.debounce_search(cx))
+            .detach();
+        let video_filter =
+            cx.new(|cx| TextInputView::new(cx).placeholder("Rechercher une vidéo..."));
+        cx.observe(&video_filter, |this, _, cx| this.debounce_search(cx))
+            .detach();
+        let history_filter = cx.new(|cx| {
+            TextInputView::new(cx).placeholder("Rechercher dans les vidéos téléchargées...")
+        });
+        cx.observe(&history_filter, |this, _, cx| this.debounce_search(cx))
+            .detach();
 
-        Self {
+        let config = Config::load();
+        cx.set_global(Theme::for_mode(config.theme));
+        let download_queue = DownloadQueue::new(cx);
+        download_queue.configure(&config);
+
+        let database = match Database::open(&paths::database_file()) {
+            Ok(database) => Some(Arc::new(database)),
+            Err(error) => {
+                tracing::warn!("Failed to open download history: {}", error);
+                None
+            }
+        };
+        let scanner = Arc::new(VideoScanner::from_config(&config).database(database.clone()));
+        Self::check_yt_dlp_version(scanner.clone(), cx);
+
+        let mut app = Self {
+            config,
             url_input,
+            channel_filter,
+            video_filter,
             channels: load_channels(),
             selected_channel: None,
             videos: Vec::new(),
-            scanner: Arc::new(VideoScanner::new()),
-            download_queue: Arc::new(DownloadQueue::new(cx)),
+            video_sort: VideoSort::default(),
+            status_filter: StatusFilter::default(),
+            scanner,
+            database,
+            download_queue: Arc::new(download_queue),
             loading: false,
+            next_page: 0,
+            has_more_videos: false,
+            loading_more: false,
+            scan_task: None,
+            status_check: None,
+            scan_expected: None,
+            scan_error: None,
+            scan_warnings: Vec::new(),
+            show_scan_warnings: false,
             download_input: None,
             download_video: None,
             downloading_videos: std::collections::HashSet::new(),
-        }
+            selected_videos: std::collections::HashSet::new(),
+            error_banner: None,
+            settings: None,
+            thumbnail_cache: Arc::new(ThumbnailCache::new()),
+            thumbnails: HashMap::new(),
+            avatars: HashMap::new(),
+            toasts: Vec::new(),
+            next_toast_id: 0,
+            deleted_channel: None,
+            show_downloads: false,
+            show_history: false,
+            history_filter,
+            channel_query: String::new(),
+            video_query: String::new(),
+            history_query: String::new(),
+            history_results: None,
+            history_search: None,
+            search_debounce: None,
+            context_menu: None,
+            monitor: ChannelMonitor::default(),
+            monitor_task: None,
+            collapsed_groups: HashSet::new(),
+            group_editor: None,
+            pending_group_delete: None,
+            pending_download_all: false,
+            channel_batches: HashMap::new(),
+            forward_channels: ForwardStack::default(),
+            window_title: APP_NAME.to_string(),
+            window_state: WindowState::from_window(window),
+            config_watcher: None,
+            deferred_storage_paths: None,
+            unavailable_storage: HashSet::new(),
+        };
+        app.load_avatars(cx);
+        app.listen_notifications(cx);
+        app.start_monitoring(cx);
+        app.watch_config(cx);
+        app.watch_storage(cx);
+        app.track_window_state(window, cx);
+        app
     }
 
-    fn add_channel_from_url(&mut self, url: String) {
-        if let Some(platform) = Platform::from_url(&url) {
-            if let Some(name) = Platform::extract_channel_name(&url) {
-                // Éviter les doublons
-                if !self.channels.iter().any(|c| c.url == url) {
-                    self.channels.push(Channel {
-                        name,
-                        platform,
-                        url,
-                    });
-                    save_channels(&self.channels);
+    /// Vérifie en arrière-plan que yt-dlp est installé et à jour
+    fn check_yt_dlp_version(scanner: Arc<VideoScanner>, cx: &mut Context<Self>) {
+        cx.background_spawn(async move {
+            match scanner.check_yt_dlp_version().await {
+                Ok(version) if is_yt_dlp_outdated(version, chrono::Local::now().date_naive()) => {
+                    Notification::info(
+                        "yt-dlp obsolète",
+                        &format!(
+                            "La version {} de yt-dlp date de plus de six mois, mettez-la à jour (yt-dlp -U)",
+                            version.format("%Y.%m.%d")
+                        ),
+                    );
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    tracing::warn!("yt-dlp version check failed: {}", error);
+                    Notification::error("yt-dlp indisponible", &error.to_string());
                 }
             }
+        })
+        .detach();
+    }
+
+    fn add_channel_from_url(&mut self, url: String, cx: &mut Context<Self>) -> AddChannelOutcome {
+        let url = url.trim().to_string();
+        let Some(platform) = Platform::from_url(&url) else {
+            return AddChannelOutcome::UnsupportedPlatform;
+        };
+
+        if Platform::extract_video_id(&url).is_some() {
+            self.add_channel_from_video(url, cx);
+            return AddChannelOutcome::Resolving;
+        }
+        if let Some(playlist_id) = Platform::extract_playlist_id(&url) {
+            return self.add_playlist(url, playlist_id, cx);
+        }
+
+        let Some(name) = Platform::extract_channel_name(&url) else {
+            return AddChannelOutcome::Unparseable;
+        };
+        if let Some(existing) = find_duplicate(&self.channels, &url, &platform, &name) {
+            return AddChannelOutcome::Duplicate(existing.name.clone());
+        }
+
+        self.channels
+            .push(Channel::new(name.clone(), platform, url));
+        save_channels(&self.channels);
+        AddChannelOutcome::Added(name)
+    }
+
+    /// Ajoute la chaîne d'une vidéo collée à la place de l'URL de la chaîne
+    fn add_channel_from_video(&mut self, video_url: String, cx: &mut Context<Self>) {
+        Notification::info(
+            "Recherche de la chaîne",
+            "Lien vers une vidéo : récupération de sa chaîne...",
+        );
+
+        let scanner = self.scanner.clone();
+        cx.spawn(async move |this, cx| {
+            let channel_url = match scanner.video_channel_url(&video_url).await {
+                Ok(Some(channel_url)) => channel_url,
+                Ok(None) => {
+                    Notification::error(
+                        "Chaîne introuvable",
+                        "yt-dlp n'indique pas la chaîne de cette vidéo",
+                    );
+                    return;
+                }
+                Err(error) => {
+                    tracing::warn!("Failed to resolve channel of {}: {}", video_url, error);
+                    Notification::error("Chaîne introuvable", &error.to_string());
+                    return;
+                }
+            };
+
+            this.update(cx, |this, cx| {
+                // Ne pas boucler si yt-dlp renvoie une URL qui n'est pas une chaîne
+                if Platform::extract_channel_name(&channel_url).is_none() {
+                    Notification::error(
+                        "Chaîne introuvable",
+                        &format!("URL de chaîne non reconnue: {channel_url}"),
+                    );
+                    return;
+                }
+                this.add_channel_from_url(channel_url, cx).notify();
+                this.load_avatars(cx);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Ajoute une playlist sous son identifiant, remplacé par son titre dès que yt-dlp le fournit
+    fn add_playlist(
+        &mut self,
+        url: String,
+        playlist_id: String,
+        cx: &mut Context<Self>,
+    ) -> AddChannelOutcome {
+        if let Some(existing) = self
+            .channels
+            .iter()
+            .find(|c| c.is_playlist && c.url.contains(&playlist_id))
+        {
+            return AddChannelOutcome::Duplicate(existing.name.clone());
         }
+        self.channels.push(Channel {
+            is_playlist: true,
+            ..Channel::new(playlist_id.clone(), Platform::YouTube, url.clone())
+        });
+        save_channels(&self.channels);
+
+        let scanner = self.scanner.clone();
+        cx.spawn(async move |this, cx| {
+            let title = match scanner.playlist_title(&url).await {
+                Ok(Some(title)) => filename::sanitize(&title),
+                Ok(None) => return,
+                Err(error) => {
+                    tracing::warn!("Failed to fetch playlist title for {}: {}", url, error);
+                    return;
+                }
+            };
+            if title.is_empty() {
+                return;
+            }
+
+            this.update(cx, |this, cx| {
+                if let Some(channel) = this.channels.iter_mut().find(|c| c.url == url) {
+                    channel.name = title;
+                    save_channels(&this.channels);
+                    cx.notify();
+                }
+            })
+            .ok();
+        })
+        .detach();
+
+        AddChannelOutcome::Added(playlist_id)
     }
 
     fn handle_add_channel(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
-        let url = self.url_input.read(cx).value();
-        if !url.trim().is_empty() {
-            self.add_channel_from_url(url);
-            // Clear the input
-            self.url_input.update(cx, |input, _cx| {
-                input.clear();
-            });
-            cx.notify();
+        let text = self.url_input.read(cx).value();
+        // Une liste collée (une URL par ligne ou séparées par des espaces) est ajoutée en entier
+        let urls: Vec<&str> = text.split_whitespace().collect();
+        match urls.as_slice() {
+            [] => return,
+            [url] => {
+                // L'URL reste dans le champ si elle est refusée, pour pouvoir la corriger
+                if self.add_channel_from_url(url.to_string(), cx).notify() {
+                    self.url_input.update(cx, |input, _cx| {
+                        input.clear();
+                    });
+                }
+            }
+            urls => {
+                let mut summary = BulkAddSummary::default();
+                let rejected: Vec<&str> = urls
+                    .iter()
+                    .copied()
+                    .filter(|url| !summary.record(&self.add_channel_from_url(url.to_string(), cx)))
+                    .collect();
+                summary.notify();
+                self.url_input.update(cx, |input, _cx| {
+                    input.set_value(rejected.join(" "));
+                });
+            }
         }
+        self.load_avatars(cx);
+        cx.notify();
+    }
+
+    /// Applique les recherches une fois la frappe arrêtée. Chaque frappe remplace
+    /// (et annule) l'attente précédente: la dernière est toujours appliquée.
+    fn debounce_search(&mut self, cx: &mut Context<Self>) {
+        self.search_debounce = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+            this.update(cx, |this, cx| {
+                this.channel_query = this.channel_filter.read(cx).value();
+                this.video_query = this.video_filter.read(cx).value();
+                let history_query = this.history_filter.read(cx).value();
+                if history_query != this.history_query {
+                    this.history_query = history_query;
+                    if this.show_history {
+                        this.refresh_history(cx);
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
     }
 
     fn select_channel(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.open_channel(index, false, window, cx);
+    }
+
+    /// Affiche une chaîne, en la rescannant sans le cache si `bypass_cache`
+    fn open_channel(
+        &mut self,
+        index: usize,
+        bypass_cache: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.video_filter.update(cx, |input, _| input.clear());
+        self.video_query.clear();
+        self.selected_videos.clear();
+        self.pending_download_all = false;
+        self.scan_channel(index, bypass_cache, window, cx);
+    }
+
+    /// Rescanne la chaîne sélectionnée sans passer par le cache
+    fn refresh_videos(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.loading {
+            return;
+        }
+        if let Some(index) = self.selected_channel {
+            self.scan_channel(index, true, window, cx);
+        }
+    }
+
+    fn scan_channel(
+        &mut self,
+        index: usize,
+        bypass_cache: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         self.selected_channel = Some(index);
         self.loading = true;
+        self.status_check = None;
         self.videos.clear();
+        self.next_page = 0;
+        self.has_more_videos = false;
+        self.loading_more = false;
+        self.scan_expected = None;
+        self.scan_error = None;
+        self.scan_warnings.clear();
+        self.show_scan_warnings = false;
         cx.notify();
 
-        let channel_url = self.channels[index].url.clone();
-        let channel_name = self.channels[index].name.clone();
+        let channel = &self.channels[index];
+        let stored_url = channel.url.clone();
+        let channel_url = channel.platform.scan_url(&channel.url);
+        let channel_name = channel.name.clone();
         let scanner = self.scanner.clone();
 
-        cx.spawn_in(window, async move |this, cx| {
-            let videos_result = scanner.scan_channel_videos(&channel_url).await;
+        // Remplacer la tâche annule le scan d'une chaîne précédemment sélectionnée
+        self.scan_task = Some(cx.spawn_in(window, async move |this, cx| {
+            // Les vidéos s'affichent au fil du scan; leur statut est vérifié une fois le scan fini
+            let (video_tx, mut video_rx) = futures::channel::mpsc::unbounded();
+            let scan = scanner.scan_channel_videos_streaming(
+                &channel_url,
+                bypass_cache,
+                move |video, expected| {
+                    video_tx.unbounded_send((video.clone(), expected)).ok();
+                },
+            );
+            let show_videos = async {
+                while let Some((meta, expected)) = video_rx.next().await {
+                    let updated = this.update(cx, |this, cx| {
+                        this.scan_expected = expected;
+                        let video = this.video_info(meta, false);
+                        this.videos.push(video);
+                        cx.notify();
+                    });
+                    if updated.is_err() {
+                        break;
+                    }
+                }
+            };
+            let (scan_result, ()) = futures::join!(scan, show_videos);
+
+            // Repérer les vidéos déjà téléchargées, en un seul parcours des disques
+            let downloaded = match &scan_result {
+                Ok(scan) => {
+                    let executor = cx.background_executor();
+                    detect_downloaded(executor, &scanner, &channel_name, &scan.videos).await
+                }
+                Err(_) => Ok(Default::default()),
+            };
+            let summary = match (&scan_result, &downloaded) {
+                (Ok(scan), Ok(downloaded)) => Some(DownloadSummary::new(&scan.videos, downloaded)),
+                _ => None,
+            };
 
             this.update(cx, |this, cx| {
-                match videos_result {
-                    Ok(metadata_videos) => {
+                let downloaded = downloaded.unwrap_or_else(|error| {
+                    this.report_error(&error);
+                    Default::default()
+                });
+
+                match scan_result {
+                    Ok(ChannelScan {
+                        videos: metadata_videos,
+                        warnings,
+                    }) => {
+                        this.scan_warnings = warnings;
+                        this.record_scan(&stored_url, &metadata_videos, summary);
+                        this.next_page = 1;
+                        this.has_more_videos = metadata_videos.len() >= scanner.page_size();
                         this.videos = metadata_videos
                             .into_iter()
                             .map(|meta| {
-                                let is_downloaded = scanner
-                                    .is_video_downloaded(&channel_name, meta.duration)
-                                    .is_some();
-                                let is_downloading = this.downloading_videos.contains(&meta.url);
-
-                                let status = if is_downloaded {
-                                    VideoStatus::Downloaded
-                                } else if is_downloading {
-                                    VideoStatus::Downloading
-                                } else {
-                                    VideoStatus::NotDownloaded
-                                };
-
-                                VideoInfo {
-                                    title: meta.title,
-                                    url: meta.url,
-                                    status,
-                                }
+                                let is_downloaded = downloaded.contains(&meta.url);
+                                this.video_info(meta, is_downloaded)
                             })
                             .collect();
+                        this.load_thumbnails(cx);
                     }
                     Err(error) => {
                         tracing::error!("Failed to scan channel videos: {}", error);
+                        this.report_error(&error);
+                        // Les vidéos reçues avant l'échec n'ont pas de statut vérifié
+                        this.videos.clear();
+                        this.scan_error = Some(error.to_string());
                     }
                 }
 
                 this.loading = false;
                 cx.notify();
             })
-        })
-        .detach();
+            .ok();
+        }));
     }
 
-    fn delete_channel(&mut self, index: usize, cx: &mut Context<Self>) {
-        if index < self.channels.len() {
-            self.channels.remove(index);
+    /// Charge la page suivante de vidéos de la chaîne sélectionnée
+    fn load_more_videos(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(index) = self.selected_channel else {
+            return;
+        };
+        if self.loading || self.loading_more || !self.has_more_videos {
+            return;
+        }
+        self.loading_more = true;
+        cx.notify();
 
-            // Sauvegarder les changements
-            save_channels(&self.channels);
+        let channel = &self.channels[index];
+        let channel_url = channel.platform.scan_url(&channel.url);
+        let channel_name = channel.name.clone();
+        let scanner = self.scanner.clone();
+        let page = self.next_page;
+
+        self.scan_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let videos_result = scanner.scan_channel_page(&channel_url, page).await;
+            let downloaded = match &videos_result {
+                Ok(videos) => {
+                    let executor = cx.background_executor();
+                    detect_downloaded(executor, &scanner, &channel_name, videos).await
+                }
+                Err(_) => Ok(Default::default()),
+            };
 
-            // Si on était sur cette chaîne, revenir à la liste
-            if self.selected_channel == Some(index) {
-                self.selected_channel = None;
-                self.videos.clear();
-            } else if let Some(selected) = self.selected_channel {
+            this.update(cx, |this, cx| {
+                // Ignorer le résultat si on a changé de chaîne entre-temps
+                if this.selected_channel != Some(index) || this.next_page != page {
+                    return;
+                }
+
+                let downloaded = downloaded.unwrap_or_else(|error| {
+                    this.report_error(&error);
+                    Default::default()
+                });
+
+                match videos_result {
+                    Ok(metadata_videos) => {
+                        this.next_page = page + 1;
+                        this.has_more_videos = metadata_videos.len() >= scanner.page_size();
+                        for meta in metadata_videos {
+                            // Éviter les doublons entre les pages
+                            if this.videos.iter().any(|v| v.url == meta.url) {
+                                continue;
+                            }
+                            let is_downloaded = downloaded.contains(&meta.url);
+                            let video = this.video_info(meta, is_downloaded);
+                            this.videos.push(video);
+                        }
+                        this.load_thumbnails(cx);
+                    }
+                    Err(error) => {
+                        tracing::error!("Failed to load more videos: {}", error);
+                        this.report_error(&error);
+                    }
+                }
+
+                this.loading_more = false;
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Affiche un bandeau pour les erreurs que l'utilisateur peut corriger lui-même
+    fn report_error(&mut self, error: &anyhow::Error) {
+        if error.downcast_ref::<MissingBinary>().is_some() {
+            self.error_banner = Some(error.to_string());
+        }
+    }
+
+    fn dismiss_error_banner(&mut self, cx: &mut Context<Self>) {
+        self.error_banner = None;
+        cx.notify();
+    }
+
+    /// Construit l'entrée affichée pour une vidéo scannée, avec son statut de téléchargement
+    fn video_info(&self, meta: VideoMetadata, is_downloaded: bool) -> VideoInfo {
+        let is_downloading = self.downloading_videos.contains(&meta.url);
+
+        let status = if is_downloaded {
+            VideoStatus::Downloaded
+        } else if is_downloading {
+            VideoStatus::Downloading
+        } else {
+            VideoStatus::NotDownloaded
+        };
+
+        VideoInfo {
+            id: meta.id,
+            title: meta.title,
+            url: meta.url,
+            upload_date: meta.upload_date,
+            duration: meta.duration,
+            thumbnail: meta.thumbnail,
+            status,
+        }
+    }
+
+    /// Télécharge en arrière-plan les miniatures des vidéos affichées
+    fn load_thumbnails(&mut self, cx: &mut Context<Self>) {
+        let pending: Vec<(String, String)> = self
+            .videos
+            .iter()
+            .filter(|video| !self.thumbnails.contains_key(&video.id))
+            .filter_map(|video| Some((video.id.clone(), video.thumbnail.clone()?)))
+            .collect();
+        if pending.is_empty() {
+            return;
+        }
+
+        for (id, _) in &pending {
+            self.thumbnails.insert(id.clone(), Thumbnail::Loading);
+        }
+
+        let cache = self.thumbnail_cache.clone();
+        cx.spawn(async move |this, cx| {
+            let mut results = futures::stream::iter(pending)
+                .map(|(id, url)| {
+                    let cache = cache.clone();
+                    async move {
+                        let result = cache.fetch(&id, &url).await;
+                        (id, result)
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_THUMBNAILS);
+
+            while let Some((id, result)) = results.next().await {
+                let updated = this.update(cx, |this, cx| {
+                    match result {
+                        Ok(path) => {
+                            this.thumbnails.insert(id, Thumbnail::Loaded(path));
+                        }
+                        Err(error) => {
+                            // Sans miniature, la ligne garde sa mise en page texte
+                            tracing::debug!("Failed to load thumbnail for {}: {}", id, error);
+                            this.thumbnails.remove(&id);
+                        }
+                    }
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Affiche dans la fenêtre chaque notification envoyée au système
+    fn listen_notifications(&mut self, cx: &mut Context<Self>) {
+        let mut events = notifications::subscribe();
+        cx.spawn(async move |this, cx| {
+            while let Some(event) = events.next().await {
+                if this
+                    .update(cx, |this, cx| this.push_toast(event, cx))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Récupère une fois par chaîne son avatar, conservé sur disque par URL de chaîne
+    fn load_avatars(&mut self, cx: &mut Context<Self>) {
+        let mut pending = Vec::new();
+        for channel in &self.channels {
+            if self.avatars.contains_key(&channel.url) {
+                continue;
+            }
+            let cached = self.thumbnail_cache.cached(&channel.url);
+            if cached.is_none() {
+                pending.push(channel.url.clone());
+            }
+            self.avatars.insert(channel.url.clone(), cached);
+        }
+        if pending.is_empty() {
+            return;
+        }
+
+        let scanner = self.scanner.clone();
+        let cache = self.thumbnail_cache.clone();
+        cx.spawn(async move |this, cx| {
+            let mut results = futures::stream::iter(pending)
+                .map(|channel_url| {
+                    let scanner = scanner.clone();
+                    let cache = cache.clone();
+                    async move {
+                        let result = match scanner.channel_avatar_url(&channel_url).await {
+                            Ok(Some(avatar_url)) => {
+                                cache.fetch(&channel_url, &avatar_url).await.map(Some)
+                            }
+                            Ok(None) => Ok(None),
+                            Err(error) => Err(error),
+                        };
+                        (channel_url, result)
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_THUMBNAILS);
+
+            while let Some((channel_url, result)) = results.next().await {
+                let path = result.unwrap_or_else(|error| {
+                    // L'initiale de la chaîne reste affichée à la place
+                    tracing::debug!("Failed to load avatar for {}: {}", channel_url, error);
+                    None
+                });
+                let updated = this.update(cx, |this, cx| {
+                    this.avatars.insert(channel_url, path);
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn delete_channel(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.channels.len() {
+            let channel = self.channels.remove(index);
+            // La saisie en cours pourrait viser une autre chaîne après le décalage
+            self.group_editor = None;
+
+            // Sauvegarder les changements
+            save_channels(&self.channels);
+
+            // Si on était sur cette chaîne, revenir à la liste
+            if self.selected_channel == Some(index) {
+                self.selected_channel = None;
+                self.videos.clear();
+            } else if let Some(selected) = self.selected_channel {
                 // Ajuster l'index si nécessaire
                 if selected > index {
                     self.selected_channel = Some(selected - 1);
                 }
             }
 
+            // Une seule suppression annulable à la fois
+            if let Some(previous) = self.deleted_channel.take() {
+                self.dismiss_toast(previous.toast_id, cx);
+            }
+            let toast_id = self.push_action_toast(
+                NotificationEvent {
+                    notification_type: NotificationType::Info,
+                    title: "Chaîne supprimée".to_string(),
+                    message: format!("{} n'est plus suivie", channel.name),
+                },
+                Some(ToastAction::UndoDeleteChannel),
+                cx,
+            );
+            self.deleted_channel = Some(DeletedChannel {
+                channel,
+                index,
+                toast_id,
+            });
+
             cx.notify();
         }
     }
 
+    /// Remet la dernière chaîne supprimée à sa place
+    fn undo_delete_channel(&mut self, cx: &mut Context<Self>) {
+        let Some(DeletedChannel { channel, index, .. }) = self.deleted_channel.take() else {
+            return;
+        };
+        // Rajoutée entre-temps à la main
+        if find_duplicate(
+            &self.channels,
+            &channel.url,
+            &channel.platform,
+            &channel.name,
+        )
+        .is_some()
+        {
+            return;
+        }
+
+        let index = index.min(self.channels.len());
+        self.channels.insert(index, channel);
+        save_channels(&self.channels);
+        self.selected_channel = self.selected_channel.map(|selected| {
+            if selected >= index {
+                selected + 1
+            } else {
+                selected
+            }
+        });
+        self.load_avatars(cx);
+        cx.notify();
+    }
+
+    /// Note la date d'un scan réussi, l'identifiant de chaîne fourni par yt-dlp et, si
+    /// les fichiers ont pu être vérifiés, le nombre de vidéos déjà téléchargées
+    fn record_scan(
+        &mut self,
+        channel_url: &str,
+        videos: &[VideoMetadata],
+        summary: Option<DownloadSummary>,
+    ) {
+        let Some(channel) = self.channels.iter_mut().find(|c| c.url == channel_url) else {
+            return;
+        };
+        channel.last_scanned = Some(chrono::Utc::now().timestamp());
+        if summary.is_some() {
+            channel.download_summary = summary;
+        }
+        // Une playlist mélange plusieurs chaînes: pas d'identifiant unique
+        if channel.platform_id.is_none() && !channel.is_playlist {
+            channel.platform_id = videos.iter().find_map(|video| video.channel_id.clone());
+        }
+        save_channels(&self.channels);
+    }
+
+    /// Déplace une chaîne à la place d'une autre (glisser-déposer) et enregistre le nouvel ordre
+    fn move_channel(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from == to || from >= self.channels.len() || to >= self.channels.len() {
+            return;
+        }
+
+        let channel = self.channels.remove(from);
+        self.channels.insert(to, channel);
+        save_channels(&self.channels);
+
+        self.selected_channel = self
+            .selected_channel
+            .map(|selected| moved_index(selected, from, to));
+        cx.notify();
+    }
+
     fn go_back(&mut self, _: &GoBack, _window: &mut Window, _cx: &mut Context<Self>) {
+        if self.context_menu.take().is_some() {
+            return;
+        }
+        if self.settings.take().is_some() {
+            return;
+        }
+        if self.show_downloads {
+            self.show_downloads = false;
+            return;
+        }
+        if self.show_history {
+            self.show_history = false;
+            return;
+        }
+        self.remember_channel();
         self.selected_channel = None;
+        self.scan_task = None;
+        self.status_check = None;
+        self.scan_error = None;
+        self.scan_warnings.clear();
+        self.show_scan_warnings = false;
+        self.loading = false;
+        self.loading_more = false;
         self.videos.clear();
+        self.selected_videos.clear();
+        self.pending_download_all = false;
+    }
+
+    fn handle_refresh_videos(
+        &mut self,
+        _: &RefreshVideos,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.refresh_videos(window, cx);
     }
 
     fn handle_quit(&mut self, _: &Quit, _window: &mut Window, cx: &mut Context<Self>) {
         cx.quit();
     }
 
+    fn focus_url_input(&mut self, _: &FocusUrlInput, window: &mut Window, cx: &mut Context<Self>) {
+        self.context_menu = None;
+        self.url_input.update(cx, |input, cx| {
+            input.focus(window);
+            cx.notify();
+        });
+    }
+
     fn handle_cancel_download(
         &mut self,
         _: &CancelDownload,
@@ -294,21 +1586,151 @@ impl NDownloaderApp {
         self.cancel_download(cx);
     }
 
-    fn start_download(&mut self, video_url: String, channel_name: String, cx: &mut Context<Self>) {
-        let input =
-            cx.new(|cx| TextInputView::new(cx).placeholder("Nom du fichier (sans extension)..."));
+    fn start_download(
+        &mut self,
+        video_id: String,
+        video_url: String,
+        channel_name: String,
+        cx: &mut Context<Self>,
+    ) {
+        // Proposer un nom tiré du modèle (le titre de la vidéo par défaut)
+        let suggested_name = self
+            .videos
+            .iter()
+            .find(|video| video.url == video_url)
+            .map(|video| video.file_name(&self.config.filename_template, &channel_name))
+            .unwrap_or_default();
+
+        let input = cx.new(|cx| {
+            TextInputView::new(cx)
+                .placeholder("Nom du fichier (sans extension)...")
+                .default_value(suggested_name)
+                .max_length(filename::MAX_LENGTH)
+                .show_counter()
+        });
         self.download_input = Some(input);
         self.download_video = Some(DownloadingVideo {
+            id: video_id,
             url: video_url,
             channel_name,
+            started: false,
+            quality: self.config.default_quality,
+            container: self.config.default_container,
+            subtitles: false,
             progress: 0.0,
             speed: None,
             eta: None,
+            filename_error: None,
         });
         cx.notify();
     }
 
+    fn select_quality(&mut self, quality: DownloadQuality, cx: &mut Context<Self>) {
+        if let Some(ref mut video) = self.download_video {
+            video.quality = quality;
+            cx.notify();
+        }
+    }
+
+    fn select_container(&mut self, container: VideoContainer, cx: &mut Context<Self>) {
+        if let Some(ref mut video) = self.download_video {
+            video.container = container;
+            cx.notify();
+        }
+    }
+
+    fn toggle_subtitles(&mut self, cx: &mut Context<Self>) {
+        if let Some(ref mut video) = self.download_video {
+            video.subtitles = !video.subtitles;
+            cx.notify();
+        }
+    }
+
+    /// Applique le statut final d'un téléchargement à la liste et à l'overlay
+    fn finish_download(
+        &mut self,
+        video_url: &str,
+        status: DownloadStatus,
+        filename: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let succeeded = status == DownloadStatus::Completed;
+        let video_status = match status {
+            DownloadStatus::Completed => {
+                Notification::success(
+                    "Téléchargement terminé",
+                    &format!("{filename} a été téléchargé avec succès"),
+                );
+                VideoStatus::Downloaded
+            }
+            DownloadStatus::Failed(error) => {
+                Notification::error(
+                    "Échec du téléchargement",
+                    &format!("{filename} n'a pas pu être téléchargé: {error}"),
+                );
+                VideoStatus::NotDownloaded
+            }
+            DownloadStatus::Cancelled => {
+                Notification::info(
+                    "Téléchargement annulé",
+                    &format!("Le téléchargement de {filename} a été annulé"),
+                );
+                VideoStatus::NotDownloaded
+            }
+            DownloadStatus::Queued | DownloadStatus::Downloading => return,
+        };
+
+        self.downloading_videos.remove(video_url);
+        self.finish_batch_video(video_url, succeeded);
+        self.apply_deferred_storage_paths(cx);
+        let mut newly_downloaded = false;
+        for video in &mut self.videos {
+            if video.url == video_url {
+                newly_downloaded = video_status == VideoStatus::Downloaded
+                    && video.status != VideoStatus::Downloaded;
+                video.status = video_status;
+                break;
+            }
+        }
+        // La vidéo appartient à la chaîne affichée: son compteur avance sans attendre un scan
+        if newly_downloaded {
+            if let Some(channel) = self
+                .selected_channel
+                .and_then(|index| self.channels.get_mut(index))
+            {
+                if let Some(summary) = channel
+                    .download_summary
+                    .as_mut()
+                    .filter(|summary| !summary.is_complete())
+                {
+                    summary.downloaded += 1;
+                    save_channels(&self.channels);
+                }
+            }
+        }
+
+        // Fermer l'overlay de progression
+        if self
+            .download_video
+            .as_ref()
+            .is_some_and(|video| video.url == video_url)
+        {
+            self.download_input = None;
+            self.download_video = None;
+        }
+        cx.notify();
+    }
+
     fn cancel_download(&mut self, cx: &mut Context<Self>) {
+        // Arrêter réellement le téléchargement s'il a démarré
+        if let Some(video) = self.download_video.as_ref().filter(|video| video.started) {
+            self.download_queue.cancel(&video.url);
+        }
+        self.hide_download_overlay(cx);
+    }
+
+    /// Ferme l'overlay sans interrompre un téléchargement en cours
+    fn hide_download_overlay(&mut self, cx: &mut Context<Self>) {
         self.download_input = None;
         self.download_video = None;
         cx.notify();
@@ -318,196 +1740,697 @@ impl NDownloaderApp {
         let Some(input) = &self.download_input else {
             return;
         };
-        let Some(video) = &self.download_video else {
+        let Some(video) = &mut self.download_video else {
             return;
         };
+        if video.started {
+            return;
+        }
 
-        let filename = input.read(cx).value();
-        if filename.trim().is_empty() {
-            tracing::warn!("Empty filename provided");
+        // Le nom ne doit pas pouvoir sortir du dossier de la chaîne ni faire échouer yt-dlp
+        let directory = filename::directory_name(&video.channel_name);
+        let filename = filename::sanitize(&input.read(cx).value());
+        if filename.is_empty() {
+            tracing::warn!("Invalid filename provided");
+            video.filename_error = Some(
+                "Nom de fichier invalide : utilisez au moins une lettre ou un chiffre".to_string(),
+            );
+            cx.notify();
             return;
         }
+        video.filename_error = None;
 
-        let channel_name = video.channel_name.clone();
+        let video_id = video.id.clone();
         let video_url = video.url.clone();
-        let download_queue = self.download_queue.clone();
-        let scanner = self.scanner.clone();
 
         // Trouver le meilleur disque de stockage
-        let storage_path = match scanner.find_best_storage_path() {
-            Ok(path) => path,
+        let storage_path = match self.scanner.storage_for_download() {
+            Ok(storage) => storage.path,
             Err(error) => {
                 tracing::error!("Failed to find storage path: {}", error);
+                Notification::error("Téléchargement impossible", &error.to_string());
                 return;
             }
         };
 
-        let output_path = format!("{}/{}/{}.mp4", storage_path, channel_name, filename.trim());
-
-        // Marquer comme en cours de téléchargement
-        self.downloading_videos.insert(video_url.clone());
+        let quality = video.quality;
+        let container = video.container;
+        let subtitles = video
+            .subtitles
+            .then(|| self.config.subtitle_language.clone());
+        let output_path = format!(
+            "{}/{}/{}.{}",
+            storage_path,
+            directory,
+            filename,
+            quality.extension(container)
+        );
 
-        // Mettre à jour le statut des vidéos
-        for video in &mut self.videos {
-            if video.url == video_url {
-                video.status = VideoStatus::Downloading;
-                break;
-            }
+        // Mémoriser la qualité et le conteneur choisis comme nouvelles valeurs par défaut
+        if self.config.default_quality != quality || self.config.default_container != container {
+            self.config.default_quality = quality;
+            self.config.default_container = container;
+            self.config.save();
         }
 
-        // Lancer le téléchargement
-        let output_path_buf = std::path::PathBuf::from(&output_path);
-        let filename_clone = filename.trim().to_string();
+        let output_path = self.available_output_path(&output_path, &[]);
+        let options = DownloadOptions {
+            quality,
+            container,
+            subtitles,
+            ..Default::default()
+        };
+        if !self.enqueue_download(
+            video_id,
+            video_url,
+            filename.clone(),
+            output_path,
+            options,
+            cx,
+        ) {
+            return;
+        }
 
         // Notification de début
         Notification::info(
             "Téléchargement démarré",
-            &format!("Téléchargement de {filename_clone} en cours..."),
+            &format!("Téléchargement de {filename} en cours..."),
         );
 
-        cx.spawn(async move |this, cx| {
-            if let Err(error) = download_queue
-                .add_download(
-                    filename.clone(),
-                    video_url.clone(),
-                    filename.clone(),
-                    output_path_buf.clone(),
-                )
-                .await
-            {
-                tracing::error!("Failed to add download: {}", error);
-                Notification::error(
-                    "Erreur de téléchargement",
-                    &format!("Impossible de démarrer le téléchargement: {error}"),
-                );
-
-                this.update(cx, |this, cx| {
-                    this.downloading_videos.remove(&video_url);
-                    for video in &mut this.videos {
-                        if video.url == video_url {
-                            video.status = VideoStatus::NotDownloaded;
-                            break;
-                        }
-                    }
-                    cx.notify();
-                })
-                .ok();
-                return;
-            }
+        // Garder l'overlay ouvert pour afficher la progression
+        if let Some(ref mut video) = self.download_video {
+            video.started = true;
+        }
+        cx.notify();
+    }
 
-            // Polling: attendre que le fichier existe vraiment
-            let mut progress = 0.0;
-            loop {
-                cx.background_executor()
-                    .timer(std::time::Duration::from_secs(2))
-                    .await;
+    /// Ouvre le dossier contenant une vidéo téléchargée dans le gestionnaire de fichiers
+    fn open_video_folder(&mut self, video: VideoInfo, cx: &mut Context<Self>) {
+        let Some(channel_index) = self.selected_channel else {
+            return;
+        };
+        let channel_name = self.channels[channel_index].name.clone();
+        let scanner = self.scanner.clone();
 
-                // Simuler la progression (incrémenter jusqu'à 90%)
-                if progress < 0.9 {
-                    progress += 0.1;
-                    this.update(cx, |this, cx| {
-                        if let Some(ref mut video) = this.download_video {
-                            if video.url == video_url {
-                                video.progress = progress;
-                                cx.notify();
-                            }
-                        }
-                    })
-                    .ok();
+        cx.spawn(async move |this, cx| {
+            let found = scanner
+                .is_video_downloaded(&channel_name, &video.id, video.duration)
+                .await;
+
+            match found {
+                Ok(Some(path)) => {
+                    let path = std::path::PathBuf::from(path);
+                    if let Some(folder) = path.parent() {
+                        open_in_file_manager(folder);
+                    }
                 }
-
-                if output_path_buf.exists() {
-                    // Fichier existe, téléchargement terminé !
-                    this.update(cx, |this, cx| {
-                        if let Some(ref mut video) = this.download_video {
-                            if video.url == video_url {
-                                video.progress = 1.0;
-                                cx.notify();
-                            }
-                        }
-                    })
-                    .ok();
-
-                    Notification::success(
-                        "Téléchargement terminé",
-                        &format!("{filename_clone} a été téléchargé avec succès"),
+                // Disque démonté: le fichier y est peut-être, la vidéo reste téléchargée
+                Ok(None) if !scanner.unavailable_storage_paths().is_empty() => {
+                    Notification::error(
+                        "Fichier introuvable",
+                        &format!(
+                            "{} n'est sur aucun disque branché: un disque de stockage est indisponible",
+                            video.title
+                        ),
+                    );
+                }
+                Ok(None) => {
+                    Notification::error(
+                        "Fichier introuvable",
+                        &format!("{} a été déplacé ou supprimé", video.title),
                     );
-
                     this.update(cx, |this, cx| {
-                        this.downloading_videos.remove(&video_url);
-                        for video in &mut this.videos {
-                            if video.url == video_url {
-                                video.status = VideoStatus::Downloaded;
-                                break;
-                            }
+                        if let Some(v) = this.videos.iter_mut().find(|v| v.url == video.url) {
+                            v.status = VideoStatus::NotDownloaded;
                         }
                         cx.notify();
                     })
                     .ok();
-                    break;
                 }
-
-                // Timeout après 2 heures (en cas de problème)
-                // TODO: améliorer avec une vraie vérification de l'état de la queue
+                Err(error) => {
+                    Notification::error("Fichier introuvable", &error.to_string());
+                }
             }
         })
         .detach();
+    }
 
-        // Fermer l'overlay
-        self.download_input = None;
-        self.download_video = None;
+    fn toggle_video_selection(&mut self, video_url: String, cx: &mut Context<Self>) {
+        if !self.selected_videos.remove(&video_url) {
+            self.selected_videos.insert(video_url);
+        }
         cx.notify();
     }
-}
+
+    /// Télécharge toutes les vidéos cochées, nommées d'après leur titre
+    fn download_selected(&mut self, cx: &mut Context<Self>) {
+        let Some(channel_index) = self.selected_channel else {
+            return;
+        };
+        let channel_name = self.channels[channel_index].name.clone();
+
+        let selected: Vec<VideoInfo> = self
+            .videos
+            .iter()
+            .filter(|video| {
+                video.status == VideoStatus::NotDownloaded
+                    && self.selected_videos.contains(&video.url)
+            })
+            .cloned()
+            .collect();
+
+        if !self.enqueue_videos(&channel_name, &selected, cx) {
+            return;
+        }
+        self.selected_videos.clear();
+
+        Notification::info(
+            "Téléchargements ajoutés",
+            &format!(
+                "{} vidéo(s) ajoutée(s) à la file de téléchargement",
+                selected.len()
+            ),
+        );
+    }
+
+    /// Ajoute des vidéos à la queue dans la qualité par défaut, nommées d'après leur titre.
+    /// Retourne `false` si aucun dossier de stockage n'est disponible (ou assez libre)
+    /// ou si le dossier de la chaîne ne peut pas être créé.
+    fn enqueue_videos(
+        &mut self,
+        channel_name: &str,
+        videos: &[VideoInfo],
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let storage_path = match self.scanner.storage_for_download() {
+            Ok(storage) => storage.path,
+            Err(error) => {
+                tracing::error!("Failed to find storage path: {}", error);
+                Notification::error("Téléchargement impossible", &error.to_string());
+                return false;
+            }
+        };
+
+        let quality = self.config.default_quality;
+        let container = self.config.default_container;
+        let directory = filename::directory_name(channel_name);
+
+        // Chemins attribués dans ce lot, pas encore visibles dans la queue
+        let mut reserved = Vec::new();
+        for video in videos {
+            let mut filename = video.file_name(&self.config.filename_template, channel_name);
+            if filename.is_empty() {
+                filename = video.id.clone();
+            }
+            let output_path = format!(
+                "{}/{}/{}.{}",
+                storage_path,
+                directory,
+                filename,
+                quality.extension(container)
+            );
+
+            let output_path = self.available_output_path(&output_path, &reserved);
+            reserved.push(output_path.clone());
+            let enqueued = self.enqueue_download(
+                video.id.clone(),
+                video.url.clone(),
+                filename,
+                output_path,
+                DownloadOptions {
+                    quality,
+                    container,
+                    ..Default::default()
+                },
+                cx,
+            );
+            // Toutes les vidéos vont dans le même dossier: inutile d'insister
+            if !enqueued {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Active ou désactive le téléchargement automatique d'une chaîne
+    fn toggle_auto_download(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(channel) = self.channels.get_mut(index) else {
+            return;
+        };
+        channel.auto_download = !channel.auto_download;
+        // Chaînes ajoutées avant l'enregistrement de la date: partir d'aujourd'hui
+        if channel.added_on.is_none() {
+            channel.added_on = Some(today());
+        }
+        save_channels(&self.channels);
+        cx.notify();
+    }
+
+    /// Fiche d'historique d'un téléchargement: le dossier de sortie porte le nom de la chaîne,
+    /// titre et durée viennent de la liste affichée si la vidéo y figure
+    fn download_record(
+        &self,
+        video_id: &str,
+        video_url: &str,
+        filename: &str,
+        output_path: &std::path::Path,
+    ) -> database::Video {
+        let video = self.videos.iter().find(|video| video.url == video_url);
+        let channel = output_path
+            .parent()
+            .and_then(|parent| parent.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        database::Video {
+            id: video_id.to_string(),
+            channel,
+            title: video.map_or_else(|| filename.to_string(), |video| video.title.clone()),
+            path: output_path.to_path_buf(),
+            duration: video.and_then(|video| video.duration),
+            downloaded_at: 0,
+        }
+    }
+
+    fn record_download(&self, video: &database::Video) {
+        let Some(database) = &self.database else {
+            return;
+        };
+        if let Err(error) = database.record(video) {
+            tracing::warn!("Failed to record download of {}: {}", video.id, error);
+        }
+    }
+
+    /// Évite d'écraser un fichier existant ou un téléchargement en cours en suffixant
+    /// le nom: `nom (2).mp4`. `reserved` liste des chemins déjà attribués mais pas encore en queue.
+    fn available_output_path(
+        &self,
+        output_path: &str,
+        reserved: &[std::path::PathBuf],
+    ) -> std::path::PathBuf {
+        let active: Vec<std::path::PathBuf> = self
+            .download_queue
+            .get_tasks()
+            .into_iter()
+            .filter(|t| {
+                matches!(
+                    t.status,
+                    DownloadStatus::Queued | DownloadStatus::Downloading
+                )
+            })
+            .map(|t| t.output_path)
+            .collect();
+
+        filename::unique_path(std::path::Path::new(output_path), |path| {
+            path.exists() || active.iter().chain(reserved).any(|p| p == path)
+        })
+    }
+
+    /// Ajoute une vidéo à la queue et suit sa progression jusqu'au statut final.
+    /// Retourne `false` si le dossier de destination ne peut pas être créé.
+    fn enqueue_download(
+        &mut self,
+        video_id: String,
+        video_url: String,
+        filename: String,
+        output_path: std::path::PathBuf,
+        options: DownloadOptions,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        // yt-dlp ne crée pas forcément le dossier d'une chaîne encore jamais téléchargée
+        if let Some(parent) = output_path.parent() {
+            if let Err(error) = std::fs::create_dir_all(parent) {
+                tracing::error!("Failed to create {}: {}", parent.display(), error);
+                Notification::error(
+                    "Dossier inaccessible",
+                    &format!("Impossible de créer {}: {error}", parent.display()),
+                );
+                return false;
+            }
+        }
+
+        let download_queue = self.download_queue.clone();
+        let mut record = self.download_record(&video_id, &video_url, &filename, &output_path);
+
+        // Marquer comme en cours de téléchargement
+        self.downloading_videos.insert(video_url.clone());
+        self.selected_videos.remove(&video_url);
+
+        // Mettre à jour le statut des vidéos
+        for video in &mut self.videos {
+            if video.url == video_url {
+                video.status = VideoStatus::Downloading;
+                break;
+            }
+        }
+
+        cx.spawn(async move |this, cx| {
+            let completion = download_queue
+                .add_download(
+                    video_id,
+                    video_url.clone(),
+                    filename.clone(),
+                    output_path,
+                    options,
+                )
+                .await;
+            let mut completion = match completion {
+                Ok(completion) => completion,
+                Err(error) => {
+                    tracing::error!("Failed to add download: {}", error);
+                    Notification::error(
+                        "Erreur de téléchargement",
+                        &format!("Impossible de démarrer le téléchargement: {error}"),
+                    );
+
+                    this.update(cx, |this, cx| {
+                        this.downloading_videos.remove(&video_url);
+                        this.finish_batch_video(&video_url, false);
+                        for video in &mut this.videos {
+                            if video.url == video_url {
+                                video.status = VideoStatus::NotDownloaded;
+                                break;
+                            }
+                        }
+                        cx.notify();
+                    })
+                    .ok();
+                    return;
+                }
+            };
+
+            // Suivre la progression jusqu'au statut final renvoyé par la queue
+            let status = loop {
+                cx.background_executor()
+                    .timer(std::time::Duration::from_millis(500))
+                    .await;
+
+                // Progression réelle lue depuis la sortie de yt-dlp
+                let task = download_queue
+                    .get_tasks()
+                    .into_iter()
+                    .find(|t| t.video_url == video_url);
+                if let Some(task) = task {
+                    this.update(cx, |this, cx| {
+                        if let Some(ref mut video) = this.download_video {
+                            if video.url == video_url {
+                                video.progress = task.progress;
+                                video.speed = task.speed;
+                                video.eta = task.eta;
+                            }
+                        }
+                        cx.notify();
+                    })
+                    .ok();
+                }
+
+                match completion.try_recv() {
+                    Ok(Some(status)) => break status,
+                    Ok(None) => {}
+                    Err(_) => {
+                        break DownloadStatus::Failed(
+                            "La file de téléchargement s'est arrêtée".to_string(),
+                        )
+                    }
+                }
+            };
+
+            this.update(cx, |this, cx| {
+                if status == DownloadStatus::Completed {
+                    record.downloaded_at = chrono::Utc::now().timestamp();
+                    this.record_download(&record);
+                    // Historique affiché: y faire apparaître la vidéo terminée
+                    if this.show_history {
+                        this.refresh_history(cx);
+                    }
+                }
+                this.finish_download(&video_url, status, &filename, cx);
+            })
+            .ok();
+        })
+        .detach();
+        cx.notify();
+        true
+    }
+}
 
 impl Render for NDownloaderApp {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let main_content = if let Some(channel_index) = self.selected_channel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.update_window_title(window);
+
+        let main_content = if self.settings.is_some() {
+            self.render_settings(cx)
+        } else if self.show_downloads {
+            self.render_downloads(cx)
+        } else if self.show_history {
+            self.render_history(cx)
+        } else if let Some(channel_index) = self.selected_channel {
             self.render_video_list(channel_index, cx).into_any_element()
         } else {
             self.render_channel_list(cx)
         };
 
+        // Bandeau d'erreur au-dessus de la vue courante
+        let main_content = match self.error_banner.clone() {
+            Some(message) => div()
+                .flex()
+                .flex_col()
+                .size_full()
+                .child(self.render_error_banner(message, cx))
+                .child(div().flex_1().overflow_hidden().child(main_content))
+                .into_any_element(),
+            None => main_content,
+        };
+
         // Si l'overlay de téléchargement est actif, l'afficher
-        if self.download_input.is_some() {
-            return self.render_download_overlay(main_content, cx);
-        }
+        let content = if self.download_input.is_some() {
+            self.render_download_overlay(main_content, cx)
+        } else {
+            main_content
+        };
 
-        main_content
+        // Notifications et menu contextuel au-dessus de tout le reste
+        let context_menu = self.render_context_menu(cx);
+        if self.toasts.is_empty() && context_menu.is_none() {
+            return content;
+        }
+        div()
+            .size_full()
+            .relative()
+            .child(content)
+            .when(!self.toasts.is_empty(), |this| {
+                this.child(self.render_toasts(cx))
+            })
+            .children(context_menu)
+            .into_any_element()
     }
 }
 
 impl NDownloaderApp {
+    fn update_window_title(&mut self, window: &mut Window) {
+        let channel_name = self
+            .selected_channel
+            .and_then(|index| self.channels.get(index))
+            .map(|channel| channel.name.as_str());
+        let title = window_title(channel_name, self.downloading_videos.len());
+        if title != self.window_title {
+            window.set_window_title(&title);
+            self.window_title = title;
+        }
+    }
+
+    fn render_error_banner(&self, message: String, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .px_4()
+            .py_2()
+            .bg(rgb(theme.error))
+            .child(
+                div()
+                    .text_color(rgb(theme.text_on_accent))
+                    .text_size(px(14.0))
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child(message),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .cursor_pointer()
+                    .text_color(rgb(theme.text_on_accent))
+                    .hover(|style| style.opacity(0.7))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _event, _window, cx| {
+                            this.dismiss_error_banner(cx);
+                        }),
+                    )
+                    .child("✕"),
+            )
+    }
+
     fn render_channel_list(&mut self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = Theme::get(cx);
+        let filter = self.channel_query.clone();
+        let visible_channels: Vec<(usize, Channel)> = self
+            .channels
+            .iter()
+            .enumerate()
+            .filter(|(_, channel)| channel.matches(&filter))
+            .map(|(index, channel)| (index, channel.clone()))
+            .collect();
+        let active_downloads = self
+            .download_queue
+            .get_tasks()
+            .iter()
+            .filter(|task| !task.status.is_finished())
+            .count();
+        let downloads_label = if self.download_queue.is_paused() {
+            format!("⏸ Téléchargements ({active_downloads})")
+        } else if active_downloads > 0 {
+            format!("⬇ Téléchargements ({active_downloads})")
+        } else {
+            "⬇ Téléchargements".to_string()
+        };
+
         // Sinon, afficher la liste des chaînes
         div()
             .on_action(cx.listener(Self::go_back))
+            .on_action(cx.listener(Self::go_forward))
+            .on_action(cx.listener(Self::focus_url_input))
             .on_action(cx.listener(Self::handle_quit))
             .on_action(cx.listener(Self::handle_cancel_download))
             .flex()
             .flex_col()
             .size_full()
-            .bg(rgb(NORD0))
+            .bg(rgb(theme.background))
             .gap_4()
             .p_4()
             .child(
                 // Header
                 div()
                     .flex()
-                    .flex_col()
-                    .gap_2()
+                    .items_start()
+                    .justify_between()
                     .child(
                         div()
-                            .text_color(rgb(NORD6))
-                            .text_size(px(24.0))
-                            .font_weight(FontWeight::BOLD)
-                            .child("NDownloader")
+                            .flex()
+                            .flex_col()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .text_size(px(24.0))
+                                    .font_weight(FontWeight::BOLD)
+                                    .child("NDownloader")
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text_muted))
+                                    .text_size(px(14.0))
+                                    .child("Automatic video downloader for Twitch and YouTube")
+                            )
                     )
                     .child(
                         div()
-                            .text_color(rgb(NORD4))
-                            .text_size(px(14.0))
-                            .child("Automatic video downloader for Twitch and YouTube")
+                            .flex()
+                            .gap_2()
+                            .child(
+                                // Bouton téléchargements
+                                div()
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(theme.surface))
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.border)))
+                                    .on_mouse_down(MouseButton::Left, cx.listener(|this, _event, _window, cx| {
+                                        this.open_downloads(cx);
+                                    }))
+                                    .child(
+                                        div()
+                                            .text_color(rgb(theme.text))
+                                            .text_size(px(14.0))
+                                            .child(downloads_label)
+                                    )
+                            )
+                            .child(
+                                // Recherche parmi les vidéos déjà téléchargées
+                                div()
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(theme.surface))
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.border)))
+                                    .on_mouse_down(MouseButton::Left, cx.listener(|this, _event, _window, cx| {
+                                        this.open_history(cx);
+                                    }))
+                                    .child(
+                                        div()
+                                            .text_color(rgb(theme.text))
+                                            .text_size(px(14.0))
+                                            .child("Historique")
+                                    )
+                            )
+                            .child(
+                                // Sauvegarde de la liste des chaînes
+                                div()
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(theme.surface))
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.border)))
+                                    .on_mouse_down(MouseButton::Left, cx.listener(|this, _event, _window, cx| {
+                                        this.export_channels(cx);
+                                    }))
+                                    .child(
+                                        div()
+                                            .text_color(rgb(theme.text))
+                                            .text_size(px(14.0))
+                                            .child("Exporter")
+                                    )
+                            )
+                            .child(
+                                div()
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(theme.surface))
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.border)))
+                                    .on_mouse_down(MouseButton::Left, cx.listener(|this, _event, _window, cx| {
+                                        this.import_channels(cx);
+                                    }))
+                                    .child(
+                                        div()
+                                            .text_color(rgb(theme.text))
+                                            .text_size(px(14.0))
+                                            .child("Importer")
+                                    )
+                            )
+                            .child(
+                                // Bouton paramètres
+                                div()
+                                    .px_4()
+                                    .py_2()
+                                    .bg(rgb(theme.surface))
+                                    .rounded_md()
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.border)))
+                                    .on_mouse_down(MouseButton::Left, cx.listener(|this, _event, _window, cx| {
+                                        this.open_settings(cx);
+                                    }))
+                                    .child(
+                                        div()
+                                            .text_color(rgb(theme.text))
+                                            .text_size(px(14.0))
+                                            .child("⚙ Paramètres")
+                                    )
+                            )
                     )
             )
             .child(
@@ -517,20 +2440,20 @@ impl NDownloaderApp {
                     .flex_col()
                     .gap_3()
                     .p_4()
-                    .bg(rgb(NORD1))
+                    .bg(rgb(theme.surface))
                     .rounded_md()
                     .child(
                         div()
-                            .text_color(rgb(NORD6))
+                            .text_color(rgb(theme.text))
                             .text_size(px(16.0))
                             .font_weight(FontWeight::SEMIBOLD)
                             .child("Ajouter une chaîne")
                     )
                     .child(
                         div()
-                            .text_color(rgb(NORD4))
+                            .text_color(rgb(theme.text_muted))
                             .text_size(px(13.0))
-                            .child("Collez un lien YouTube ou Twitch (l'app détectera automatiquement la plateforme)")
+                            .child("Collez un ou plusieurs liens YouTube ou Twitch (l'app détectera automatiquement la plateforme)")
                     )
                     .child(
                         // URL input and button
@@ -542,9 +2465,9 @@ impl NDownloaderApp {
                                     .flex_1()
                                     .h_10()
                                     .px_3()
-                                    .bg(rgb(NORD2))
+                                    .bg(rgb(theme.element))
                                     .border_1()
-                                    .border_color(rgb(NORD3))
+                                    .border_color(self.url_input.read(cx).border_color(&theme))
                                     .rounded_md()
                                     .on_key_down(cx.listener(|this, event: &KeyDownEvent, window, cx| {
                                         if event.keystroke.key == "enter" {
@@ -558,7 +2481,7 @@ impl NDownloaderApp {
                                 div()
                                     .h_10()
                                     .px_6()
-                                    .bg(rgb(NORD8))
+                                    .bg(rgb(theme.accent))
                                     .rounded_md()
                                     .cursor_pointer()
                                     .on_mouse_down(MouseButton::Left, cx.listener(|this, _event, window, cx| {
@@ -570,7 +2493,7 @@ impl NDownloaderApp {
                                             .items_center()
                                             .justify_center()
                                             .h_full()
-                                            .text_color(rgb(NORD6))
+                                            .text_color(rgb(theme.text_on_accent))
                                             .text_size(px(14.0))
                                             .font_weight(FontWeight::SEMIBOLD)
                                             .child("Ajouter")
@@ -586,27 +2509,46 @@ impl NDownloaderApp {
                     .flex_1()
                     .gap_2()
                     .p_4()
-                    .bg(rgb(NORD1))
+                    .bg(rgb(theme.surface))
                     .rounded_md()
                     .overflow_hidden()
                     .child(
                         div()
-                            .text_color(rgb(NORD6))
+                            .text_color(rgb(theme.text))
                             .text_size(px(16.0))
                             .font_weight(FontWeight::SEMIBOLD)
                             .mb_2()
                             .child(format!("Chaînes surveillées ({})", self.channels.len()))
                     )
+                    .when(!self.channels.is_empty(), |this| {
+                        this.child(
+                            // Search input
+                            div()
+                                .h_10()
+                                .px_3()
+                                .bg(rgb(theme.element))
+                                .border_1()
+                                .border_color(rgb(theme.border))
+                                .rounded_md()
+                                .child(self.channel_filter.clone())
+                        )
+                    })
+                    .children(self.render_group_editor(cx))
                     .child(
-                        if self.channels.is_empty() {
+                        if visible_channels.is_empty() {
+                            let message = if self.channels.is_empty() {
+                                "Aucune chaîne ajoutée"
+                            } else {
+                                "Aucune chaîne ne correspond à la recherche"
+                            };
                             div()
                                 .flex()
                                 .items_center()
                                 .justify_center()
                                 .h_full()
-                                .text_color(rgb(NORD3))
+                                .text_color(rgb(theme.border))
                                 .text_size(px(14.0))
-                                .child("Aucune chaîne ajoutée")
+                                .child(message)
                                 .into_any_element()
                         } else {
                             div()
@@ -616,222 +2558,922 @@ impl NDownloaderApp {
                                 .gap_2()
                                 .size_full()
                                 .overflow_y_scroll()
-                                .children(self.channels.iter().enumerate().map(|(index, channel)| {
-                                    div()
-                                        .flex()
-                                        .p_3()
-                                        .bg(rgb(NORD2))
-                                        .rounded_md()
-                                        .cursor_pointer()
-                                        .hover(|style| style.bg(rgb(NORD3)))
-                                        .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event, window, cx| {
-                                            this.select_channel(index, window, cx);
-                                        }))
-                                        .child(
-                                            div()
-                                                .flex_1()
-                                                .child(ChannelItem::new(channel.clone()))
-                                        )
-                                        .child(
-                                            div()
-                                                .px_2()
-                                                .py_1()
-                                                .bg(rgb(NORD11))
-                                                .rounded_sm()
-                                                .cursor_pointer()
-                                                .hover(|style| style.bg(rgb(0x8f4149)))
-                                                .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event, _window, cx| {
-                                                    this.delete_channel(index, cx);
-                                                    cx.stop_propagation();
-                                                }))
-                                                .child(
-                                                    div()
-                                                        .text_color(rgb(NORD6))
-                                                        .text_size(px(12.0))
-                                                        .font_weight(FontWeight::BOLD)
-                                                        .child("✕")
-                                                )
-                                        )
-                                }))
+                                .children(self.render_channel_sections(visible_channels, cx))
                                 .into_any_element()
                         }
                     )
             )
             .into_any_element()
     }
-}
 
-impl NDownloaderApp {
-    fn render_video_list(&mut self, channel_index: usize, cx: &mut Context<Self>) -> Div {
-        let channel = &self.channels[channel_index];
-        let platform_color = match channel.platform {
-            Platform::YouTube => rgb(NORD11),
-            Platform::Twitch => rgb(NORD15),
+    /// Ligne d'une chaîne: clic pour l'ouvrir, glisser pour la déplacer
+    fn render_channel_row(
+        &self,
+        index: usize,
+        channel: Channel,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        let avatar = self.avatars.get(&channel.url).cloned().flatten();
+        let auto_download = channel.auto_download;
+        let target_group = channel.group.clone();
+        let channel_url = channel.url.clone();
+        let menu_target = MenuTarget::Channel(channel.url.clone());
+        let dragged = DraggedChannel {
+            index,
+            name: channel.name.clone(),
         };
-
         div()
+            .id(("channel", index))
             .flex()
-            .flex_col()
-            .size_full()
-            .bg(rgb(NORD0))
-            .gap_4()
-            .p_4()
+            .items_center()
+            .gap_2()
+            .p_3()
+            .bg(rgb(theme.element))
+            .rounded_md()
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(theme.border)))
+            // Un clic ouvre la chaîne, un glisser la déplace
+            .on_click(cx.listener(move |this, _event: &ClickEvent, window, cx| {
+                this.select_channel(index, window, cx);
+            }))
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
+                    this.open_context_menu(menu_target.clone(), event.position, cx);
+                }),
+            )
+            .on_drag(dragged, |dragged, _offset, _window, cx| {
+                cx.new(|_| dragged.clone())
+            })
+            // Indicateur de l'emplacement où la chaîne sera déposée
+            .drag_over::<DraggedChannel>(move |style, dragged, _window, _cx| {
+                if dragged.index > index {
+                    style.border_t_2().border_color(rgb(theme.accent))
+                } else if dragged.index < index {
+                    style.border_b_2().border_color(rgb(theme.accent))
+                } else {
+                    style
+                }
+            })
+            .on_drop(
+                cx.listener(move |this, dragged: &DraggedChannel, _window, cx| {
+                    // La chaîne déposée rejoint le groupe de sa nouvelle voisine
+                    this.move_channel(dragged.index, index, cx);
+                    this.set_channel_group(index, target_group.clone(), cx);
+                }),
+            )
             .child(
-                // Header avec bouton retour
                 div()
-                    .flex()
-                    .items_center()
-                    .gap_4()
+                    .flex_1()
+                    .child(ChannelItem::new(channel).avatar(avatar)),
+            )
+            .child(self.render_copy_link_button(channel_url, cx))
+            .child(
+                // Choix du groupe
+                div()
+                    .px_2()
+                    .py_1()
+                    .bg(rgb(theme.surface))
+                    .rounded_sm()
+                    .cursor_pointer()
+                    .hover(|style| style.opacity(0.8))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, _window, cx| {
+                            this.start_assign_group(index, cx);
+                            cx.stop_propagation();
+                        }),
+                    )
                     .child(
-                        // Bouton retour
                         div()
-                            .px_4()
-                            .py_2()
-                            .bg(rgb(NORD1))
+                            .text_color(rgb(theme.text_muted))
+                            .text_size(px(12.0))
+                            .child("Groupe"),
+                    ),
+            )
+            .child(
+                // Téléchargement automatique
+                div()
+                    .px_2()
+                    .py_1()
+                    .bg(rgb(if auto_download {
+                        theme.success
+                    } else {
+                        theme.surface
+                    }))
+                    .rounded_sm()
+                    .cursor_pointer()
+                    .hover(|style| style.opacity(0.8))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, _window, cx| {
+                            this.toggle_auto_download(index, cx);
+                            cx.stop_propagation();
+                        }),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(if auto_download {
+                                theme.text_on_accent
+                            } else {
+                                theme.text_muted
+                            }))
+                            .text_size(px(12.0))
+                            .child(if auto_download { "Auto ✓" } else { "Auto" }),
+                    ),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .bg(rgb(theme.error))
+                    .rounded_sm()
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x8f4149)))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, _window, cx| {
+                            this.delete_channel(index, cx);
+                            cx.stop_propagation();
+                        }),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(theme.text_on_accent))
+                            .text_size(px(12.0))
+                            .font_weight(FontWeight::BOLD)
+                            .child("✕"),
+                    ),
+            )
+    }
+}
+
+impl NDownloaderApp {
+    fn render_video_list(&mut self, channel_index: usize, cx: &mut Context<Self>) -> Div {
+        let theme = Theme::get(cx);
+        let filter = self.video_query.clone();
+        let mut visible_videos: Vec<&VideoInfo> = self
+            .videos
+            .iter()
+            .filter(|video| video.matches(&filter) && self.status_filter.accepts(&video.status))
+            .collect();
+        visible_videos.sort_by(|a, b| self.video_sort.compare(a, b));
+        let videos_count = if visible_videos.len() == self.videos.len() {
+            self.videos.len().to_string()
+        } else {
+            format!("{} / {}", visible_videos.len(), self.videos.len())
+        };
+
+        let channel = &self.channels[channel_index];
+        let platform_color = match channel.platform {
+            Platform::YouTube => rgb(theme.error),
+            Platform::Twitch => rgb(theme.highlight),
+        };
+
+        div()
+            .on_action(cx.listener(Self::handle_refresh_videos))
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(rgb(theme.background))
+            .gap_4()
+            .p_4()
+            .child(
+                // Header avec bouton retour
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .child(
+                        // Bouton retour
+                        div()
+                            .px_4()
+                            .py_2()
+                            .bg(rgb(theme.surface))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.border)))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(|this, _event, window, cx| {
+                                    this.go_back(&GoBack, window, cx);
+                                    cx.notify();
+                                }),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .text_size(px(14.0))
+                                    .child("← Retour"),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .child(
+                                div().px_2().py_1().bg(platform_color).rounded_sm().child(
+                                    div()
+                                        .text_color(rgb(theme.text))
+                                        .text_size(px(12.0))
+                                        .font_weight(FontWeight::BOLD)
+                                        .child(match channel.platform {
+                                            Platform::YouTube => "YouTube",
+                                            Platform::Twitch => "Twitch",
+                                        }),
+                                ),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .text_size(px(20.0))
+                                    .font_weight(FontWeight::BOLD)
+                                    .child(channel.name.clone()),
+                            ),
+                    )
+                    .child(
+                        // Bouton rafraîchir (désactivé pendant un scan)
+                        div()
+                            .ml_auto()
+                            .px_4()
+                            .py_2()
+                            .bg(rgb(theme.surface))
+                            .rounded_md()
+                            .when(self.loading, |this| this.opacity(0.5))
+                            .when(!self.loading, |this| {
+                                this.cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.border)))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _event, window, cx| {
+                                            this.refresh_videos(window, cx);
+                                        }),
+                                    )
+                            })
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .text_size(px(14.0))
+                                    .child("⟳ Rafraîchir"),
+                            ),
+                    )
+                    .child(self.render_status_check_button(cx)),
+            )
+            .child(
+                // Liste des vidéos
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .gap_2()
+                    .p_4()
+                    .bg(rgb(theme.surface))
+                    .rounded_md()
+                    .overflow_hidden()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .justify_between()
+                            .mb_2()
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .text_size(px(16.0))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .child(format!("Vidéos disponibles ({videos_count})")),
+                            )
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_3()
+                                    .child(self.render_download_all(channel_index, cx))
+                                    .when(!self.selected_videos.is_empty(), |this| {
+                                        this.child(self.render_download_selected_button(cx))
+                                    })
+                                    .child(self.render_status_filter(cx))
+                                    .child(self.render_sort_selector(cx)),
+                            ),
+                    )
+                    .children(self.render_scan_warnings(cx))
+                    .when(!self.videos.is_empty(), |this| {
+                        this.child(
+                            // Search input
+                            div()
+                                .h_10()
+                                .px_3()
+                                .bg(rgb(theme.element))
+                                .border_1()
+                                .border_color(rgb(theme.border))
+                                .rounded_md()
+                                .child(self.video_filter.clone()),
+                        )
+                    })
+                    .child(if self.loading && self.videos.is_empty() {
+                        div()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .h_full()
+                            .child(self.render_scan_progress(cx))
+                            .into_any_element()
+                    } else if let Some(error) = self.scan_error.clone() {
+                        self.render_scan_error(error, cx).into_any_element()
+                    } else if self.videos.is_empty() {
+                        div()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .h_full()
+                            .text_color(rgb(theme.border))
+                            .text_size(px(14.0))
+                            .child("Aucune vidéo trouvée")
+                            .into_any_element()
+                    } else if visible_videos.is_empty() {
+                        div()
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .h_full()
+                            .text_color(rgb(theme.border))
+                            .text_size(px(14.0))
+                            .child("Aucune vidéo ne correspond à la recherche")
+                            .into_any_element()
+                    } else {
+                        div()
+                            .id("videos-list")
+                            .flex()
+                            .flex_col()
+                            .gap_2()
+                            .size_full()
+                            .overflow_y_scroll()
+                            .children(visible_videos.into_iter().map(|video| {
+                                let video_id = video.id.clone();
+                                let video_url = video.url.clone();
+                                let channel_name = self.channels[channel_index].name.clone();
+                                let status = video.status.clone();
+
+                                // Récupérer la tâche si en cours de téléchargement
+                                let task = if status == VideoStatus::Downloading {
+                                    self.download_queue
+                                        .get_tasks()
+                                        .into_iter()
+                                        .find(|t| t.video_url == video_url)
+                                } else {
+                                    None
+                                };
+
+                                let mut video_item = VideoItem::new(video.clone())
+                                    .thumbnail(self.thumbnails.get(&video.id).cloned());
+                                match task {
+                                    Some(t) if t.status == DownloadStatus::Queued => {
+                                        video_item = video_item.queued();
+                                    }
+                                    Some(t) => video_item = video_item.with_progress(t.progress),
+                                    None => {}
+                                }
+
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_3()
+                                    .p_3()
+                                    .bg(rgb(theme.element))
+                                    .rounded_md()
+                                    // Pendant le scan, le statut n'est pas encore vérifié
+                                    .when(
+                                        status == VideoStatus::NotDownloaded && !self.loading,
+                                        |this| {
+                                            let checked = self.selected_videos.contains(&video_url);
+                                            this.child(self.render_video_checkbox(
+                                                video_url.clone(),
+                                                checked,
+                                                cx,
+                                            ))
+                                            .cursor_pointer()
+                                            .hover(|style| style.bg(rgb(theme.border)))
+                                            .on_mouse_down(
+                                                MouseButton::Left,
+                                                cx.listener(move |this, _event, _window, cx| {
+                                                    this.start_download(
+                                                        video_id.clone(),
+                                                        video_url.clone(),
+                                                        channel_name.clone(),
+                                                        cx,
+                                                    );
+                                                }),
+                                            )
+                                        },
+                                    )
+                                    .on_mouse_down(
+                                        MouseButton::Right,
+                                        cx.listener({
+                                            let video = video.clone();
+                                            move |this, event: &MouseDownEvent, _window, cx| {
+                                                this.open_context_menu(
+                                                    MenuTarget::Video(video.clone()),
+                                                    event.position,
+                                                    cx,
+                                                );
+                                            }
+                                        }),
+                                    )
+                                    .child(video_item)
+                                    .child(self.render_copy_link_button(video.url.clone(), cx))
+                                    .when(status == VideoStatus::Downloaded, |this| {
+                                        this.child(
+                                            self.render_open_folder_button(video.clone(), cx),
+                                        )
+                                    })
+                            }))
+                            .when(self.loading, |this| {
+                                this.child(
+                                    div()
+                                        .flex()
+                                        .justify_center()
+                                        .p_3()
+                                        .child(self.render_scan_progress(cx)),
+                                )
+                            })
+                            .when(self.has_more_videos, |this| {
+                                this.child(self.render_load_more_button(cx))
+                            })
+                            .into_any_element()
+                    }),
+            )
+    }
+
+    fn render_download_selected_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .px_3()
+            .py_1()
+            .bg(rgb(theme.primary))
+            .rounded_md()
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(theme.info)))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _event, _window, cx| {
+                    this.download_selected(cx);
+                }),
+            )
+            .child(
+                div()
+                    .text_color(rgb(theme.text_on_accent))
+                    .text_size(px(13.0))
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child(format!(
+                        "Télécharger la sélection ({})",
+                        self.selected_videos.len()
+                    )),
+            )
+    }
+
+    fn render_video_checkbox(
+        &self,
+        video_url: String,
+        checked: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .size_4()
+            .flex()
+            .flex_none()
+            .items_center()
+            .justify_center()
+            .rounded_sm()
+            .border_1()
+            .border_color(rgb(theme.border))
+            .cursor_pointer()
+            .when(checked, |this| {
+                this.bg(rgb(theme.primary)).child(
+                    div()
+                        .text_color(rgb(theme.text_on_accent))
+                        .text_size(px(11.0))
+                        .child("✓"),
+                )
+            })
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _event, _window, cx| {
+                    this.toggle_video_selection(video_url.clone(), cx);
+                    cx.stop_propagation();
+                }),
+            )
+    }
+
+    /// Copie le lien d'une vidéo ou d'une chaîne dans le presse-papier
+    fn copy_link(&mut self, url: String, cx: &mut Context<Self>) {
+        cx.write_to_clipboard(ClipboardItem::new_string(url.clone()));
+        self.push_toast(
+            NotificationEvent {
+                notification_type: NotificationType::Info,
+                title: "Lien copié".to_string(),
+                message: url,
+            },
+            cx,
+        );
+    }
+
+    fn render_copy_link_button(&self, url: String, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex_none()
+            .px_2()
+            .py_1()
+            .bg(rgb(theme.surface))
+            .rounded_sm()
+            .cursor_pointer()
+            .hover(|style| style.opacity(0.8))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _event, _window, cx| {
+                    this.copy_link(url.clone(), cx);
+                    // Ne pas ouvrir la chaîne ni lancer le téléchargement de la ligne
+                    cx.stop_propagation();
+                }),
+            )
+            .child(
+                div()
+                    .text_color(rgb(theme.text_muted))
+                    .text_size(px(12.0))
+                    .child("Copier le lien"),
+            )
+    }
+
+    fn render_open_folder_button(
+        &self,
+        video: VideoInfo,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex_none()
+            .px_3()
+            .py_1()
+            .bg(rgb(theme.surface))
+            .rounded_md()
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(theme.border)))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _event, _window, cx| {
+                    this.open_video_folder(video.clone(), cx);
+                }),
+            )
+            .child(
+                div()
+                    .text_color(rgb(theme.text))
+                    .text_size(px(13.0))
+                    .child("Ouvrir le dossier"),
+            )
+    }
+
+    fn render_sort_selector(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .child(
+                div()
+                    .text_color(rgb(theme.text_muted))
+                    .text_size(px(13.0))
+                    .child("Trier par :"),
+            )
+            .children(VideoSort::ALL.into_iter().map(|sort| {
+                let is_selected = sort == self.video_sort;
+                div()
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .cursor_pointer()
+                    .bg(rgb(if is_selected {
+                        theme.primary
+                    } else {
+                        theme.element
+                    }))
+                    .hover(|style| style.bg(rgb(theme.border)))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, _window, cx| {
+                            this.video_sort = sort;
+                            cx.notify();
+                        }),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(if is_selected {
+                                theme.text_on_accent
+                            } else {
+                                theme.text
+                            }))
+                            .text_size(px(13.0))
+                            .child(sort.label()),
+                    )
+            }))
+    }
+
+    /// Boutons accolés Toutes / À télécharger / Téléchargées
+    fn render_status_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .rounded_md()
+            .overflow_hidden()
+            .border_1()
+            .border_color(rgb(theme.border))
+            .children(StatusFilter::ALL.into_iter().map(|filter| {
+                let is_selected = filter == self.status_filter;
+                div()
+                    .px_3()
+                    .py_1()
+                    .cursor_pointer()
+                    .bg(rgb(if is_selected {
+                        theme.primary
+                    } else {
+                        theme.element
+                    }))
+                    .hover(|style| style.bg(rgb(theme.border)))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, _window, cx| {
+                            this.status_filter = filter;
+                            cx.notify();
+                        }),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(if is_selected {
+                                theme.text_on_accent
+                            } else {
+                                theme.text
+                            }))
+                            .text_size(px(13.0))
+                            .child(filter.label()),
+                    )
+            }))
+    }
+
+    fn render_quality_selector(
+        &self,
+        selected: DownloadQuality,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_color(rgb(theme.text_muted))
+                    .text_size(px(13.0))
+                    .child("Qualité :"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .children(DownloadQuality::ALL.into_iter().map(|quality| {
+                        let is_selected = quality == selected;
+                        div()
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .bg(rgb(if is_selected {
+                                theme.primary
+                            } else {
+                                theme.element
+                            }))
+                            .hover(|style| style.bg(rgb(theme.border)))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(move |this, _event, _window, cx| {
+                                    this.select_quality(quality, cx);
+                                }),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(if is_selected {
+                                        theme.text_on_accent
+                                    } else {
+                                        theme.text
+                                    }))
+                                    .text_size(px(13.0))
+                                    .child(quality.label()),
+                            )
+                    })),
+            )
+    }
+
+    fn render_container_selector(
+        &self,
+        selected: VideoContainer,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_color(rgb(theme.text_muted))
+                    .text_size(px(13.0))
+                    .child("Format :"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .children(VideoContainer::ALL.into_iter().map(|container| {
+                        let is_selected = container == selected;
+                        div()
+                            .px_3()
+                            .py_1()
                             .rounded_md()
                             .cursor_pointer()
-                            .hover(|style| style.bg(rgb(NORD3)))
+                            .bg(rgb(if is_selected {
+                                theme.primary
+                            } else {
+                                theme.element
+                            }))
+                            .hover(|style| style.bg(rgb(theme.border)))
                             .on_mouse_down(
                                 MouseButton::Left,
-                                cx.listener(|this, _event, window, cx| {
-                                    this.go_back(&GoBack, window, cx);
-                                    cx.notify();
+                                cx.listener(move |this, _event, _window, cx| {
+                                    this.select_container(container, cx);
                                 }),
                             )
                             .child(
                                 div()
-                                    .text_color(rgb(NORD6))
-                                    .text_size(px(14.0))
-                                    .child("← Retour"),
-                            ),
-                    )
-                    .child(
-                        div()
-                            .flex()
-                            .items_center()
-                            .gap_3()
-                            .child(
-                                div().px_2().py_1().bg(platform_color).rounded_sm().child(
-                                    div()
-                                        .text_color(rgb(NORD6))
-                                        .text_size(px(12.0))
-                                        .font_weight(FontWeight::BOLD)
-                                        .child(match channel.platform {
-                                            Platform::YouTube => "YouTube",
-                                            Platform::Twitch => "Twitch",
-                                        }),
-                                ),
+                                    .text_color(rgb(if is_selected {
+                                        theme.text_on_accent
+                                    } else {
+                                        theme.text
+                                    }))
+                                    .text_size(px(13.0))
+                                    .child(container.label()),
                             )
-                            .child(
-                                div()
-                                    .text_color(rgb(NORD6))
-                                    .text_size(px(20.0))
-                                    .font_weight(FontWeight::BOLD)
-                                    .child(channel.name.clone()),
-                            ),
-                    ),
+                    })),
+            )
+    }
+
+    fn render_subtitles_checkbox(&self, checked: bool, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .cursor_pointer()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _event, _window, cx| {
+                    this.toggle_subtitles(cx);
+                }),
             )
             .child(
-                // Liste des vidéos
                 div()
+                    .size_4()
                     .flex()
-                    .flex_col()
-                    .flex_1()
-                    .gap_2()
-                    .p_4()
-                    .bg(rgb(NORD1))
+                    .items_center()
+                    .justify_center()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(rgb(theme.border))
+                    .when(checked, |this| {
+                        this.bg(rgb(theme.primary)).child(
+                            div()
+                                .text_color(rgb(theme.text_on_accent))
+                                .text_size(px(11.0))
+                                .child("✓"),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .text_color(rgb(theme.text_muted))
+                    .text_size(px(13.0))
+                    .child(format!(
+                        "Télécharger les sous-titres ({})",
+                        self.config.subtitle_language
+                    )),
+            )
+    }
+
+    /// Échec du scan, distinct d'une chaîne sans vidéo, avec un bouton pour relancer
+    fn render_scan_error(&self, error: String, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .justify_center()
+            .gap_3()
+            .h_full()
+            .child(
+                div()
+                    .max_w(px(600.0))
+                    .text_color(rgb(theme.error))
+                    .text_size(px(14.0))
+                    .child(format!("Échec du scan: {error}")),
+            )
+            .child(
+                div()
+                    .px_4()
+                    .py_2()
+                    .bg(rgb(theme.surface))
                     .rounded_md()
-                    .overflow_hidden()
-                    .child(
-                        div()
-                            .text_color(rgb(NORD6))
-                            .text_size(px(16.0))
-                            .font_weight(FontWeight::SEMIBOLD)
-                            .mb_2()
-                            .child(format!("Vidéos disponibles ({})", self.videos.len())),
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(theme.border)))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _event, window, cx| {
+                            this.refresh_videos(window, cx);
+                        }),
                     )
-                    .child(if self.loading {
-                        div()
-                            .flex()
-                            .items_center()
-                            .justify_center()
-                            .h_full()
-                            .text_color(rgb(NORD8))
-                            .text_size(px(14.0))
-                            .child("Chargement des vidéos...")
-                            .into_any_element()
-                    } else if self.videos.is_empty() {
+                    .child(
                         div()
-                            .flex()
-                            .items_center()
-                            .justify_center()
-                            .h_full()
-                            .text_color(rgb(NORD3))
+                            .text_color(rgb(theme.text))
                             .text_size(px(14.0))
-                            .child("Aucune vidéo trouvée")
-                            .into_any_element()
-                    } else {
-                        div()
-                            .id("videos-list")
-                            .flex()
-                            .flex_col()
-                            .gap_2()
-                            .size_full()
-                            .overflow_y_scroll()
-                            .children(self.videos.iter().map(|video| {
-                                let video_url = video.url.clone();
-                                let channel_name = self.channels[channel_index].name.clone();
-                                let status = video.status.clone();
-
-                                // Récupérer la progression si en cours de téléchargement
-                                let progress = if status == VideoStatus::Downloading {
-                                    self.download_queue
-                                        .get_tasks()
-                                        .iter()
-                                        .find(|t| t.video_url == video_url)
-                                        .map(|t| t.progress)
-                                } else {
-                                    None
-                                };
-
-                                let mut video_item = VideoItem::new(video.clone());
-                                if let Some(p) = progress {
-                                    video_item = video_item.with_progress(p);
-                                }
+                            .child("Réessayer"),
+                    ),
+            )
+    }
 
-                                div()
-                                    .flex()
-                                    .items_center()
-                                    .gap_3()
-                                    .p_3()
-                                    .bg(rgb(NORD2))
-                                    .rounded_md()
-                                    .when(status == VideoStatus::NotDownloaded, |this| {
-                                        this.cursor_pointer()
-                                            .hover(|style| style.bg(rgb(NORD3)))
-                                            .on_mouse_down(
-                                                MouseButton::Left,
-                                                cx.listener(move |this, _event, _window, cx| {
-                                                    this.start_download(
-                                                        video_url.clone(),
-                                                        channel_name.clone(),
-                                                        cx,
-                                                    );
-                                                }),
-                                            )
-                                    })
-                                    .child(video_item)
-                            }))
-                            .into_any_element()
-                    }),
+    /// Indicateur du scan en cours: "12 / 30 vidéos..."
+    fn render_scan_progress(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .child(Spinner::new("scan-spinner"))
+            .child(
+                div()
+                    .text_color(rgb(theme.accent))
+                    .text_size(px(14.0))
+                    .child(scan_progress_label(self.videos.len(), self.scan_expected)),
             )
     }
 
+    fn render_load_more_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .items_center()
+            .justify_center()
+            .gap_2()
+            .p_3()
+            .bg(rgb(theme.element))
+            .rounded_md()
+            .when(self.loading_more, |this| {
+                this.child(Spinner::new("load-more-spinner")).child(
+                    div()
+                        .text_color(rgb(theme.text_muted))
+                        .text_size(px(14.0))
+                        .child("Chargement..."),
+                )
+            })
+            .when(!self.loading_more, |this| {
+                this.cursor_pointer()
+                    .hover(|style| style.bg(rgb(theme.border)))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _event, window, cx| {
+                            this.load_more_videos(window, cx);
+                        }),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(theme.accent))
+                            .text_size(px(14.0))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child("Charger plus"),
+                    )
+            })
+    }
+
     fn render_download_overlay(
         &mut self,
         main_content: AnyElement,
         cx: &mut Context<Self>,
     ) -> AnyElement {
+        let theme = Theme::get(cx);
+        let progress_video = self.download_video.as_ref().filter(|video| video.started);
+        let started = progress_video.is_some();
+        let filename_error = self
+            .download_video
+            .as_ref()
+            .and_then(|video| video.filename_error.clone());
+
         div()
             .size_full()
             .relative()
@@ -850,14 +3492,14 @@ impl NDownloaderApp {
                     .on_mouse_down(
                         MouseButton::Left,
                         cx.listener(|this, _event, _window, cx| {
-                            this.cancel_download(cx);
+                            this.hide_download_overlay(cx);
                         }),
                     )
                     .child(
                         // Dialog box
                         div()
                             .w(px(500.0))
-                            .bg(rgb(NORD1))
+                            .bg(rgb(theme.surface))
                             .rounded_lg()
                             .p_6()
                             .flex()
@@ -869,11 +3511,23 @@ impl NDownloaderApp {
                             .child(
                                 // Titre
                                 div()
-                                    .text_color(rgb(NORD6))
+                                    .text_color(rgb(theme.text))
                                     .text_size(px(18.0))
                                     .font_weight(FontWeight::BOLD)
                                     .child("Télécharger la vidéo"),
                             )
+                            .when_some(
+                                self.download_video.as_ref().filter(|_| !started),
+                                |this, video| {
+                                    this.child(self.render_quality_selector(video.quality, cx))
+                                        .when(video.quality != DownloadQuality::AudioOnly, |this| {
+                                            this.child(
+                                                self.render_container_selector(video.container, cx),
+                                            )
+                                        })
+                                        .child(self.render_subtitles_checkbox(video.subtitles, cx))
+                                },
+                            )
                             .child(
                                 // Input
                                 div()
@@ -881,17 +3535,18 @@ impl NDownloaderApp {
                                     .flex_col()
                                     .gap_2()
                                     .child(
-                                        div().text_color(rgb(NORD4)).text_size(px(13.0)).child(
-                                            "Entrez le nom du fichier (sans extension .mp4) :",
-                                        ),
+                                        div()
+                                            .text_color(rgb(theme.text_muted))
+                                            .text_size(px(13.0))
+                                            .child("Entrez le nom du fichier (sans extension) :"),
                                     )
                                     .child(
                                         div()
                                             .h_10()
                                             .px_3()
-                                            .bg(rgb(NORD2))
+                                            .bg(rgb(theme.element))
                                             .border_1()
-                                            .border_color(rgb(NORD3))
+                                            .border_color(rgb(theme.border))
                                             .rounded_md()
                                             .on_key_down(cx.listener(
                                                 |this, event: &KeyDownEvent, window, cx| {
@@ -903,9 +3558,17 @@ impl NDownloaderApp {
                                                 },
                                             ))
                                             .child(self.download_input.clone().unwrap()),
-                                    ),
+                                    )
+                                    .when_some(filename_error, |this, error| {
+                                        this.child(
+                                            div()
+                                                .text_color(rgb(theme.error))
+                                                .text_size(px(13.0))
+                                                .child(error),
+                                        )
+                                    }),
                             )
-                            .when_some(self.download_video.as_ref(), |this, video| {
+                            .when_some(progress_video, |this, video| {
                                 this.child(
                                     div()
                                         .flex()
@@ -917,7 +3580,7 @@ impl NDownloaderApp {
                                                 .justify_between()
                                                 .child(
                                                     div()
-                                                        .text_color(rgb(NORD4))
+                                                        .text_color(rgb(theme.text_muted))
                                                         .text_size(px(13.0))
                                                         .child(format!(
                                                             "Progression: {:.0}%",
@@ -927,7 +3590,7 @@ impl NDownloaderApp {
                                                 .when_some(video.speed.as_ref(), |this, speed| {
                                                     this.child(
                                                         div()
-                                                            .text_color(rgb(NORD4))
+                                                            .text_color(rgb(theme.text_muted))
                                                             .text_size(px(13.0))
                                                             .child(speed.to_string()),
                                                     )
@@ -935,7 +3598,7 @@ impl NDownloaderApp {
                                                 .when_some(video.eta.as_ref(), |this, eta| {
                                                     this.child(
                                                         div()
-                                                            .text_color(rgb(NORD4))
+                                                            .text_color(rgb(theme.text_muted))
                                                             .text_size(px(13.0))
                                                             .child(format!("ETA {eta}")),
                                                     )
@@ -955,10 +3618,10 @@ impl NDownloaderApp {
                                         div()
                                             .px_4()
                                             .py_2()
-                                            .bg(rgb(NORD2))
+                                            .bg(rgb(theme.element))
                                             .rounded_md()
                                             .cursor_pointer()
-                                            .hover(|style| style.bg(rgb(NORD3)))
+                                            .hover(|style| style.bg(rgb(theme.border)))
                                             .on_mouse_down(
                                                 MouseButton::Left,
                                                 cx.listener(|this, _event, _window, cx| {
@@ -967,37 +3630,350 @@ impl NDownloaderApp {
                                             )
                                             .child(
                                                 div()
-                                                    .text_color(rgb(NORD6))
+                                                    .text_color(rgb(theme.text))
                                                     .text_size(px(14.0))
                                                     .child("Annuler"),
                                             ),
                                     )
-                                    .child(
-                                        // Bouton Télécharger
-                                        div()
-                                            .px_4()
-                                            .py_2()
-                                            .bg(rgb(NORD8))
-                                            .rounded_md()
-                                            .cursor_pointer()
-                                            .hover(|style| style.bg(rgb(NORD10)))
-                                            .on_mouse_down(
-                                                MouseButton::Left,
-                                                cx.listener(|this, _event, window, cx| {
-                                                    this.confirm_download(window, cx);
-                                                }),
-                                            )
-                                            .child(
-                                                div()
-                                                    .text_color(rgb(NORD6))
-                                                    .text_size(px(14.0))
-                                                    .font_weight(FontWeight::SEMIBOLD)
-                                                    .child("Télécharger"),
-                                            ),
-                                    ),
+                                    .when(!started, |this| {
+                                        this.child(
+                                            // Bouton Télécharger
+                                            div()
+                                                .px_4()
+                                                .py_2()
+                                                .bg(rgb(theme.accent))
+                                                .rounded_md()
+                                                .cursor_pointer()
+                                                .hover(|style| style.bg(rgb(theme.primary)))
+                                                .on_mouse_down(
+                                                    MouseButton::Left,
+                                                    cx.listener(|this, _event, window, cx| {
+                                                        this.confirm_download(window, cx);
+                                                    }),
+                                                )
+                                                .child(
+                                                    div()
+                                                        .text_color(rgb(theme.text_on_accent))
+                                                        .text_size(px(14.0))
+                                                        .font_weight(FontWeight::SEMIBOLD)
+                                                        .child("Télécharger"),
+                                                ),
+                                        )
+                                    }),
                             ),
                     ),
             )
             .into_any_element()
     }
 }
+
+/// Données de test partagées par les sous-modules de l'interface
+#[cfg(test)]
+mod test_support {
+    use super::{VideoInfo, VideoStatus};
+
+    /// Vidéo non téléchargée dont l'identifiant et l'URL dérivent du titre
+    pub(super) fn video(
+        title: &str,
+        upload_date: Option<&str>,
+        duration: Option<f64>,
+    ) -> VideoInfo {
+        VideoInfo {
+            id: title.to_string(),
+            title: title.to_string(),
+            url: format!("https://example.com/{title}"),
+            upload_date: upload_date.map(str::to_string),
+            duration,
+            thumbnail: None,
+            status: VideoStatus::NotDownloaded,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::video;
+    use super::*;
+
+    #[test]
+    fn test_extract_youtube_handle() {
+        for url in [
+            "https://www.youtube.com/@handle",
+            "https://www.youtube.com/@handle/",
+            "https://www.youtube.com/@handle/videos",
+            "https://www.youtube.com/@handle/streams",
+            "https://www.youtube.com/@handle/featured",
+            "youtube.com/@handle?si=abc",
+        ] {
+            assert_eq!(
+                Platform::extract_channel_name(url),
+                Some("handle".to_string()),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_youtube_legacy_forms() {
+        assert_eq!(
+            Platform::extract_channel_name("https://www.youtube.com/c/name/videos"),
+            Some("name".to_string())
+        );
+        assert_eq!(
+            Platform::extract_channel_name("https://www.youtube.com/channel/UC123/streams"),
+            Some("UC123".to_string())
+        );
+        assert_eq!(
+            Platform::extract_channel_name("https://www.youtube.com/user/name/featured"),
+            Some("name".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_youtube_unsupported() {
+        assert_eq!(
+            Platform::extract_channel_name("https://www.youtube.com/watch?v=abc"),
+            None
+        );
+        assert_eq!(
+            Platform::extract_channel_name("https://www.youtube.com/@"),
+            None
+        );
+        assert_eq!(
+            Platform::extract_channel_name("https://www.youtube.com/"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_channel_matches_name_or_platform() {
+        let channel = Channel {
+            group: Some("Jeux".to_string()),
+            ..Channel::new(
+                "SomeStreamer".to_string(),
+                Platform::Twitch,
+                "https://www.twitch.tv/somestreamer".to_string(),
+            )
+        };
+
+        assert!(channel.matches("streamer"));
+        assert!(channel.matches("jeux"));
+        assert!(channel.matches("TWITCH"));
+        assert!(channel.matches(""));
+        assert!(!channel.matches("youtube"));
+    }
+
+    fn sorted_titles(videos: &[VideoInfo], sort: VideoSort) -> Vec<&str> {
+        let mut videos: Vec<&VideoInfo> = videos.iter().collect();
+        videos.sort_by(|a, b| sort.compare(a, b));
+        videos.iter().map(|v| v.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_status_filter() {
+        use VideoStatus::*;
+        assert!([NotDownloaded, Downloading, Downloaded]
+            .iter()
+            .all(|status| StatusFilter::All.accepts(status)));
+        assert!(StatusFilter::ToDownload.accepts(&NotDownloaded));
+        assert!(StatusFilter::ToDownload.accepts(&Downloading));
+        assert!(!StatusFilter::ToDownload.accepts(&Downloaded));
+        assert!(StatusFilter::Downloaded.accepts(&Downloaded));
+        assert!(!StatusFilter::Downloaded.accepts(&Downloading));
+    }
+
+    #[test]
+    fn test_video_sort_puts_missing_values_last() {
+        let videos = [
+            video("b", None, Some(60.0)),
+            video("a", Some("20240101"), None),
+            video("c", Some("20240301"), Some(3600.0)),
+        ];
+
+        assert_eq!(sorted_titles(&videos, VideoSort::Date), ["c", "a", "b"]);
+        assert_eq!(sorted_titles(&videos, VideoSort::Duration), ["c", "b", "a"]);
+        assert_eq!(sorted_titles(&videos, VideoSort::Title), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_extract_video_id() {
+        for url in [
+            "https://youtu.be/abc123",
+            "https://youtu.be/abc123?si=xyz",
+            "https://www.youtube.com/watch?v=abc123",
+            "https://www.youtube.com/watch?list=PL1&v=abc123",
+            "https://www.youtube.com/shorts/abc123",
+            "https://www.twitch.tv/videos/abc123",
+        ] {
+            assert_eq!(
+                Platform::extract_video_id(url),
+                Some("abc123".to_string()),
+                "{url}"
+            );
+        }
+
+        assert_eq!(
+            Platform::extract_video_id("https://www.youtube.com/@handle"),
+            None
+        );
+        assert_eq!(
+            Platform::extract_video_id("https://www.youtube.com/watch"),
+            None
+        );
+        assert_eq!(
+            Platform::extract_video_id("https://www.twitch.tv/streamer"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_playlist_id() {
+        assert_eq!(
+            Platform::extract_playlist_id("https://www.youtube.com/playlist?list=PL123abc"),
+            Some("PL123abc".to_string())
+        );
+        assert_eq!(
+            Platform::extract_playlist_id("youtube.com/playlist?si=x&list=PL123abc#top"),
+            Some("PL123abc".to_string())
+        );
+        assert_eq!(
+            Platform::extract_playlist_id("https://www.youtube.com/watch?v=abc&list=PL123abc"),
+            None
+        );
+        assert_eq!(
+            Platform::extract_playlist_id("https://www.youtube.com/playlist?list="),
+            None
+        );
+        assert_eq!(
+            Platform::extract_playlist_id("https://www.youtube.com/@handle"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_moved_index() {
+        // [a, b, c, d]: b déplacée en dernière position -> [a, c, d, b]
+        let moved: Vec<_> = (0..4).map(|i| moved_index(i, 1, 3)).collect();
+        assert_eq!(moved, [0, 3, 1, 2]);
+
+        // d déplacée en première position -> [d, a, b, c]
+        let moved: Vec<_> = (0..4).map(|i| moved_index(i, 3, 0)).collect();
+        assert_eq!(moved, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_scan_progress_label() {
+        assert_eq!(scan_progress_label(0, None), "Chargement des vidéos...");
+        assert_eq!(scan_progress_label(12, Some(30)), "12 / 30 vidéos...");
+        assert_eq!(scan_progress_label(12, None), "12 vidéos...");
+    }
+
+    #[test]
+    fn test_is_supported_urls() {
+        assert!(is_supported_urls(""));
+        assert!(is_supported_urls("https://www.youtube.com/@handle"));
+        assert!(is_supported_urls(
+            "https://www.youtube.com/@handle\nhttps://www.twitch.tv/streamer"
+        ));
+        assert!(!is_supported_urls("https://vimeo.com/123"));
+        assert!(!is_supported_urls(
+            "https://www.youtube.com/@handle vimeo.com/123"
+        ));
+    }
+
+    #[test]
+    fn test_window_title() {
+        assert_eq!(window_title(None, 0), "NDownloader");
+        assert_eq!(window_title(Some("handle"), 0), "handle - NDownloader");
+        assert_eq!(
+            window_title(Some("handle"), 2),
+            "handle - NDownloader (2 téléchargements)"
+        );
+        assert_eq!(window_title(None, 1), "NDownloader (1 téléchargement)");
+    }
+
+    #[test]
+    fn test_bulk_add_summary() {
+        let mut summary = BulkAddSummary::default();
+        assert!(summary.record(&AddChannelOutcome::Added("a".to_string())));
+        assert!(summary.record(&AddChannelOutcome::Added("b".to_string())));
+        assert!(summary.record(&AddChannelOutcome::Duplicate("a".to_string())));
+        assert!(!summary.record(&AddChannelOutcome::UnsupportedPlatform));
+        assert!(!summary.record(&AddChannelOutcome::Unparseable));
+        assert_eq!(
+            summary.message(),
+            "2 ajoutée(s), 1 déjà suivie(s), 2 non reconnue(s)"
+        );
+
+        let mut summary = BulkAddSummary::default();
+        summary.record(&AddChannelOutcome::Resolving);
+        assert_eq!(summary.message(), "0 ajoutée(s), 1 en cours de recherche");
+    }
+
+    #[test]
+    fn test_download_summary() {
+        let videos: Vec<VideoMetadata> = ["a", "b", "c"]
+            .into_iter()
+            .map(|id| VideoMetadata {
+                id: id.to_string(),
+                title: id.to_string(),
+                url: format!("https://www.youtube.com/watch?v={id}"),
+                duration: None,
+                upload_date: None,
+                uploader: None,
+                thumbnail: None,
+                channel_id: None,
+            })
+            .collect();
+        let downloaded = HashSet::from(["https://www.youtube.com/watch?v=b".to_string()]);
+
+        let summary = DownloadSummary::new(&videos, &downloaded);
+        assert_eq!(summary.label(), "1/3");
+        assert!(!summary.is_complete());
+        assert!(DownloadSummary::new(&videos[1..2], &downloaded).is_complete());
+    }
+
+    #[test]
+    fn test_find_duplicate_channel() {
+        let channels = [Channel::new(
+            "Handle".to_string(),
+            Platform::YouTube,
+            "https://www.youtube.com/@Handle".to_string(),
+        )];
+
+        let other_form = "https://www.youtube.com/@handle/videos";
+        assert!(find_duplicate(&channels, other_form, &Platform::YouTube, "handle").is_some());
+        assert!(find_duplicate(&channels, other_form, &Platform::Twitch, "handle").is_none());
+        assert!(find_duplicate(&channels, other_form, &Platform::YouTube, "other").is_none());
+    }
+
+    #[test]
+    fn test_extract_twitch() {
+        assert_eq!(
+            Platform::extract_channel_name("https://www.twitch.tv/streamer/videos"),
+            Some("streamer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_twitch_scan_url() {
+        let twitch = Platform::Twitch;
+        assert_eq!(
+            twitch.scan_url("https://www.twitch.tv/streamer/"),
+            "https://www.twitch.tv/streamer/videos"
+        );
+        // Idempotent: une liste de VODs ou une VOD précise n'est pas modifiée
+        for url in [
+            "https://www.twitch.tv/streamer/videos",
+            "https://www.twitch.tv/streamer/videos?filter=archives",
+            "https://www.twitch.tv/videos/123456",
+        ] {
+            assert_eq!(twitch.scan_url(url), url);
+            assert_eq!(twitch.scan_url(&twitch.scan_url(url)), url);
+        }
+        assert_eq!(
+            Platform::YouTube.scan_url("https://www.youtube.com/@Handle"),
+            "https://www.youtube.com/@Handle"
+        );
+    }
+}
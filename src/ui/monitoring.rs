@@ -0,0 +1,106 @@
+use crate::monitor::new_videos_message;
+use crate::notifications::Notification;
+use crate::throttle::RateLimited;
+use gpui::*;
+use std::time::Duration;
+
+use super::{detect_downloaded, DownloadSummary, NDownloaderApp};
+
+impl NDownloaderApp {
+    /// (Re)lance la vérification périodique des chaînes selon la configuration.
+    /// La tâche précédente est annulée en étant remplacée.
+    pub(super) fn start_monitoring(&mut self, cx: &mut Context<Self>) {
+        let minutes = self.config.monitor_interval_minutes;
+        if minutes == 0 {
+            self.monitor_task = None;
+            return;
+        }
+        let interval = Duration::from_secs(minutes as u64 * 60);
+
+        self.monitor_task = Some(cx.spawn(async move |this, cx| loop {
+            let Ok((channels, scanner)) =
+                this.update(cx, |this, _| (this.channels.clone(), this.scanner.clone()))
+            else {
+                break;
+            };
+
+            // Une chaîne après l'autre: le cache des scans évite de relancer yt-dlp
+            // pour une chaîne consultée récemment
+            for channel in channels {
+                let scan_url = channel.platform.scan_url(&channel.url);
+                // Chaîne refusée pour trop de requêtes: attendre la fin de sa pause
+                if let Some(remaining) = scanner.cooldown_remaining(&scan_url) {
+                    tracing::info!(
+                        "Skipping {} during rate limit cooldown ({}s left)",
+                        channel.name,
+                        remaining.as_secs()
+                    );
+                    continue;
+                }
+                let videos = match scanner.scan_channel_videos(&scan_url).await {
+                    Ok(videos) => videos,
+                    Err(error) => {
+                        tracing::warn!("Monitoring scan failed for {}: {}", channel.name, error);
+                        if let Some(limited) = error.downcast_ref::<RateLimited>() {
+                            Notification::info(
+                                "Chaîne en pause",
+                                &format!("{}: {limited}", channel.name),
+                            );
+                        }
+                        continue;
+                    }
+                };
+                let executor = cx.background_executor();
+                let downloaded =
+                    detect_downloaded(executor, &scanner, &channel.name, &videos).await;
+                let summary = downloaded
+                    .as_ref()
+                    .ok()
+                    .map(|downloaded| DownloadSummary::new(&videos, downloaded));
+                let downloaded = downloaded.unwrap_or_default();
+
+                let Ok(new_videos) = this.update(cx, |this, cx| {
+                    this.record_scan(&channel.url, &videos, summary);
+                    cx.notify();
+                    this.monitor.new_videos(&channel.url, &videos, &downloaded)
+                }) else {
+                    return;
+                };
+
+                if !new_videos.is_empty() {
+                    tracing::info!("{} nouvelles vidéos sur {}", new_videos.len(), channel.name);
+                    Notification::info(
+                        "Nouvelles vidéos",
+                        &new_videos_message(new_videos.len(), &channel.name),
+                    );
+                }
+
+                if let (true, Some(since)) = (channel.auto_download, channel.added_on.as_deref()) {
+                    this.update(cx, |this, cx| {
+                        let candidates =
+                            this.monitor
+                                .auto_download_candidates(&videos, &downloaded, since);
+                        let videos: Vec<_> = candidates
+                            .into_iter()
+                            .filter(|video| !this.downloading_videos.contains(&video.url))
+                            .map(|video| this.video_info(video, false))
+                            .collect();
+                        if !videos.is_empty() && this.enqueue_videos(&channel.name, &videos, cx) {
+                            Notification::info(
+                                "Téléchargement automatique",
+                                &format!(
+                                    "{} vidéo(s) de {} ajoutée(s) à la file de téléchargement",
+                                    videos.len(),
+                                    channel.name
+                                ),
+                            );
+                        }
+                    })
+                    .ok();
+                }
+            }
+
+            cx.background_executor().timer(interval).await;
+        }));
+    }
+}
@@ -0,0 +1,98 @@
+use gpui::*;
+
+use super::{GoForward, NDownloaderApp};
+
+/// Nombre de chaînes mémorisées pour « Suivant »
+const MAX_FORWARD_CHANNELS: usize = 10;
+
+/// Chaînes quittées avec « Retour », rouvertes par « Suivant » de la plus récente à la
+/// plus ancienne. Les chaînes sont repérées par leur URL: leur position peut changer.
+#[derive(Debug, Default)]
+pub struct ForwardStack {
+    urls: Vec<String>,
+}
+
+impl ForwardStack {
+    fn push(&mut self, url: String) {
+        self.urls.retain(|existing| *existing != url);
+        self.urls.push(url);
+        if self.urls.len() > MAX_FORWARD_CHANNELS {
+            self.urls.remove(0);
+        }
+    }
+
+    /// Dernière chaîne quittée encore suivie (les chaînes supprimées depuis sont oubliées)
+    fn pop(&mut self, is_followed: impl Fn(&str) -> bool) -> Option<String> {
+        while let Some(url) = self.urls.pop() {
+            if is_followed(&url) {
+                return Some(url);
+            }
+        }
+        None
+    }
+}
+
+impl NDownloaderApp {
+    /// Mémorise la chaîne affichée avant de revenir à la liste
+    pub(super) fn remember_channel(&mut self) {
+        if let Some(channel) = self
+            .selected_channel
+            .and_then(|index| self.channels.get(index))
+        {
+            self.forward_channels.push(channel.url.clone());
+        }
+    }
+
+    /// Rouvre la dernière chaîne quittée, avec les vidéos du cache plutôt qu'un nouveau scan
+    pub(super) fn go_forward(
+        &mut self,
+        _: &GoForward,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected_channel.is_some() {
+            return;
+        }
+        let channels = &self.channels;
+        let Some(url) = self
+            .forward_channels
+            .pop(|url| channels.iter().any(|channel| channel.url == url))
+        else {
+            return;
+        };
+        let Some(index) = self.channels.iter().position(|channel| channel.url == url) else {
+            return;
+        };
+        self.select_channel(index, window, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_stack() {
+        let mut stack = ForwardStack::default();
+        stack.push("a".to_string());
+        stack.push("b".to_string());
+        // Revenir d'une chaîne déjà mémorisée la replace en tête
+        stack.push("a".to_string());
+        assert_eq!(stack.pop(|_| true).as_deref(), Some("a"));
+        assert_eq!(stack.pop(|_| true).as_deref(), Some("b"));
+        assert_eq!(stack.pop(|_| true), None);
+
+        // Les chaînes supprimées sont sautées
+        stack.push("removed".to_string());
+        stack.push("c".to_string());
+        stack.push("gone".to_string());
+        assert_eq!(stack.pop(|url| url == "c").as_deref(), Some("c"));
+        assert_eq!(stack.pop(|_| true).as_deref(), Some("removed"));
+
+        for index in 0..=MAX_FORWARD_CHANNELS {
+            stack.push(index.to_string());
+        }
+        assert_eq!(stack.urls.len(), MAX_FORWARD_CHANNELS);
+        assert_eq!(stack.urls[0], "1");
+    }
+}
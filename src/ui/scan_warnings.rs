@@ -0,0 +1,78 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+use super::theme::Theme;
+use super::NDownloaderApp;
+
+/// Résumé affiché au-dessus de la liste des vidéos
+fn warnings_label(count: usize, expanded: bool) -> String {
+    let arrow = if expanded { "▾" } else { "▸" };
+    match count {
+        1 => format!("{arrow} Scan terminé avec 1 avertissement"),
+        count => format!("{arrow} Scan terminé avec {count} avertissements"),
+    }
+}
+
+impl NDownloaderApp {
+    fn toggle_scan_warnings(&mut self, cx: &mut Context<Self>) {
+        self.show_scan_warnings = !self.show_scan_warnings;
+        cx.notify();
+    }
+
+    /// Avertissements de yt-dlp du dernier scan, repliés par défaut: ils expliquent
+    /// souvent une liste incomplète sans empêcher de l'utiliser
+    pub(super) fn render_scan_warnings(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        if self.scan_warnings.is_empty() || self.loading {
+            return None;
+        }
+        let theme = Theme::get(cx);
+        let expanded = self.show_scan_warnings;
+
+        Some(
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .child(
+                    div()
+                        .cursor_pointer()
+                        .text_color(rgb(theme.warning))
+                        .text_size(px(12.0))
+                        .hover(|style| style.opacity(0.8))
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(|this, _event, _window, cx| {
+                                this.toggle_scan_warnings(cx);
+                            }),
+                        )
+                        .child(warnings_label(self.scan_warnings.len(), expanded)),
+                )
+                .when(expanded, |this| {
+                    this.children(self.scan_warnings.iter().map(|warning| {
+                        div()
+                            .pl_4()
+                            .text_color(rgb(theme.text_muted))
+                            .text_size(px(12.0))
+                            .child(warning.clone())
+                    }))
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warnings_label() {
+        assert_eq!(
+            warnings_label(1, false),
+            "▸ Scan terminé avec 1 avertissement"
+        );
+        assert_eq!(
+            warnings_label(3, true),
+            "▾ Scan terminé avec 3 avertissements"
+        );
+    }
+}
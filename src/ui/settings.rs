@@ -0,0 +1,664 @@
+use crate::config::{Config, ThemeMode};
+use crate::downloader_queue::{parse_rate_limit, DownloadQuality};
+use crate::filename;
+use crate::scanner::validate_cookies_file;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use std::collections::HashMap;
+
+use super::text_input::TextInputView;
+use super::theme::Theme;
+use super::{GoBack, NDownloaderApp};
+
+/// Intervalle minimal de surveillance, pour ne pas relancer yt-dlp en boucle
+const MIN_MONITOR_INTERVAL: usize = 5;
+
+/// Champ texte de l'écran des paramètres
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SettingField {
+    StoragePaths,
+    ScanLimit,
+    ScanDepth,
+    ScanInterval,
+    VideoExtensions,
+    DurationTolerance,
+    MaxConcurrentDownloads,
+    MaxDownloadRetries,
+    DownloadRateLimit,
+    CookiesFile,
+    FilenameTemplate,
+    SubtitleLanguage,
+    MonitorInterval,
+    MinFreeSpace,
+}
+
+impl SettingField {
+    pub const ALL: [SettingField; 14] = [
+        SettingField::StoragePaths,
+        SettingField::ScanLimit,
+        SettingField::ScanDepth,
+        SettingField::ScanInterval,
+        SettingField::VideoExtensions,
+        SettingField::DurationTolerance,
+        SettingField::MaxConcurrentDownloads,
+        SettingField::MaxDownloadRetries,
+        SettingField::DownloadRateLimit,
+        SettingField::CookiesFile,
+        SettingField::FilenameTemplate,
+        SettingField::SubtitleLanguage,
+        SettingField::MonitorInterval,
+        SettingField::MinFreeSpace,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingField::StoragePaths => "Dossiers de stockage (séparés par des virgules)",
+            SettingField::ScanLimit => "Vidéos récupérées par scan",
+            SettingField::ScanDepth => "Profondeur des sous-dossiers analysés",
+            SettingField::ScanInterval => "Pause entre deux appels à yt-dlp (secondes)",
+            SettingField::VideoExtensions => "Extensions vidéo reconnues",
+            SettingField::DurationTolerance => {
+                "Écart de durée toléré pour reconnaître une vidéo (secondes)"
+            }
+            SettingField::MaxConcurrentDownloads => "Téléchargements simultanés",
+            SettingField::MaxDownloadRetries => "Nouveaux essais après une erreur réseau",
+            SettingField::DownloadRateLimit => "Débit maximal (ex: 2M, 500K, vide = illimité)",
+            SettingField::CookiesFile => "Fichier de cookies (cookies.txt, optionnel)",
+            SettingField::FilenameTemplate => "Nom des fichiers ({title}, {id}, {date}, {channel})",
+            SettingField::SubtitleLanguage => "Langue des sous-titres",
+            SettingField::MonitorInterval => {
+                "Vérification des nouvelles vidéos (minutes, 0 = désactivée)"
+            }
+            SettingField::MinFreeSpace => {
+                "Espace libre minimal avant téléchargement (Mo, 0 = aucun)"
+            }
+        }
+    }
+
+    /// Valeur actuelle du champ, telle qu'affichée dans le formulaire
+    fn value(&self, config: &Config) -> String {
+        match self {
+            SettingField::StoragePaths => config.storage_paths.join(", "),
+            SettingField::ScanLimit => config.scan_limit.to_string(),
+            SettingField::ScanDepth => config.scan_depth.to_string(),
+            SettingField::ScanInterval => config.min_scan_interval_secs.to_string(),
+            SettingField::VideoExtensions => config.video_extensions.join(", "),
+            SettingField::DurationTolerance => config.duration_tolerance_secs.to_string(),
+            SettingField::MaxConcurrentDownloads => config.max_concurrent_downloads.to_string(),
+            SettingField::MaxDownloadRetries => config.max_download_retries.to_string(),
+            SettingField::DownloadRateLimit => {
+                config.download_rate_limit.clone().unwrap_or_default()
+            }
+            SettingField::CookiesFile => config.cookies_file.clone().unwrap_or_default(),
+            SettingField::FilenameTemplate => config.filename_template.clone(),
+            SettingField::SubtitleLanguage => config.subtitle_language.clone(),
+            SettingField::MonitorInterval => config.monitor_interval_minutes.to_string(),
+            SettingField::MinFreeSpace => config.min_free_space_mb.to_string(),
+        }
+    }
+
+    /// Valide le texte saisi et l'écrit dans la configuration
+    fn apply(&self, config: &mut Config, text: &str) -> Result<(), String> {
+        let text = text.trim();
+        match self {
+            SettingField::StoragePaths => {
+                let paths = split_list(text);
+                if let Some(path) = paths
+                    .iter()
+                    .find(|p| !p.starts_with('/') && !p.starts_with('~'))
+                {
+                    return Err(format!("Chemin absolu attendu: {path}"));
+                }
+                config.storage_paths = paths;
+            }
+            SettingField::ScanLimit => config.scan_limit = parse_number(text, 1, 500)?,
+            SettingField::ScanDepth => config.scan_depth = parse_number(text, 0, 10)?,
+            SettingField::ScanInterval => {
+                config.min_scan_interval_secs = parse_number(text, 0, 60)?
+            }
+            SettingField::VideoExtensions => {
+                let extensions = split_list(text);
+                if extensions.is_empty() {
+                    return Err("Au moins une extension est nécessaire".to_string());
+                }
+                config.video_extensions = extensions;
+            }
+            SettingField::DurationTolerance => {
+                config.duration_tolerance_secs = parse_number(text, 1, 60)?
+            }
+            SettingField::MaxConcurrentDownloads => {
+                config.max_concurrent_downloads = parse_number(text, 1, 10)?
+            }
+            SettingField::MaxDownloadRetries => {
+                config.max_download_retries = parse_number(text, 0, 10)?
+            }
+            SettingField::DownloadRateLimit => {
+                config.download_rate_limit = if text.is_empty() {
+                    None
+                } else {
+                    Some(parse_rate_limit(text).map_err(|e| e.to_string())?)
+                };
+            }
+            SettingField::CookiesFile => {
+                if !text.is_empty() {
+                    validate_cookies_file(text).map_err(|e| e.to_string())?;
+                }
+                config.cookies_file = (!text.is_empty()).then(|| text.to_string());
+            }
+            SettingField::FilenameTemplate => {
+                filename::validate_template(text)?;
+                config.filename_template = text.to_string();
+            }
+            SettingField::SubtitleLanguage => {
+                if text.is_empty() || !text.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                    return Err("Code de langue attendu (ex: en, fr, pt-BR)".to_string());
+                }
+                config.subtitle_language = text.to_string();
+            }
+            SettingField::MonitorInterval => {
+                let minutes = parse_number(text, 0, 1440)?;
+                if (1..MIN_MONITOR_INTERVAL).contains(&minutes) {
+                    return Err(format!(
+                        "{MIN_MONITOR_INTERVAL} minutes minimum pour ne pas surcharger yt-dlp"
+                    ));
+                }
+                config.monitor_interval_minutes = minutes;
+            }
+            SettingField::MinFreeSpace => {
+                config.min_free_space_mb = parse_number(text, 0, 1_000_000)?
+            }
+        }
+        Ok(())
+    }
+}
+
+fn split_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_number(text: &str, min: usize, max: usize) -> Result<usize, String> {
+    match text.parse::<usize>() {
+        Ok(value) if (min..=max).contains(&value) => Ok(value),
+        _ => Err(format!("Nombre entre {min} et {max} attendu")),
+    }
+}
+
+/// Formulaire d'édition de la configuration
+pub struct SettingsForm {
+    pub inputs: Vec<(SettingField, Entity<TextInputView>)>,
+    pub default_quality: DownloadQuality,
+    pub embed_thumbnail: bool,
+    pub errors: HashMap<SettingField, String>,
+}
+
+impl SettingsForm {
+    pub fn new(config: &Config, cx: &mut App) -> Self {
+        let inputs = SettingField::ALL
+            .into_iter()
+            .map(|field| {
+                let value = field.value(config);
+                (
+                    field,
+                    cx.new(|cx| TextInputView::new(cx).default_value(value)),
+                )
+            })
+            .collect();
+
+        Self {
+            inputs,
+            default_quality: config.default_quality,
+            embed_thumbnail: config.embed_thumbnail,
+            errors: HashMap::new(),
+        }
+    }
+
+    /// Construit la nouvelle configuration à partir des champs saisis.
+    /// Retourne `None` et renseigne `errors` si un champ est invalide.
+    pub fn build_config(&mut self, current: &Config, cx: &App) -> Option<Config> {
+        let mut config = current.clone();
+        config.default_quality = self.default_quality;
+        config.embed_thumbnail = self.embed_thumbnail;
+
+        self.errors.clear();
+        for (field, input) in &self.inputs {
+            if let Err(error) = field.apply(&mut config, &input.read(cx).value()) {
+                self.errors.insert(*field, error);
+            }
+        }
+
+        self.errors.is_empty().then_some(config)
+    }
+}
+
+impl NDownloaderApp {
+    pub(super) fn open_settings(&mut self, cx: &mut Context<Self>) {
+        self.settings = Some(SettingsForm::new(&self.config, cx));
+        cx.notify();
+    }
+
+    /// Applique le thème immédiatement, sans attendre l'enregistrement du formulaire
+    fn set_theme(&mut self, mode: ThemeMode, cx: &mut Context<Self>) {
+        if self.config.theme == mode {
+            return;
+        }
+        self.config.theme = mode;
+        self.config.save();
+        cx.set_global(Theme::for_mode(mode));
+        cx.refresh_windows();
+    }
+
+    fn close_settings(&mut self, cx: &mut Context<Self>) {
+        self.settings = None;
+        cx.notify();
+    }
+
+    /// Valide le formulaire, enregistre la configuration et l'applique immédiatement
+    fn save_settings(&mut self, cx: &mut Context<Self>) {
+        let Some(form) = &mut self.settings else {
+            return;
+        };
+        let Some(config) = form.build_config(&self.config, cx) else {
+            // Les erreurs sont affichées sous les champs concernés
+            cx.notify();
+            return;
+        };
+
+        config.save();
+        // Les dossiers saisis remplacent ceux relus du fichier et encore en attente
+        self.deferred_storage_paths = None;
+        self.apply_config(config, cx);
+        self.close_settings(cx);
+    }
+
+    pub(super) fn render_settings(&mut self, cx: &mut Context<Self>) -> AnyElement {
+        let theme = Theme::get(cx);
+        let Some(form) = &self.settings else {
+            return div().into_any_element();
+        };
+
+        let fields: Vec<_> = form
+            .inputs
+            .iter()
+            .map(|(field, input)| (*field, input.clone(), form.errors.get(field).cloned()))
+            .collect();
+        let default_quality = form.default_quality;
+        let embed_thumbnail = form.embed_thumbnail;
+
+        div()
+            .on_action(cx.listener(Self::go_back))
+            .on_action(cx.listener(Self::handle_quit))
+            .flex()
+            .flex_col()
+            .size_full()
+            .bg(rgb(theme.background))
+            .gap_4()
+            .p_4()
+            .child(
+                // Header avec bouton retour
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .child(
+                        div()
+                            .px_4()
+                            .py_2()
+                            .bg(rgb(theme.surface))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.border)))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(|this, _event, window, cx| {
+                                    this.go_back(&GoBack, window, cx);
+                                    cx.notify();
+                                }),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .text_size(px(14.0))
+                                    .child("← Retour"),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(theme.text))
+                            .text_size(px(20.0))
+                            .font_weight(FontWeight::BOLD)
+                            .child("Paramètres"),
+                    ),
+            )
+            .child(
+                div()
+                    .id("settings-list")
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .gap_4()
+                    .p_4()
+                    .bg(rgb(theme.surface))
+                    .rounded_md()
+                    .overflow_y_scroll()
+                    .children(fields.into_iter().map(|(field, input, error)| {
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text_muted))
+                                    .text_size(px(13.0))
+                                    .child(field.label()),
+                            )
+                            .child(
+                                div()
+                                    .h_10()
+                                    .bg(rgb(theme.element))
+                                    .border_1()
+                                    .border_color(rgb(if error.is_some() {
+                                        theme.error
+                                    } else {
+                                        theme.border
+                                    }))
+                                    .rounded_md()
+                                    .child(input),
+                            )
+                            .when_some(error, |this, error| {
+                                this.child(
+                                    div()
+                                        .text_color(rgb(theme.error))
+                                        .text_size(px(12.0))
+                                        .child(error),
+                                )
+                            })
+                    }))
+                    .child(self.render_default_quality_selector(default_quality, cx))
+                    .child(self.render_embed_thumbnail_checkbox(embed_thumbnail, cx))
+                    .child(self.render_theme_selector(cx)),
+            )
+            .child(
+                // Boutons
+                div()
+                    .flex()
+                    .justify_end()
+                    .gap_3()
+                    .child(
+                        div()
+                            .px_6()
+                            .py_2()
+                            .bg(rgb(theme.border))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.element)))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(|this, _event, _window, cx| {
+                                    this.close_settings(cx);
+                                }),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .text_size(px(14.0))
+                                    .child("Annuler"),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .px_6()
+                            .py_2()
+                            .bg(rgb(theme.primary))
+                            .rounded_md()
+                            .cursor_pointer()
+                            .hover(|style| style.opacity(0.9))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(|this, _event, _window, cx| {
+                                    this.save_settings(cx);
+                                }),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(theme.text_on_accent))
+                                    .text_size(px(14.0))
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .child("Enregistrer"),
+                            ),
+                    ),
+            )
+            .into_any_element()
+    }
+
+    fn render_default_quality_selector(
+        &self,
+        selected: DownloadQuality,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_color(rgb(theme.text_muted))
+                    .text_size(px(13.0))
+                    .child("Qualité par défaut"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .children(DownloadQuality::ALL.into_iter().map(|quality| {
+                        let is_selected = quality == selected;
+                        div()
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .bg(rgb(if is_selected {
+                                theme.primary
+                            } else {
+                                theme.element
+                            }))
+                            .hover(|style| style.bg(rgb(theme.border)))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(move |this, _event, _window, cx| {
+                                    if let Some(form) = &mut this.settings {
+                                        form.default_quality = quality;
+                                        cx.notify();
+                                    }
+                                }),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(if is_selected {
+                                        theme.text_on_accent
+                                    } else {
+                                        theme.text
+                                    }))
+                                    .text_size(px(13.0))
+                                    .child(quality.label()),
+                            )
+                    })),
+            )
+    }
+
+    fn render_embed_thumbnail_checkbox(
+        &self,
+        checked: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .cursor_pointer()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _event, _window, cx| {
+                    if let Some(form) = &mut this.settings {
+                        form.embed_thumbnail = !form.embed_thumbnail;
+                        cx.notify();
+                    }
+                }),
+            )
+            .child(
+                div()
+                    .size_4()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(rgb(theme.border))
+                    .when(checked, |this| {
+                        this.bg(rgb(theme.primary)).child(
+                            div()
+                                .text_color(rgb(theme.text_on_accent))
+                                .text_size(px(11.0))
+                                .child("✓"),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .text_color(rgb(theme.text_muted))
+                    .text_size(px(13.0))
+                    .child("Intégrer la miniature aux fichiers téléchargés"),
+            )
+    }
+
+    fn render_theme_selector(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        let selected = self.config.theme;
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_color(rgb(theme.text_muted))
+                    .text_size(px(13.0))
+                    .child("Thème"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .children(ThemeMode::ALL.into_iter().map(|mode| {
+                        let is_selected = mode == selected;
+                        div()
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .bg(rgb(if is_selected {
+                                theme.primary
+                            } else {
+                                theme.element
+                            }))
+                            .hover(|style| style.bg(rgb(theme.border)))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(move |this, _event, _window, cx| {
+                                    this.set_theme(mode, cx);
+                                }),
+                            )
+                            .child(
+                                div()
+                                    .text_color(rgb(if is_selected {
+                                        theme.text_on_accent
+                                    } else {
+                                        theme.text
+                                    }))
+                                    .text_size(px(13.0))
+                                    .child(mode.label()),
+                            )
+                    })),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_valid_values() {
+        let mut config = Config::default();
+
+        SettingField::StoragePaths
+            .apply(&mut config, "/mnt/a, ~/Videos")
+            .unwrap();
+        SettingField::MaxConcurrentDownloads
+            .apply(&mut config, "3")
+            .unwrap();
+        SettingField::DownloadRateLimit
+            .apply(&mut config, "500k")
+            .unwrap();
+        SettingField::DownloadRateLimit
+            .apply(&mut config, "")
+            .unwrap();
+        SettingField::MinFreeSpace.apply(&mut config, "0").unwrap();
+        SettingField::ScanInterval.apply(&mut config, "5").unwrap();
+        SettingField::DurationTolerance
+            .apply(&mut config, "10")
+            .unwrap();
+        SettingField::FilenameTemplate
+            .apply(&mut config, " {date} - {title} ")
+            .unwrap();
+
+        assert_eq!(config.storage_paths, ["/mnt/a", "~/Videos"]);
+        assert_eq!(config.max_concurrent_downloads, 3);
+        assert_eq!(config.min_free_space_mb, 0);
+        assert_eq!(config.min_scan_interval_secs, 5);
+        assert_eq!(config.duration_tolerance_secs, 10);
+        assert_eq!(config.filename_template, "{date} - {title}");
+        assert_eq!(config.download_rate_limit, None);
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_values() {
+        let mut config = Config::default();
+
+        assert!(SettingField::StoragePaths
+            .apply(&mut config, "relative/path")
+            .is_err());
+        assert!(SettingField::ScanLimit.apply(&mut config, "0").is_err());
+        assert!(SettingField::MaxConcurrentDownloads
+            .apply(&mut config, "abc")
+            .is_err());
+        assert!(SettingField::DownloadRateLimit
+            .apply(&mut config, "fast")
+            .is_err());
+        assert!(SettingField::CookiesFile
+            .apply(&mut config, "/nonexistent/cookies.txt")
+            .is_err());
+        assert!(SettingField::VideoExtensions
+            .apply(&mut config, " , ")
+            .is_err());
+        assert!(SettingField::MonitorInterval
+            .apply(&mut config, "2")
+            .is_err());
+        assert!(SettingField::DurationTolerance
+            .apply(&mut config, "0")
+            .is_err());
+        assert!(SettingField::ScanInterval
+            .apply(&mut config, "120")
+            .is_err());
+        assert!(SettingField::FilenameTemplate
+            .apply(&mut config, "{titre}")
+            .is_err());
+
+        // La configuration n'est pas modifiée par une valeur invalide
+        assert_eq!(config.scan_limit, Config::default().scan_limit);
+    }
+}
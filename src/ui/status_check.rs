@@ -0,0 +1,211 @@
+use crate::notifications::Notification;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use std::collections::HashMap;
+
+use super::theme::Theme;
+use super::{save_channels, NDownloaderApp, VideoInfo, VideoStatus};
+
+/// Applique le résultat d'une vérification aux vidéos affichées, sans toucher à celles
+/// en cours de téléchargement. Disque de stockage démonté (`storage_unavailable`): une
+/// vidéo introuvable y est peut-être, elle reste téléchargée.
+/// Retourne le nombre de vidéos trouvées et perdues.
+fn apply_statuses(
+    videos: &mut [VideoInfo],
+    downloaded: &HashMap<String, bool>,
+    storage_unavailable: bool,
+) -> (usize, usize) {
+    let (mut found, mut lost) = (0, 0);
+    for video in videos {
+        let Some(&is_downloaded) = downloaded.get(&video.url) else {
+            continue;
+        };
+        match (&video.status, is_downloaded) {
+            (VideoStatus::NotDownloaded, true) => {
+                video.status = VideoStatus::Downloaded;
+                found += 1;
+            }
+            (VideoStatus::Downloaded, false) if !storage_unavailable => {
+                video.status = VideoStatus::NotDownloaded;
+                lost += 1;
+            }
+            _ => {}
+        }
+    }
+    (found, lost)
+}
+
+impl NDownloaderApp {
+    /// Revérifie sur les disques le statut des vidéos affichées, sans rescanner la chaîne
+    fn check_download_statuses(&mut self, cx: &mut Context<Self>) {
+        if self.loading || self.status_check.is_some() {
+            return;
+        }
+        let Some(channel) = self
+            .selected_channel
+            .and_then(|index| self.channels.get(index))
+        else {
+            return;
+        };
+        let channel_url = channel.url.clone();
+        let channel_name = channel.name.clone();
+        let videos: Vec<_> = self
+            .videos
+            .iter()
+            .filter(|video| video.status != VideoStatus::Downloading)
+            .map(|video| (video.url.clone(), video.id.clone(), video.duration))
+            .collect();
+        let scanner = self.scanner.clone();
+        scanner.forget_probes(&channel_name);
+
+        self.status_check = Some(cx.spawn(async move |this, cx| {
+            // Parcours des disques, ffprobe et points de montage (un montage réseau figé
+            // peut bloquer) hors du thread de l'interface, en un seul passage
+            let (result, storage_unavailable) = cx
+                .background_executor()
+                .spawn(async move {
+                    let found = scanner.find_downloaded(&channel_name, &videos).await;
+                    let downloaded = found.map(|found| {
+                        videos
+                            .into_iter()
+                            .map(|(url, _, _)| {
+                                let is_downloaded = found.contains(&url);
+                                (url, is_downloaded)
+                            })
+                            .collect::<HashMap<_, _>>()
+                    });
+                    (downloaded, !scanner.unavailable_storage_paths().is_empty())
+                })
+                .await;
+            let (downloaded, error) = match result {
+                Ok(downloaded) => (downloaded, None),
+                Err(error) => (HashMap::new(), Some(error)),
+            };
+
+            this.update(cx, |this, cx| {
+                this.status_check = None;
+                if let Some(error) = error {
+                    tracing::warn!("Download status check failed: {}", error);
+                    this.report_error(&error);
+                    Notification::error("Vérification impossible", &error.to_string());
+                    cx.notify();
+                    return;
+                }
+                // Chaîne quittée entre-temps: les vidéos affichées ne sont plus les mêmes
+                let still_shown = this
+                    .selected_channel
+                    .and_then(|index| this.channels.get(index))
+                    .is_some_and(|channel| channel.url == channel_url);
+                if !still_shown {
+                    return;
+                }
+
+                let (found, lost) =
+                    apply_statuses(&mut this.videos, &downloaded, storage_unavailable);
+                if let Some(summary) = this
+                    .channels
+                    .iter_mut()
+                    .find(|channel| channel.url == channel_url)
+                    .and_then(|channel| channel.download_summary.as_mut())
+                    .filter(|_| found + lost > 0)
+                {
+                    summary.downloaded = (summary.downloaded + found)
+                        .saturating_sub(lost)
+                        .min(summary.total);
+                    save_channels(&this.channels);
+                }
+                Notification::info(
+                    "Statuts vérifiés",
+                    &format!("{found} vidéo(s) retrouvée(s), {lost} manquante(s)"),
+                );
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    /// Bouton « Vérifier les fichiers », désactivé pendant un scan ou une vérification
+    pub(super) fn render_status_check_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+        let busy = self.loading || self.status_check.is_some();
+        let label = if self.status_check.is_some() {
+            "Vérification..."
+        } else {
+            "Vérifier les fichiers"
+        };
+        div()
+            .px_4()
+            .py_2()
+            .bg(rgb(theme.surface))
+            .rounded_md()
+            .when(busy, |this| this.opacity(0.5))
+            .when(!busy, |this| {
+                this.cursor_pointer()
+                    .hover(|style| style.bg(rgb(theme.border)))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(|this, _event, _window, cx| {
+                            this.check_download_statuses(cx);
+                        }),
+                    )
+            })
+            .child(
+                div()
+                    .text_color(rgb(theme.text))
+                    .text_size(px(14.0))
+                    .child(label),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support;
+    use super::*;
+
+    fn video(title: &str, status: VideoStatus) -> VideoInfo {
+        VideoInfo {
+            status,
+            ..test_support::video(title, None, None)
+        }
+    }
+
+    #[test]
+    fn test_apply_statuses() {
+        let mut videos = vec![
+            video("moved-in", VideoStatus::NotDownloaded),
+            video("deleted", VideoStatus::Downloaded),
+            video("unchanged", VideoStatus::Downloaded),
+            video("running", VideoStatus::Downloading),
+            video("unchecked", VideoStatus::NotDownloaded),
+        ];
+        let downloaded = HashMap::from(
+            [
+                ("moved-in", true),
+                ("deleted", false),
+                ("unchanged", true),
+                ("running", false),
+            ]
+            .map(|(title, downloaded)| (format!("https://example.com/{title}"), downloaded)),
+        );
+
+        // Disque démonté: les vidéos introuvables restent téléchargées
+        let mut unmounted = videos.clone();
+        assert_eq!(apply_statuses(&mut unmounted, &downloaded, true), (1, 0));
+        assert_eq!(unmounted[1].status, VideoStatus::Downloaded);
+
+        assert_eq!(apply_statuses(&mut videos, &downloaded, false), (1, 1));
+        let statuses: Vec<_> = videos.iter().map(|video| video.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            [
+                VideoStatus::Downloaded,
+                VideoStatus::NotDownloaded,
+                VideoStatus::Downloaded,
+                VideoStatus::Downloading,
+                VideoStatus::NotDownloaded,
+            ]
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::notifications::Notification;
+use gpui::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::NDownloaderApp;
+
+/// Intervalle entre deux vérifications des disques de stockage
+const STORAGE_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Disques devenus indisponibles et disques revenus depuis la dernière vérification
+fn storage_changes(
+    previous: &HashSet<String>,
+    current: &HashSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    let mut lost: Vec<_> = current.difference(previous).cloned().collect();
+    let mut restored: Vec<_> = previous.difference(current).cloned().collect();
+    lost.sort();
+    restored.sort();
+    (lost, restored)
+}
+
+impl NDownloaderApp {
+    /// Surveille les disques de stockage pour prévenir quand l'un d'eux est démonté
+    /// en cours de session, puis quand il revient. Les disques déjà absents au
+    /// lancement (chemins par défaut sans disque branché) ne sont pas signalés.
+    pub(super) fn watch_storage(&mut self, cx: &mut Context<Self>) {
+        self.reset_unavailable_storage();
+
+        cx.spawn(async move |this, cx| loop {
+            cx.background_executor().timer(STORAGE_CHECK_INTERVAL).await;
+            let Ok(scanner) = this.update(cx, |this, _| this.scanner.clone()) else {
+                break;
+            };
+            // Un point de montage réseau figé peut bloquer: vérifier hors du thread de l'interface
+            let checked = scanner.clone();
+            let current: HashSet<String> = cx
+                .background_executor()
+                .spawn(async move { checked.unavailable_storage_paths() })
+                .await
+                .into_iter()
+                .collect();
+            let updated = this.update(cx, |this, cx| {
+                // Configuration rechargée pendant la vérification: résultat périmé
+                if Arc::ptr_eq(&this.scanner, &scanner) {
+                    this.apply_storage_check(current, cx);
+                }
+            });
+            if updated.is_err() {
+                break;
+            }
+        })
+        .detach();
+    }
+
+    /// Reprend l'état des disques comme référence, après un changement de configuration
+    pub(super) fn reset_unavailable_storage(&mut self) {
+        self.unavailable_storage = self
+            .scanner
+            .unavailable_storage_paths()
+            .into_iter()
+            .collect();
+    }
+
+    fn apply_storage_check(&mut self, current: HashSet<String>, cx: &mut Context<Self>) {
+        let (lost, restored) = storage_changes(&self.unavailable_storage, &current);
+        for path in &lost {
+            tracing::warn!("Storage path became unavailable: {}", path);
+            Notification::error(
+                "Disque de stockage indisponible",
+                &format!(
+                    "{path} n'est plus accessible: il est ignoré pour les nouveaux téléchargements jusqu'à son retour"
+                ),
+            );
+        }
+        for path in &restored {
+            tracing::info!("Storage path is available again: {}", path);
+            Notification::info(
+                "Disque de stockage de retour",
+                &format!("{path} est de nouveau utilisé. Actualisez la chaîne pour revoir l'état de ses vidéos"),
+            );
+        }
+        if !lost.is_empty() || !restored.is_empty() {
+            self.unavailable_storage = current;
+            cx.notify();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_changes() {
+        let set = |paths: &[&str]| paths.iter().map(|path| path.to_string()).collect();
+        let previous = set(&["/run/mount/a", "/run/mount/b"]);
+        let current = set(&["/run/mount/b", "/run/mount/d", "/run/mount/c"]);
+
+        assert_eq!(
+            storage_changes(&previous, &current),
+            (
+                vec!["/run/mount/c".to_string(), "/run/mount/d".to_string()],
+                vec!["/run/mount/a".to_string()]
+            )
+        );
+        assert_eq!(storage_changes(&current, &current), (vec![], vec![]));
+    }
+}
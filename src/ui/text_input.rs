@@ -1,30 +1,240 @@
+use gpui::prelude::FluentBuilder;
 use gpui::*;
+use std::ops::Range;
+
+use super::theme::Theme;
 
 type OnEnterCallback = Box<dyn Fn(&str) + 'static>;
+type Validator = Box<dyn Fn(&str) -> bool + 'static>;
+
+/// Nombre maximum d'étapes gardées pour Ctrl+Z
+const MAX_UNDO_STEPS: usize = 100;
+
+/// Texte et curseur d'un champ, restaurés par Ctrl+Z / Ctrl+Y
+#[derive(Debug, Clone, PartialEq)]
+struct EditState {
+    value: String,
+    cursor: usize,
+}
+
+/// Historique des modifications d'un champ
+#[derive(Debug, Default)]
+struct EditHistory {
+    undo: Vec<EditState>,
+    redo: Vec<EditState>,
+    /// La dernière étape est une suite de frappes, que la suivante vient compléter
+    typing: bool,
+}
+
+impl EditHistory {
+    /// Note l'état précédant une modification. Les frappes d'un caractère qui se suivent
+    /// forment une seule étape, pour ne pas annuler lettre par lettre.
+    fn record(&mut self, before: EditState, typing: bool) {
+        self.redo.clear();
+        if typing && self.typing {
+            return;
+        }
+        self.typing = typing;
+        self.undo.push(before);
+        if self.undo.len() > MAX_UNDO_STEPS {
+            self.undo.remove(0);
+        }
+    }
+
+    fn undo(&mut self, current: EditState) -> Option<EditState> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        self.typing = false;
+        Some(previous)
+    }
+
+    fn redo(&mut self, current: EditState) -> Option<EditState> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        self.typing = false;
+        Some(next)
+    }
+
+    /// Un déplacement du curseur termine la suite de frappes en cours
+    fn break_typing(&mut self) {
+        self.typing = false;
+    }
+}
 
 pub struct TextInput {
     focus_handle: FocusHandle,
-    value: SharedString,
+    value: String,
+    cursor: usize, // Position du curseur en octets dans `value`
+    selection_anchor: Option<usize>,
     placeholder: SharedString,
     on_enter: Option<OnEnterCallback>,
+    /// Nombre maximum de caractères, au-delà duquel la saisie et le collage sont coupés
+    max_length: Option<usize>,
+    history: EditHistory,
 }
 
 impl TextInput {
     pub fn new(cx: &mut App) -> Self {
         Self {
             focus_handle: cx.focus_handle(),
-            value: "".into(),
+            value: String::new(),
+            cursor: 0,
+            selection_anchor: None,
             placeholder: "".into(),
             on_enter: None,
+            max_length: None,
+            history: EditHistory::default(),
         }
     }
 
     pub fn value(&self) -> String {
-        self.value.to_string()
+        self.value.clone()
     }
 
     pub fn clear(&mut self) {
-        self.value = "".into();
+        self.value.clear();
+        self.cursor = 0;
+        self.selection_anchor = None;
+    }
+
+    fn state(&self) -> EditState {
+        EditState {
+            value: self.value.clone(),
+            cursor: self.cursor,
+        }
+    }
+
+    fn restore(&mut self, state: EditState) {
+        self.value = state.value;
+        self.cursor = state.cursor;
+        self.selection_anchor = None;
+    }
+
+    /// Applique une modification, notée dans l'historique si elle change le texte
+    fn edit(&mut self, typing: bool, change: impl FnOnce(&mut Self)) {
+        let before = self.state();
+        change(self);
+        if self.value != before.value {
+            self.history.record(before, typing);
+        }
+    }
+
+    fn undo(&mut self) {
+        if let Some(state) = self.history.undo(self.state()) {
+            self.restore(state);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(state) = self.history.redo(self.state()) {
+            self.restore(state);
+        }
+    }
+
+    /// Plage sélectionnée (en octets), si elle n'est pas vide
+    fn selection_range(&self) -> Option<Range<usize>> {
+        let anchor = self.selection_anchor?;
+        (anchor != self.cursor).then(|| anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        self.selection_range()
+            .map(|range| self.value[range].to_string())
+    }
+
+    /// Supprime la sélection active, retourne `true` si du texte a été supprimé
+    fn delete_selection(&mut self) -> bool {
+        let range = self.selection_range();
+        self.selection_anchor = None;
+        let Some(range) = range else {
+            return false;
+        };
+        self.cursor = range.start;
+        self.value.replace_range(range, "");
+        true
+    }
+
+    /// Insère du texte à la position du curseur, en remplaçant la sélection.
+    /// Le texte est coupé s'il dépasse la longueur maximale.
+    fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let text = match self.max_length {
+            Some(max) => truncate_chars(text, max.saturating_sub(self.value.chars().count())),
+            None => text,
+        };
+        self.value.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    /// Supprime la sélection ou le caractère avant le curseur
+    fn backspace(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
+        let start = previous_char_boundary(&self.value, self.cursor);
+        self.value.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    /// Supprime la sélection ou le caractère après le curseur
+    fn delete(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
+        let end = next_char_boundary(&self.value, self.cursor);
+        self.value.replace_range(self.cursor..end, "");
+    }
+
+    /// Supprime la sélection ou le mot avant le curseur (Ctrl+Retour arrière)
+    fn delete_word_before(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
+        let start = previous_word_boundary(&self.value, self.cursor);
+        self.value.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    /// Supprime la sélection ou le mot après le curseur (Ctrl+Suppr)
+    fn delete_word_after(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
+        let end = next_word_boundary(&self.value, self.cursor);
+        self.value.replace_range(self.cursor..end, "");
+    }
+
+    /// Déplace le curseur, en étendant la sélection si `selecting`
+    fn move_to(&mut self, offset: usize, selecting: bool) {
+        self.history.break_typing();
+        if selecting {
+            self.selection_anchor.get_or_insert(self.cursor);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor = offset;
+    }
+
+    fn move_left(&mut self, selecting: bool) {
+        self.move_to(previous_char_boundary(&self.value, self.cursor), selecting);
+    }
+
+    fn move_right(&mut self, selecting: bool) {
+        self.move_to(next_char_boundary(&self.value, self.cursor), selecting);
+    }
+
+    fn move_home(&mut self, selecting: bool) {
+        self.move_to(0, selecting);
+    }
+
+    fn move_end(&mut self, selecting: bool) {
+        self.move_to(self.value.len(), selecting);
+    }
+
+    fn select_all(&mut self) {
+        self.history.break_typing();
+        self.selection_anchor = Some(0);
+        self.cursor = self.value.len();
     }
 
     pub fn on_enter<F>(mut self, callback: F) -> Self
@@ -36,6 +246,85 @@ impl TextInput {
     }
 }
 
+/// Texte collé ramené sur une ligne: chaque retour à la ligne devient un espace (une liste
+/// de liens reste séparable), tabulations et autres caractères de contrôle sont retirés
+fn single_line(text: &str) -> String {
+    text.split(['\r', '\n'])
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('\t', " ")
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect()
+}
+
+/// Les mots sont séparés par les espaces et la ponctuation (`/`, `?`, `.`, `=`...)
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Début du mot avant `offset`, en passant d'abord les séparateurs qui le suivent
+fn previous_word_boundary(text: &str, offset: usize) -> usize {
+    let mut chars = text[..offset].char_indices().rev().peekable();
+    while chars.next_if(|(_, c)| !is_word_char(*c)).is_some() {}
+    while chars.next_if(|(_, c)| is_word_char(*c)).is_some() {}
+    chars.peek().map_or(0, |(index, c)| index + c.len_utf8())
+}
+
+/// Fin du mot après `offset`, en passant d'abord les séparateurs qui le précèdent
+fn next_word_boundary(text: &str, offset: usize) -> usize {
+    let mut chars = text[offset..].char_indices().peekable();
+    while chars.next_if(|(_, c)| !is_word_char(*c)).is_some() {}
+    while chars.next_if(|(_, c)| is_word_char(*c)).is_some() {}
+    chars.peek().map_or(text.len(), |(index, _)| offset + index)
+}
+
+/// Début de `text` limité à `max` caractères
+fn truncate_chars(text: &str, max: usize) -> &str {
+    text.char_indices()
+        .nth(max)
+        .map_or(text, |(index, _)| &text[..index])
+}
+
+fn previous_char_boundary(text: &str, offset: usize) -> usize {
+    text[..offset]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(index, _)| index)
+}
+
+fn next_char_boundary(text: &str, offset: usize) -> usize {
+    text[offset..]
+        .chars()
+        .next()
+        .map_or(offset, |c| offset + c.len_utf8())
+}
+
+/// Texte produit par une frappe, en tenant compte de Shift et de la disposition clavier
+fn typed_text(keystroke: &Keystroke) -> Option<String> {
+    if keystroke.modifiers.control || keystroke.modifiers.platform {
+        return None;
+    }
+
+    // Caractère réellement produit (ex: Shift+2 → "@" sur un clavier US)
+    if let Some(key_char) = keystroke.key_char.as_deref() {
+        return (!key_char.is_empty() && !key_char.chars().any(char::is_control))
+            .then(|| key_char.to_string());
+    }
+
+    // Repli sur le nom de la touche quand aucun caractère n'est fourni
+    if keystroke.key == "space" {
+        return Some(" ".to_string());
+    }
+    let mut chars = keystroke.key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if keystroke.modifiers.shift => Some(c.to_uppercase().collect()),
+        (Some(c), None) => Some(c.to_string()),
+        _ => None,
+    }
+}
+
 impl Focusable for TextInput {
     fn focus_handle(&self, _cx: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -44,15 +333,39 @@ impl Focusable for TextInput {
 
 pub struct TextInputView {
     input: TextInput,
+    /// Afficher « longueur/maximum » à droite du champ
+    show_counter: bool,
+    /// Vérifie le texte à chaque modification
+    validator: Option<Validator>,
+    valid: bool,
 }
 
 impl TextInputView {
     pub fn new(cx: &mut App) -> Self {
         Self {
             input: TextInput::new(cx),
+            show_counter: false,
+            validator: None,
+            valid: true,
         }
     }
 
+    /// Limite le nombre de caractères, en coupant le texte déjà présent si besoin
+    pub fn max_length(mut self, max: usize) -> Self {
+        let length = truncate_chars(&self.input.value, max).len();
+        self.input.value.truncate(length);
+        self.input.cursor = self.input.cursor.min(length);
+        self.input.selection_anchor = None;
+        self.input.max_length = Some(max);
+        self
+    }
+
+    /// Affiche le nombre de caractères saisis (avec `max_length`)
+    pub fn show_counter(mut self) -> Self {
+        self.show_counter = true;
+        self
+    }
+
     pub fn placeholder(mut self, text: impl Into<SharedString>) -> Self {
         self.input.placeholder = text.into();
         self
@@ -66,12 +379,67 @@ impl TextInputView {
         self
     }
 
+    /// Texte initial du champ, curseur placé à la fin
+    pub fn default_value(mut self, value: impl Into<String>) -> Self {
+        self.input.value = value.into();
+        self.input.cursor = self.input.value.len();
+        self
+    }
+
+    /// Vérifie le texte à chaque modification. Un champ sans validateur est toujours valide.
+    pub fn validator<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.validator = Some(Box::new(validator));
+        self.validate();
+        self
+    }
+
     pub fn value(&self) -> String {
         self.input.value()
     }
 
     pub fn clear(&mut self) {
-        self.input.clear();
+        self.input.edit(false, TextInput::clear);
+        self.validate();
+    }
+
+    /// Remplace le texte du champ, curseur placé à la fin
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        let value = value.into();
+        self.input.edit(false, |input| {
+            input.clear();
+            input.insert(&value);
+        });
+        self.validate();
+    }
+
+    /// Donne le focus au champ, curseur à la fin et sans sélection
+    pub fn focus(&mut self, window: &mut Window) {
+        self.input.move_end(false);
+        window.focus(&self.input.focus_handle);
+    }
+
+    /// Résultat du validateur pour le texte actuel
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+
+    /// Bordure à donner au champ: rouge quand le texte est invalide
+    pub fn border_color(&self, theme: &Theme) -> Rgba {
+        if self.valid {
+            rgb(theme.border)
+        } else {
+            rgb(theme.error)
+        }
+    }
+
+    fn validate(&mut self) {
+        self.valid = self
+            .validator
+            .as_ref()
+            .is_none_or(|validator| validator(&self.input.value));
     }
 }
 
@@ -84,22 +452,56 @@ impl Focusable for TextInputView {
 impl Render for TextInputView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let focused = self.input.focus_handle.is_focused(window);
+        let theme = Theme::get(cx);
+        let counter = self
+            .input
+            .max_length
+            .filter(|_| self.show_counter)
+            .map(|max| (self.input.value.chars().count(), max));
 
         div()
             .id("text-input")
             .track_focus(&self.input.focus_handle)
             .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
-                // Gestion de Ctrl+V pour paste
-                if event.keystroke.modifiers.control && event.keystroke.key == "v" {
-                    // Essayer de lire le presse-papier
-                    if let Some(clipboard_item) = cx.read_from_clipboard() {
-                        if let Some(text) = clipboard_item.text() {
-                            let mut new_value = this.input.value.to_string();
-                            new_value.push_str(&text);
-                            this.input.value = new_value.into();
-                            cx.notify();
+                let modifiers = &event.keystroke.modifiers;
+
+                // Raccourcis Ctrl: copier, couper, coller, tout sélectionner, annuler
+                if modifiers.control {
+                    match event.keystroke.key.as_str() {
+                        "a" => this.input.select_all(),
+                        "z" if modifiers.shift => this.input.redo(),
+                        "z" => this.input.undo(),
+                        "y" => this.input.redo(),
+                        "backspace" => this.input.edit(false, TextInput::delete_word_before),
+                        "delete" => this.input.edit(false, TextInput::delete_word_after),
+                        "c" => {
+                            if let Some(text) = this.input.selected_text() {
+                                cx.write_to_clipboard(ClipboardItem::new_string(text));
+                            }
+                            return;
+                        }
+                        "x" => {
+                            let Some(text) = this.input.selected_text() else {
+                                return;
+                            };
+                            cx.write_to_clipboard(ClipboardItem::new_string(text));
+                            this.input.edit(false, |input| {
+                                input.delete_selection();
+                            });
                         }
+                        "v" => {
+                            // Collé au curseur, à la place de la sélection
+                            let Some(text) = cx.read_from_clipboard().and_then(|item| item.text())
+                            else {
+                                return;
+                            };
+                            this.input
+                                .edit(false, |input| input.insert(&single_line(&text)));
+                        }
+                        _ => return,
                     }
+                    this.validate();
+                    cx.notify();
                     return;
                 }
 
@@ -113,22 +515,23 @@ impl Render for TextInputView {
                 }
 
                 // Gestion des caractères normaux
-                if event.keystroke.key.as_str().len() == 1 && !event.keystroke.modifiers.control {
-                    let mut new_value = this.input.value.to_string();
-                    new_value.push_str(&event.keystroke.key);
-                    this.input.value = new_value.into();
-                    cx.notify();
-                } else if event.keystroke.key == "backspace" {
-                    let mut new_value = this.input.value.to_string();
-                    new_value.pop();
-                    this.input.value = new_value.into();
-                    cx.notify();
-                } else if event.keystroke.key == "space" {
-                    let mut new_value = this.input.value.to_string();
-                    new_value.push(' ');
-                    this.input.value = new_value.into();
-                    cx.notify();
+                let selecting = modifiers.shift;
+                if let Some(text) = typed_text(&event.keystroke) {
+                    let typing = text.chars().count() == 1;
+                    this.input.edit(typing, |input| input.insert(&text));
+                } else {
+                    match event.keystroke.key.as_str() {
+                        "backspace" => this.input.edit(false, TextInput::backspace),
+                        "delete" => this.input.edit(false, TextInput::delete),
+                        "left" => this.input.move_left(selecting),
+                        "right" => this.input.move_right(selecting),
+                        "home" => this.input.move_home(selecting),
+                        "end" => this.input.move_end(selecting),
+                        _ => return,
+                    }
                 }
+                this.validate();
+                cx.notify();
             }))
             .flex()
             .items_center()
@@ -137,16 +540,195 @@ impl Render for TextInputView {
             .px_3()
             .child(if self.input.value.is_empty() {
                 div()
+                    .flex()
+                    .items_center()
                     .text_color(rgb(0x888888))
+                    .when(focused, |this| this.child(caret(&theme)))
                     .child(self.input.placeholder.clone())
             } else {
+                let value = &self.input.value;
+                let cursor = self.input.cursor;
+                let selection = self.input.selection_range().unwrap_or(cursor..cursor);
+                let caret_at_start = cursor == selection.start;
+
                 div()
+                    .flex()
+                    .items_center()
                     .text_color(if focused {
-                        rgb(0xffffff)
+                        rgb(theme.text)
                     } else {
-                        rgb(0xcccccc)
+                        rgb(theme.text_muted)
                     })
-                    .child(self.input.value.clone())
+                    .child(SharedString::from(value[..selection.start].to_string()))
+                    .when(focused && caret_at_start, |this| this.child(caret(&theme)))
+                    .when(!selection.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .bg(rgb(theme.primary))
+                                .child(SharedString::from(value[selection.clone()].to_string())),
+                        )
+                    })
+                    .when(focused && !caret_at_start, |this| this.child(caret(&theme)))
+                    .child(SharedString::from(value[selection.end..].to_string()))
+            })
+            .when_some(counter, |this, (length, max)| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .ml_auto()
+                        .pl_2()
+                        .text_size(px(12.0))
+                        .text_color(if length >= max {
+                            rgb(theme.warning)
+                        } else {
+                            rgb(theme.text_muted)
+                        })
+                        .child(format!("{length}/{max}")),
+                )
             })
     }
 }
+
+/// Curseur d'insertion affiché quand le champ a le focus
+fn caret(theme: &Theme) -> Div {
+    div().w(px(1.0)).h(px(18.0)).bg(rgb(theme.accent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keystroke(key: &str, key_char: Option<&str>, modifiers: Modifiers) -> Keystroke {
+        Keystroke {
+            modifiers,
+            key: key.to_string(),
+            key_char: key_char.map(str::to_string),
+        }
+    }
+
+    fn state(value: &str) -> EditState {
+        EditState {
+            value: value.to_string(),
+            cursor: value.len(),
+        }
+    }
+
+    #[test]
+    fn test_history_groups_typing() {
+        let mut history = EditHistory::default();
+        // « abc » tapé lettre par lettre, puis un collage
+        history.record(state(""), true);
+        history.record(state("a"), true);
+        history.record(state("ab"), true);
+        history.record(state("abc"), false);
+
+        assert_eq!(history.undo(state("abc/def")), Some(state("abc")));
+        assert_eq!(history.undo(state("abc")), Some(state("")));
+        assert_eq!(history.undo(state("")), None);
+        assert_eq!(history.redo(state("")), Some(state("abc")));
+        assert_eq!(history.redo(state("abc")), Some(state("abc/def")));
+        assert_eq!(history.redo(state("abc/def")), None);
+    }
+
+    #[test]
+    fn test_history_breaks_and_limits() {
+        let mut history = EditHistory::default();
+        history.record(state(""), true);
+        // Un déplacement du curseur commence une nouvelle étape
+        history.break_typing();
+        history.record(state("a"), true);
+        assert_eq!(history.undo(state("ab")), Some(state("a")));
+
+        // Une nouvelle modification efface ce qui pouvait être rétabli
+        history.record(state("a"), true);
+        assert_eq!(history.redo(state("ax")), None);
+
+        for index in 0..MAX_UNDO_STEPS + 10 {
+            history.record(state(&index.to_string()), false);
+        }
+        assert_eq!(history.undo.len(), MAX_UNDO_STEPS);
+    }
+
+    #[test]
+    fn test_word_boundaries_in_url() {
+        let url = "https://www.youtube.com/watch?v=abc";
+        // Ctrl+Retour arrière depuis la fin: « abc », puis « v= », puis « watch? »
+        let start = previous_word_boundary(url, url.len());
+        assert_eq!(&url[..start], "https://www.youtube.com/watch?v=");
+        let start = previous_word_boundary(url, start);
+        assert_eq!(&url[..start], "https://www.youtube.com/watch?");
+        let start = previous_word_boundary(url, start);
+        assert_eq!(&url[..start], "https://www.youtube.com/");
+        let start = previous_word_boundary(url, start);
+        assert_eq!(&url[..start], "https://www.youtube.");
+        assert_eq!(previous_word_boundary(url, 0), 0);
+
+        // Ctrl+Suppr depuis le début du chemin: « /watch », puis « ?v »
+        let offset = "https://www.youtube.com".len();
+        let end = next_word_boundary(url, offset);
+        assert_eq!(&url[end..], "?v=abc");
+        let end = next_word_boundary(url, end);
+        assert_eq!(&url[end..], "=abc");
+        assert_eq!(next_word_boundary(url, url.len()), url.len());
+
+        // Caractères multi-octets
+        assert_eq!(previous_word_boundary("vidéo été", "vidéo été".len()), 7);
+        assert_eq!(next_word_boundary("vidéo été", 0), 6);
+    }
+
+    #[test]
+    fn test_single_line() {
+        assert_eq!(single_line("https://youtu.be/abc"), "https://youtu.be/abc");
+        assert_eq!(
+            single_line("https://youtu.be/abc\n"),
+            "https://youtu.be/abc"
+        );
+        assert_eq!(
+            single_line("https://youtu.be/a\r\nhttps://youtu.be/b\n\nhttps://youtu.be/c"),
+            "https://youtu.be/a https://youtu.be/b https://youtu.be/c"
+        );
+        assert_eq!(single_line("nom\tdu\u{7}fichier"), "nom dufichier");
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("abc", 5), "abc");
+        assert_eq!(truncate_chars("abc", 2), "ab");
+        assert_eq!(truncate_chars("abc", 0), "");
+        // Coupé entre deux caractères, jamais au milieu d'un caractère multi-octets
+        assert_eq!(truncate_chars("éèà", 2), "éè");
+    }
+
+    #[test]
+    fn test_typed_text_uses_key_char() {
+        let at = keystroke("2", Some("@"), Modifiers::shift());
+        assert_eq!(typed_text(&at), Some("@".to_string()));
+
+        let upper = keystroke("a", Some("A"), Modifiers::shift());
+        assert_eq!(typed_text(&upper), Some("A".to_string()));
+
+        let underscore = keystroke("-", Some("_"), Modifiers::shift());
+        assert_eq!(typed_text(&underscore), Some("_".to_string()));
+    }
+
+    #[test]
+    fn test_typed_text_fallback_without_key_char() {
+        let upper = keystroke("a", None, Modifiers::shift());
+        assert_eq!(typed_text(&upper), Some("A".to_string()));
+
+        let space = keystroke("space", None, Modifiers::none());
+        assert_eq!(typed_text(&space), Some(" ".to_string()));
+    }
+
+    #[test]
+    fn test_typed_text_ignores_shortcuts_and_named_keys() {
+        let copy = keystroke("c", Some("c"), Modifiers::control());
+        assert_eq!(typed_text(&copy), None);
+
+        let left = keystroke("left", None, Modifiers::shift());
+        assert_eq!(typed_text(&left), None);
+
+        let enter = keystroke("enter", Some("\n"), Modifiers::none());
+        assert_eq!(typed_text(&enter), None);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::config::ThemeMode;
+use gpui::{App, Global};
+
+/// Palette de couleurs de l'interface, partagée via un global gpui
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    /// Fond de la fenêtre
+    pub background: u32,
+    /// Panneaux et boutons secondaires
+    pub surface: u32,
+    /// Champs de saisie et lignes de liste
+    pub element: u32,
+    /// Bordures et survol
+    pub border: u32,
+    pub text: u32,
+    pub text_muted: u32,
+    /// Texte posé sur une couleur d'accentuation (boutons, bandeaux)
+    pub text_on_accent: u32,
+    pub accent: u32,
+    pub info: u32,
+    pub primary: u32,
+    pub error: u32,
+    pub warning: u32,
+    pub success: u32,
+    pub highlight: u32,
+}
+
+impl Global for Theme {}
+
+impl Theme {
+    /// Palette Nord sombre (thème par défaut)
+    pub const DARK: Theme = Theme {
+        background: 0x2e3440, // NORD0
+        surface: 0x3b4252,    // NORD1
+        element: 0x434c5e,    // NORD2
+        border: 0x4c566a,     // NORD3
+        text: 0xeceff4,       // NORD6
+        text_muted: 0xd8dee9, // NORD4
+        text_on_accent: 0xeceff4,
+        accent: 0x88c0d0,    // NORD8
+        info: 0x81a1c1,      // NORD9
+        primary: 0x5e81ac,   // NORD10
+        error: 0xbf616a,     // NORD11
+        warning: 0xebcb8b,   // NORD13
+        success: 0xa3be8c,   // NORD14
+        highlight: 0xb48ead, // NORD15
+    };
+
+    /// Palette Nord claire (Snow Storm en fond, couleurs assombries pour le contraste)
+    pub const LIGHT: Theme = Theme {
+        background: 0xeceff4,
+        surface: 0xe5e9f0,
+        element: 0xd8dee9,
+        border: 0xc2cad8,
+        text: 0x2e3440,
+        text_muted: 0x4c566a,
+        text_on_accent: 0xeceff4,
+        accent: 0x5e81ac,
+        info: 0x4c7399,
+        primary: 0x5e81ac,
+        error: 0xbf616a,
+        warning: 0xb5832a,
+        success: 0x5f8a44,
+        highlight: 0x9a6d94,
+    };
+
+    pub fn for_mode(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Dark => Self::DARK,
+            ThemeMode::Light => Self::LIGHT,
+        }
+    }
+
+    /// Thème actif
+    pub fn get(cx: &App) -> Self {
+        *cx.global::<Theme>()
+    }
+}
@@ -0,0 +1,171 @@
+use crate::notifications::{NotificationEvent, NotificationType};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use std::time::Duration;
+
+use super::theme::Theme;
+use super::NDownloaderApp;
+
+/// Durée d'affichage d'une notification dans la fenêtre
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Nombre maximum de notifications empilées
+const MAX_TOASTS: usize = 4;
+
+/// Notification affichée en bas à droite de la fenêtre
+pub struct Toast {
+    id: usize,
+    event: NotificationEvent,
+    action: Option<ToastAction>,
+}
+
+/// Bouton proposé dans une notification tant qu'elle est affichée
+#[derive(Clone, Copy)]
+pub enum ToastAction {
+    UndoDeleteChannel,
+}
+
+impl ToastAction {
+    fn label(&self) -> &'static str {
+        match self {
+            ToastAction::UndoDeleteChannel => "Annuler",
+        }
+    }
+}
+
+impl NDownloaderApp {
+    pub(super) fn push_toast(&mut self, event: NotificationEvent, cx: &mut Context<Self>) {
+        self.push_action_toast(event, None, cx);
+    }
+
+    /// Affiche une notification avec un bouton d'action, retourne son identifiant
+    pub(super) fn push_action_toast(
+        &mut self,
+        event: NotificationEvent,
+        action: Option<ToastAction>,
+        cx: &mut Context<Self>,
+    ) -> usize {
+        let id = self.next_toast_id;
+        self.next_toast_id += 1;
+
+        self.toasts.push(Toast { id, event, action });
+        if self.toasts.len() > MAX_TOASTS {
+            let evicted = self.toasts.remove(0);
+            self.expire_toast_action(evicted.id);
+        }
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(TOAST_DURATION).await;
+            this.update(cx, |this, cx| this.dismiss_toast(id, cx))
+        })
+        .detach();
+        id
+    }
+
+    pub(super) fn dismiss_toast(&mut self, id: usize, cx: &mut Context<Self>) {
+        self.toasts.retain(|toast| toast.id != id);
+        self.expire_toast_action(id);
+        cx.notify();
+    }
+
+    /// L'action n'est plus proposée une fois la notification disparue
+    fn expire_toast_action(&mut self, id: usize) {
+        if self
+            .deleted_channel
+            .as_ref()
+            .is_some_and(|deleted| deleted.toast_id == id)
+        {
+            self.deleted_channel = None;
+        }
+    }
+
+    fn run_toast_action(&mut self, action: ToastAction, cx: &mut Context<Self>) {
+        match action {
+            ToastAction::UndoDeleteChannel => self.undo_delete_channel(cx),
+        }
+    }
+
+    pub(super) fn render_toasts(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::get(cx);
+
+        div()
+            .absolute()
+            .bottom_4()
+            .right_4()
+            .w(px(340.0))
+            .flex()
+            .flex_col()
+            .gap_2()
+            .children(self.toasts.iter().map(|toast| {
+                let id = toast.id;
+                let action = toast.action;
+                let color = match toast.event.notification_type {
+                    NotificationType::Success => theme.success,
+                    NotificationType::Error => theme.error,
+                    NotificationType::Info => theme.info,
+                };
+
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .px_4()
+                    .py_3()
+                    .bg(rgb(theme.surface))
+                    .border_l_4()
+                    .border_color(rgb(color))
+                    .rounded_md()
+                    .shadow_lg()
+                    .cursor_pointer()
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, _window, cx| {
+                            this.dismiss_toast(id, cx);
+                            cx.stop_propagation();
+                        }),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(color))
+                            .text_size(px(14.0))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child(toast.event.title.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_color(rgb(theme.text_muted))
+                            .text_size(px(12.0))
+                            .child(toast.event.message.clone()),
+                    )
+                    .when_some(action, |this, action| {
+                        this.child(
+                            div().flex().justify_end().child(
+                                div()
+                                    .px_3()
+                                    .py_1()
+                                    .bg(rgb(theme.accent))
+                                    .rounded_sm()
+                                    .cursor_pointer()
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(move |this, _event, _window, cx| {
+                                            // Lancer l'action avant que la fermeture ne l'expire
+                                            this.run_toast_action(action, cx);
+                                            this.dismiss_toast(id, cx);
+                                            cx.stop_propagation();
+                                        }),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_color(rgb(theme.text_on_accent))
+                                            .text_size(px(12.0))
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .child(action.label()),
+                                    ),
+                            ),
+                        )
+                    })
+            }))
+    }
+}
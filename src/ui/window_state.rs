@@ -0,0 +1,177 @@
+use crate::paths;
+use gpui::*;
+use serde::{Deserialize, Serialize};
+
+use super::NDownloaderApp;
+
+/// Taille de la fenêtre au premier lancement
+const DEFAULT_WIDTH: f32 = 1200.0;
+const DEFAULT_HEIGHT: f32 = 800.0;
+
+/// Position et taille de la fenêtre, retrouvées au lancement suivant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    #[serde(default)]
+    maximized: bool,
+}
+
+impl WindowState {
+    pub(super) fn from_window(window: &Window) -> Self {
+        // Fenêtre agrandie ou en plein écran: garder la taille qu'elle retrouvera ensuite
+        let (bounds, maximized) = match window.window_bounds() {
+            WindowBounds::Windowed(bounds) => (bounds, false),
+            WindowBounds::Maximized(bounds) => (bounds, true),
+            WindowBounds::Fullscreen(bounds) => (bounds, false),
+        };
+        Self {
+            x: f32::from(bounds.origin.x),
+            y: f32::from(bounds.origin.y),
+            width: f32::from(bounds.size.width),
+            height: f32::from(bounds.size.height),
+            maximized,
+        }
+    }
+
+    fn load() -> Option<Self> {
+        let content = match std::fs::read_to_string(paths::window_state_file()) {
+            Ok(content) => content,
+            Err(error) => {
+                tracing::debug!("No window state file found: {}", error);
+                return None;
+            }
+        };
+        serde_json::from_str(&content)
+            .map_err(|error| tracing::warn!("Failed to parse window state file: {}", error))
+            .ok()
+    }
+
+    fn save(&self) {
+        match serde_json::to_string_pretty(self) {
+            Ok(content) => {
+                if let Err(error) = std::fs::write(paths::window_state_file(), content) {
+                    tracing::error!("Failed to save window state: {}", error);
+                }
+            }
+            Err(error) => {
+                tracing::error!("Failed to serialize window state: {}", error);
+            }
+        }
+    }
+
+    /// Ramène la fenêtre entière dans l'écran, sans descendre sous la taille minimale
+    fn fit(&self, display: Bounds<Pixels>, min_size: Size<Pixels>) -> Bounds<Pixels> {
+        let (x, width) = fit_axis(
+            self.x,
+            self.width,
+            f32::from(display.origin.x),
+            f32::from(display.size.width),
+            f32::from(min_size.width),
+        );
+        let (y, height) = fit_axis(
+            self.y,
+            self.height,
+            f32::from(display.origin.y),
+            f32::from(display.size.height),
+            f32::from(min_size.height),
+        );
+        Bounds::new(point(px(x), px(y)), size(px(width), px(height)))
+    }
+}
+
+/// Position et longueur d'une fenêtre sur un axe, ramenées dans celles de l'écran.
+/// Une fenêtre plus grande que l'écran est réduite, puis décalée pour y tenir entièrement.
+fn fit_axis(
+    position: f32,
+    length: f32,
+    display_start: f32,
+    display_length: f32,
+    min_length: f32,
+) -> (f32, f32) {
+    let length = length.min(display_length).max(min_length);
+    let position = position
+        .min(display_start + display_length - length)
+        .max(display_start);
+    (position, length)
+}
+
+/// Position de la fenêtre au lancement: celle de la session précédente si elle existe,
+/// sinon centrée sur l'écran principal
+pub fn restored_window_bounds(min_size: Size<Pixels>, cx: &App) -> WindowBounds {
+    let default = || {
+        WindowBounds::Windowed(Bounds::centered(
+            None,
+            size(px(DEFAULT_WIDTH), px(DEFAULT_HEIGHT)),
+            cx,
+        ))
+    };
+    let Some(state) = WindowState::load() else {
+        return default();
+    };
+    let Some(display) = cx.primary_display() else {
+        return default();
+    };
+    let bounds = state.fit(display.bounds(), min_size);
+    if state.maximized {
+        WindowBounds::Maximized(bounds)
+    } else {
+        WindowBounds::Windowed(bounds)
+    }
+}
+
+impl NDownloaderApp {
+    /// Suit la position de la fenêtre pour l'enregistrer quand elle est fermée
+    pub(super) fn track_window_state(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        cx.observe_window_bounds(window, |this, window, _cx| {
+            this.window_state = WindowState::from_window(window);
+        })
+        .detach();
+        window.on_window_should_close(cx, |window, _cx| {
+            WindowState::from_window(window).save();
+            true
+        });
+        // Quitter (cmd-q) ne passe pas par la fermeture de la fenêtre
+        cx.on_app_quit(|this, _cx| {
+            this.window_state.save();
+            async {}
+        })
+        .detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_axis() {
+        // Fenêtre déjà dans l'écran: inchangée
+        assert_eq!(fit_axis(100.0, 1200.0, 0.0, 1920.0, 800.0), (100.0, 1200.0));
+        // Enregistrée sur un écran plus grand: réduite et ramenée dans l'écran
+        assert_eq!(fit_axis(2000.0, 2400.0, 0.0, 1920.0, 800.0), (0.0, 1920.0));
+        // Partiellement hors de l'écran, à droite puis à gauche
+        assert_eq!(
+            fit_axis(1500.0, 1000.0, 0.0, 1920.0, 800.0),
+            (920.0, 1000.0)
+        );
+        assert_eq!(fit_axis(-300.0, 1000.0, 0.0, 1920.0, 800.0), (0.0, 1000.0));
+        // Écran secondaire décalé
+        assert_eq!(
+            fit_axis(100.0, 1000.0, 1920.0, 1280.0, 800.0),
+            (1920.0, 1000.0)
+        );
+        // Jamais sous la taille minimale, même sur un petit écran
+        assert_eq!(fit_axis(0.0, 500.0, 0.0, 700.0, 800.0), (0.0, 800.0));
+    }
+
+    #[test]
+    fn test_window_state_without_maximized() {
+        let state: WindowState =
+            serde_json::from_str(r#"{"x": 10, "y": 20, "width": 1000, "height": 700}"#).unwrap();
+        assert!(!state.maximized);
+        assert_eq!(state.width, 1000.0);
+    }
+}